@@ -7,7 +7,10 @@ use std::io::stdout;
 use crossterm::event::poll;
 use crossterm::{
     cursor::position,
-    event::{read, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        read, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode},
     Result,
@@ -15,7 +18,7 @@ use crossterm::{
 use std::time::Duration;
 
 const HELP: &str = r#"Blocking read()
- - Keyboard, mouse and terminal resize events enabled
+ - Keyboard, mouse, paste and terminal resize events enabled
  - Hit "c" to print current cursor position
  - Use Esc to quit
 "#;
@@ -32,7 +35,7 @@ fn print_events() -> Result<()> {
         }
 
         if let Event::Resize(_, _) = event {
-            let (original_size, new_size) = flush_resize_events(event);
+            let (original_size, new_size) = flush_resize_events(event.clone());
             println!("Resize from: {:?}, to: {:?}", original_size, new_size);
         }
 
@@ -67,13 +70,13 @@ fn main() -> Result<()> {
     enable_raw_mode()?;
 
     let mut stdout = stdout();
-    execute!(stdout, EnableMouseCapture)?;
+    execute!(stdout, EnableBracketedPaste, EnableMouseCapture)?;
 
     if let Err(e) = print_events() {
         println!("Error: {:?}\r", e);
     }
 
-    execute!(stdout, DisableMouseCapture)?;
+    execute!(stdout, DisableBracketedPaste, DisableMouseCapture)?;
 
     disable_raw_mode()
 }
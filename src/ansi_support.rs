@@ -1,4 +1,5 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
 use crossterm_winapi::{ConsoleMode, Handle};
 use parking_lot::Once;
@@ -20,6 +21,7 @@ fn enable_vt_processing() -> Result<()> {
 
     let console_mode = ConsoleMode::from(Handle::current_out_handle()?);
     let old_mode = console_mode.mode()?;
+    ORIGINAL_OUTPUT_MODE.store(u64::from(old_mode), Ordering::SeqCst);
 
     if old_mode & mask == 0 {
         console_mode.set_mode(old_mode | mask)?;
@@ -28,18 +30,35 @@ fn enable_vt_processing() -> Result<()> {
     Ok(())
 }
 
+/// The output console mode before crossterm changed it, `u64::MAX` if it couldn't be read.
+static ORIGINAL_OUTPUT_MODE: AtomicU64 = AtomicU64::new(u64::MAX);
+
+/// Returns the output console mode before crossterm changed it, `None` if it couldn't be read.
+pub(crate) fn original_output_mode() -> Option<u32> {
+    supports_ansi();
+    u32::try_from(ORIGINAL_OUTPUT_MODE.load(Ordering::SeqCst)).ok()
+}
+
 static SUPPORTS_ANSI_ESCAPE_CODES: AtomicBool = AtomicBool::new(false);
 static INITIALIZER: Once = Once::new();
 
 /// Checks if the current terminal supports ansi escape sequences
+///
+/// The first call enables virtual terminal processing on Windows 10 and later, which makes the
+/// ANSI (256 and RGB colors) code paths available instead of the WinAPI fallback. The result is
+/// cached for the lifetime of the process.
 pub fn supports_ansi() -> bool {
     INITIALIZER.call_once(|| {
+        // Always try to enable virtual terminal processing first. A console started with `TERM`
+        // set (e.g. by a shell) still needs the flag, otherwise the escape codes would be
+        // printed as-is instead of being interpreted.
+        //
         // Some terminals on Windows like GitBash can't use WinAPI calls directly
         // so when we try to enable the ANSI-flag for Windows this won't work.
-        // Because of that we should check first if the TERM-variable is set
+        // Because of that we check if the TERM-variable is set
         // and see if the current terminal is a terminal who does support ANSI.
-        let supported = std::env::var("TERM").map_or(false, |term| term != "dumb")
-            || enable_vt_processing().is_ok();
+        let supported = enable_vt_processing().is_ok()
+            || std::env::var("TERM").is_ok_and(|term| term != "dumb");
 
         SUPPORTS_ANSI_ESCAPE_CODES.store(supported, Ordering::SeqCst);
     });
@@ -1,5 +1,8 @@
 use std::fmt;
 use std::io::{self, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::style::{color_choice, ColorChoice};
 
 use super::error::Result;
 
@@ -34,6 +37,22 @@ pub trait Command {
     fn is_ansi_code_supported(&self) -> bool {
         super::ansi_support::supports_ansi()
     }
+
+    /// Updates the terminal state which is tracked by crossterm (e.g. the style stack).
+    ///
+    /// It's called once the command was queued, formatting a command (e.g. with `to_string`)
+    /// doesn't change the tracked state.
+    #[doc(hidden)]
+    fn update_state(&self) {}
+
+    /// Writes the text printed by this command without any escape codes, see
+    /// [`set_plain_output`](fn.set_plain_output.html).
+    ///
+    /// Commands which don't print text (e.g. moving the cursor) write nothing.
+    #[doc(hidden)]
+    fn write_plain(&self, _f: &mut impl fmt::Write) -> fmt::Result {
+        Ok(())
+    }
 }
 
 impl<T: Command + ?Sized> Command for &T {
@@ -52,6 +71,83 @@ impl<T: Command + ?Sized> Command for &T {
     fn is_ansi_code_supported(&self) -> bool {
         T::is_ansi_code_supported(self)
     }
+
+    #[inline]
+    fn update_state(&self) {
+        T::update_state(self)
+    }
+
+    #[inline]
+    fn write_plain(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        T::write_plain(self, f)
+    }
+}
+
+// Whether the commands are written as plain text, see `set_plain_output` (`PLAIN_OUTPUT_AUTO` =
+// decided by the environment).
+static PLAIN_OUTPUT: AtomicU8 = AtomicU8::new(PLAIN_OUTPUT_AUTO);
+// Whether the environment asks for the plain output, resolved on first use (`u8::MAX` = unknown).
+static ENV_PLAIN_OUTPUT: AtomicU8 = AtomicU8::new(u8::MAX);
+
+const PLAIN_OUTPUT_AUTO: u8 = u8::MAX;
+
+/// Sets whether the commands are written as plain text.
+///
+/// In the plain output mode, the commands only write the text they print (e.g.
+/// [`Print`](style/struct.Print.html) or the content of
+/// [`PrintStyledContent`](style/struct.PrintStyledContent.html)). The other commands (colors,
+/// attributes, cursor movements, clearing, ...) do nothing, no escape codes are written. This
+/// applies to queued and executed commands and to formatted commands (e.g. with `to_string`).
+///
+/// By default, the plain output mode is used if the standard output is not a terminal, so that
+/// `mytool | less` doesn't get escape codes. The escape codes are forced by setting the
+/// `CLICOLOR_FORCE` environment variable to anything else than `0`, by
+/// [`ColorChoice::Always`](style/enum.ColorChoice.html#variant.Always) or by calling this function
+/// with `false`. The standard output is checked once, before the first command is written.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::set_plain_output;
+///
+/// // e.g. for a `--color=always` command line flag, to write escape codes to a pipe
+/// set_plain_output(false);
+/// ```
+pub fn set_plain_output(plain: bool) {
+    PLAIN_OUTPUT.store(plain as u8, Ordering::Relaxed);
+}
+
+/// Returns whether the commands are written as plain text, see
+/// [`set_plain_output`](fn.set_plain_output.html).
+pub fn is_plain_output() -> bool {
+    match PLAIN_OUTPUT.load(Ordering::Relaxed) {
+        PLAIN_OUTPUT_AUTO => env_plain_output() && color_choice() != ColorChoice::Always,
+        plain => plain != 0,
+    }
+}
+
+fn env_plain_output() -> bool {
+    match ENV_PLAIN_OUTPUT.load(Ordering::Relaxed) {
+        u8::MAX => {
+            // The unit tests check the escape codes, wherever their output goes
+            #[cfg(test)]
+            let is_tty = true;
+            #[cfg(not(test))]
+            let is_tty = crate::tty::IsTty::is_tty(&io::stdout());
+
+            let plain =
+                resolve_plain_output(std::env::var("CLICOLOR_FORCE").ok().as_deref(), is_tty);
+            ENV_PLAIN_OUTPUT.store(plain as u8, Ordering::Relaxed);
+            plain
+        }
+        plain => plain != 0,
+    }
+}
+
+/// Resolves the default plain output mode from the value of the `CLICOLOR_FORCE` environment
+/// variable and whether the standard output is a terminal.
+fn resolve_plain_output(clicolor_force: Option<&str>, is_tty: bool) -> bool {
+    !is_tty && !matches!(clicolor_force, Some(v) if !v.is_empty() && v != "0")
 }
 
 /// An interface for types that can queue commands for further execution.
@@ -115,23 +211,31 @@ impl<T: Write + ?Sized> QueueableCommand for T {
     ///
     /// * In the case of UNIX and Windows 10, ANSI codes are written to the given 'writer'.
     /// * In case of Windows versions lower than 10, a direct WinAPI call will be made.
-    ///     The reason for this is that Windows versions lower than 10 do not support ANSI codes,
-    ///     and can therefore not be written to the given `writer`.
-    ///     Therefore, there is no difference between [execute](./trait.ExecutableCommand.html)
-    ///     and [queue](./trait.QueueableCommand.html) for those old Windows versions.
+    ///   The reason for this is that Windows versions lower than 10 do not support ANSI codes,
+    ///   and can therefore not be written to the given `writer`.
+    ///   Therefore, there is no difference between [execute](./trait.ExecutableCommand.html)
+    ///   and [queue](./trait.QueueableCommand.html) for those old Windows versions.
     fn queue(&mut self, command: impl Command) -> Result<&mut Self> {
+        if is_plain_output() {
+            write_command(self, &command, true)?;
+            command.update_state();
+            return Ok(self);
+        }
+
         #[cfg(windows)]
         if !command.is_ansi_code_supported() {
             command.execute_winapi(|| {
-                write_command_ansi(self, &command)?;
+                write_command(self, &command, false)?;
                 // winapi doesn't support queuing
                 self.flush()?;
                 Ok(())
             })?;
+            command.update_state();
             return Ok(self);
         }
 
-        write_command_ansi(self, command)?;
+        write_command(self, &command, false)?;
+        command.update_state();
         Ok(self)
     }
 }
@@ -174,10 +278,10 @@ impl<T: Write + ?Sized> ExecutableCommand for T {
     ///
     /// * In the case of UNIX and Windows 10, ANSI codes are written to the given 'writer'.
     /// * In case of Windows versions lower than 10, a direct WinAPI call will be made.
-    ///     The reason for this is that Windows versions lower than 10 do not support ANSI codes,
-    ///     and can therefore not be written to the given `writer`.
-    ///     Therefore, there is no difference between [execute](./trait.ExecutableCommand.html)
-    ///     and [queue](./trait.QueueableCommand.html) for those old Windows versions.
+    ///   The reason for this is that Windows versions lower than 10 do not support ANSI codes,
+    ///   and can therefore not be written to the given `writer`.
+    ///   Therefore, there is no difference between [execute](./trait.ExecutableCommand.html)
+    ///   and [queue](./trait.QueueableCommand.html) for those old Windows versions.
     fn execute(&mut self, command: impl Command) -> Result<&mut Self> {
         self.queue(command)?;
         self.flush()?;
@@ -185,10 +289,11 @@ impl<T: Write + ?Sized> ExecutableCommand for T {
     }
 }
 
-/// Writes the ANSI representation of a command to the given writer.
-fn write_command_ansi<C: Command>(
+/// Writes the ANSI (or the plain text) representation of a command to the given writer.
+fn write_command<C: Command>(
     io: &mut (impl io::Write + ?Sized),
     command: C,
+    plain: bool,
 ) -> io::Result<()> {
     struct Adapter<T> {
         inner: T,
@@ -209,19 +314,30 @@ fn write_command_ansi<C: Command>(
         res: Ok(()),
     };
 
-    command
-        .write_ansi(&mut adapter)
-        .map_err(|fmt::Error| match adapter.res {
-            Ok(()) => panic!(
-                "<{}>::write_ansi incorrectly errored",
-                std::any::type_name::<C>()
-            ),
-            Err(e) => e,
-        })
+    let result = if plain {
+        command.write_plain(&mut adapter)
+    } else {
+        command.write_ansi(&mut adapter)
+    };
+
+    result.map_err(|fmt::Error| match adapter.res {
+        // The error wasn't caused by the writer, but by formatting the command (e.g. the
+        // `Display` implementation of the printed value failed).
+        Ok(()) => io::Error::other(format!(
+            "<{}>::{} failed",
+            std::any::type_name::<C>(),
+            if plain { "write_plain" } else { "write_ansi" }
+        )),
+        Err(e) => e,
+    })
 }
 
 /// Executes the ANSI representation of a command, using the given `fmt::Write`.
 pub(crate) fn execute_fmt(f: &mut impl fmt::Write, command: impl Command) -> fmt::Result {
+    if is_plain_output() {
+        return command.write_plain(f);
+    }
+
     #[cfg(windows)]
     if !command.is_ansi_code_supported() {
         return command
@@ -231,3 +347,78 @@ pub(crate) fn execute_fmt(f: &mut impl fmt::Write, command: impl Command) -> fmt
 
     command.write_ansi(f)
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{fmt, io, io::Write};
+
+    use super::{resolve_plain_output, write_command, QueueableCommand};
+    use crate::{
+        cursor::MoveTo,
+        style::{style, Colorize, Print, PrintStyledContent},
+        ErrorKind,
+    };
+
+    /// A writer behaving like a closed stdout.
+    pub(crate) struct ClosedWriter;
+
+    impl Write for ClosedWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+        }
+    }
+
+    struct FailingDisplay;
+
+    impl fmt::Display for FailingDisplay {
+        fn fmt(&self, _f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            Err(fmt::Error)
+        }
+    }
+
+    #[test]
+    fn test_queue_propagates_writer_error() {
+        match ClosedWriter.queue(Print("text")) {
+            Err(ErrorKind::IoError(e)) => assert_eq!(e.kind(), io::ErrorKind::BrokenPipe),
+            _ => panic!("the writer error should be returned"),
+        }
+    }
+
+    #[test]
+    fn test_queue_propagates_format_error() {
+        let mut buffer = Vec::new();
+
+        match buffer.queue(Print(FailingDisplay)) {
+            Err(ErrorKind::IoError(e)) => assert_eq!(e.kind(), io::ErrorKind::Other),
+            _ => panic!("the format error should be returned"),
+        }
+    }
+
+    #[test]
+    fn test_write_command_plain() {
+        let mut buffer = Vec::new();
+        write_command(&mut buffer, MoveTo(1, 2), true).unwrap();
+        write_command(&mut buffer, PrintStyledContent(style("text").red()), true).unwrap();
+        write_command(&mut buffer, Print("!"), true).unwrap();
+
+        assert_eq!(buffer, b"text!");
+    }
+
+    #[test]
+    fn test_write_command_plain_reports_format_error() {
+        let error = write_command(&mut Vec::new(), Print(FailingDisplay), true).unwrap_err();
+        assert!(error.to_string().ends_with(">::write_plain failed"));
+    }
+
+    #[test]
+    fn test_resolve_plain_output() {
+        assert!(!resolve_plain_output(None, true));
+        assert!(resolve_plain_output(None, false));
+        assert!(resolve_plain_output(Some("0"), false));
+        assert!(!resolve_plain_output(Some("1"), false));
+    }
+}
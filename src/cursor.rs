@@ -42,11 +42,11 @@
 //!
 //! For manual execution control check out [crossterm::queue](../macro.queue.html).
 
-use std::fmt;
+use std::{fmt, io};
 
-#[cfg(windows)]
-use crate::Result;
-use crate::{csi, impl_display, Command};
+use parking_lot::Mutex;
+
+use crate::{csi, impl_display, style::Color, terminal, Command, Result};
 
 pub use sys::position;
 
@@ -56,14 +56,77 @@ pub(crate) mod sys;
 ///
 /// # Notes
 ///
-/// * Top left cell is represented as `0,0`.
+/// * Top left cell is represented as `0,0`, on every platform. The coordinates are relative to the
+///   visible part of the terminal, like the ones returned by [position](./fn.position.html).
 /// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MoveTo(pub u16, pub u16);
 
+impl MoveTo {
+    /// Creates a `MoveTo` command with the position clamped to the current terminal size.
+    ///
+    /// Terminals silently pin the cursor to the last column/row, clamping makes this explicit,
+    /// e.g. after the terminal was resized.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io::{stdout, Write};
+    ///
+    /// use crossterm::{cursor::MoveTo, execute, Result};
+    ///
+    /// fn main() -> Result<()> {
+    ///     // Moves to the bottom right cell in terminals smaller than 1000x1000
+    ///     execute!(stdout(), MoveTo::clamped(999, 999)?)
+    /// }
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// An error is returned if the terminal size can't be retrieved.
+    pub fn clamped(column: u16, row: u16) -> Result<MoveTo> {
+        let (columns, rows) = terminal::size()?;
+        Ok(clamp_position(column, row, columns, rows))
+    }
+
+    /// Creates a `MoveTo` command, returns an error if the position is outside of the current
+    /// terminal size.
+    ///
+    /// An error is returned as well if the terminal size can't be retrieved.
+    pub fn checked(column: u16, row: u16) -> Result<MoveTo> {
+        let (columns, rows) = terminal::size()?;
+
+        if column >= columns || row >= rows {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "The cursor position ({}, {}) is outside of the terminal size ({}, {})",
+                    column, row, columns, rows
+                ),
+            )
+            .into());
+        }
+        Ok(MoveTo(column, row))
+    }
+}
+
+/// Clamps the position to the last column and row of a terminal with the given size.
+fn clamp_position(column: u16, row: u16, columns: u16, rows: u16) -> MoveTo {
+    MoveTo(
+        column.min(columns.saturating_sub(1)),
+        row.min(rows.saturating_sub(1)),
+    )
+}
+
 impl Command for MoveTo {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        write!(f, csi!("{};{}H"), self.1 + 1, self.0 + 1)
+        // The ANSI coordinates are 1-based
+        write!(
+            f,
+            csi!("{};{}H"),
+            u32::from(self.1) + 1,
+            u32::from(self.0) + 1
+        )
     }
 
     #[cfg(windows)]
@@ -75,15 +138,33 @@ impl Command for MoveTo {
 /// A command that moves the terminal cursor down the given number of lines,
 /// and moves it to the first column.
 ///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{cursor::MoveToNextLine, execute, style::Print, Result};
+///
+/// fn main() -> Result<()> {
+///     execute!(stdout(), Print("name: "), MoveToNextLine(1), Print("email: "))
+/// }
+/// ```
+///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * Only the column is changed if the given number is `0`.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MoveToNextLine(pub u16);
 
 impl Command for MoveToNextLine {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        write!(f, csi!("{}E"), self.0)
+        // `CSI 0 E` would move the cursor one line down
+        if self.0 == 0 {
+            f.write_str(csi!("1G"))
+        } else {
+            write!(f, csi!("{}E"), self.0)
+        }
     }
 
     #[cfg(windows)]
@@ -97,13 +178,19 @@ impl Command for MoveToNextLine {
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * Only the column is changed if the given number is `0`.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MoveToPreviousLine(pub u16);
 
 impl Command for MoveToPreviousLine {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        write!(f, csi!("{}F"), self.0)
+        // `CSI 0 F` would move the cursor one line up
+        if self.0 == 0 {
+            f.write_str(csi!("1G"))
+        } else {
+            write!(f, csi!("{}F"), self.0)
+        }
     }
 
     #[cfg(windows)]
@@ -114,15 +201,36 @@ impl Command for MoveToPreviousLine {
 
 /// A command that moves the terminal cursor to the given column on the current row.
 ///
+/// # Examples
+///
+/// Rewrite the current line, e.g. for a progress bar:
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{cursor::MoveToColumn, queue, style::Print, Result};
+///
+/// fn main() -> Result<()> {
+///     let mut stdout = stdout();
+///
+///     for percent in (0..=100).step_by(10) {
+///         queue!(stdout, MoveToColumn(0), Print(format!("{:>3}%", percent)))?;
+///         stdout.flush()?;
+///     }
+///     Ok(())
+/// }
+/// ```
+///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * The leftmost column is `0`.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MoveToColumn(pub u16);
 
 impl Command for MoveToColumn {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        write!(f, csi!("{}G"), self.0)
+        write!(f, csi!("{}G"), u32::from(self.0) + 1)
     }
 
     #[cfg(windows)]
@@ -135,13 +243,14 @@ impl Command for MoveToColumn {
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * The top row is `0`.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MoveToRow(pub u16);
 
 impl Command for MoveToRow {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        write!(f, csi!("{}d"), self.0)
+        write!(f, csi!("{}d"), u32::from(self.0) + 1)
     }
 
     #[cfg(windows)]
@@ -154,7 +263,9 @@ impl Command for MoveToRow {
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * The cursor stops at the top edge of the terminal.
+/// * This command is ignored if the given number is `0`.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MoveUp(pub u16);
 
@@ -176,7 +287,9 @@ impl Command for MoveUp {
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * Terminals supporting ANSI escape codes stop the cursor at the right edge of the terminal.
+/// * This command is ignored if the given number is `0`.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MoveRight(pub u16);
 
@@ -198,7 +311,9 @@ impl Command for MoveRight {
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * Terminals supporting ANSI escape codes stop the cursor at the bottom edge of the terminal.
+/// * This command is ignored if the given number is `0`.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MoveDown(pub u16);
 
@@ -220,7 +335,9 @@ impl Command for MoveDown {
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * The cursor stops at the left edge of the terminal.
+/// * This command is ignored if the given number is `0`.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MoveLeft(pub u16);
 
@@ -238,13 +355,107 @@ impl Command for MoveLeft {
     }
 }
 
+/// A command that moves the terminal cursor forward to the next tab stop, the given number of
+/// times.
+///
+/// Column-aligned output can move to the tab stops instead of being padded with spaces, see
+/// [SetTabStop](../terminal/struct.SetTabStop.html).
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{cursor::MoveToNextTab, execute, style::Print, Result};
+///
+/// fn main() -> Result<()> {
+///     execute!(stdout(), Print("name"), MoveToNextTab(1), Print("size"))
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * The cursor stops at the right edge of the terminal.
+/// * This uses `CHT` (`CSI n I`). The legacy Windows console has a tab stop every 8 columns.
+/// * This command is ignored if the given number is `0`.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveToNextTab(pub u16);
+
+impl Command for MoveToNextTab {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.0 != 0 {
+            write!(f, csi!("{}I"), self.0)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::move_to_next_tab(self.0)
+    }
+}
+
+/// A command that moves the terminal cursor back to the previous tab stop, the given number of
+/// times.
+///
+/// # Notes
+///
+/// * The cursor stops at the left edge of the terminal.
+/// * This uses `CBT` (`CSI n Z`). The legacy Windows console has a tab stop every 8 columns.
+/// * This command is ignored if the given number is `0`.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveToPreviousTab(pub u16);
+
+impl Command for MoveToPreviousTab {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.0 != 0 {
+            write!(f, csi!("{}Z"), self.0)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::move_to_previous_tab(self.0)
+    }
+}
+
 /// A command that saves the current terminal cursor position.
 ///
 /// See the [RestorePosition](./struct.RestorePosition.html) command.
 ///
+/// # Examples
+///
+/// Update a status line and continue where the cursor was:
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{
+///     cursor::{MoveTo, RestorePosition, SavePosition},
+///     execute,
+///     style::Print,
+///     Result,
+/// };
+///
+/// fn main() -> Result<()> {
+///     execute!(
+///         stdout(),
+///         SavePosition,
+///         MoveTo(0, 0),
+///         Print("status: 42 files processed"),
+///         RestorePosition
+///     )
+/// }
+/// ```
+///
 /// # Notes
 ///
-/// - The cursor position is stored globally.
+/// - The cursor position is stored globally, saving it again overwrites the saved position.
+/// - On UNIX and Windows 10 this uses `DECSC` (`ESC 7`), the legacy Windows console stores the
+///   coordinates.
 /// - Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SavePosition;
@@ -282,10 +493,74 @@ impl Command for RestorePosition {
     }
 }
 
+// The positions saved with `push_position`, the last one is popped first.
+static POSITION_STACK: Mutex<Vec<(u16, u16)>> = parking_lot::const_mutex(Vec::new());
+
+/// Saves the current cursor position on a stack.
+///
+/// Unlike [SavePosition](./struct.SavePosition.html), which has a single slot, every push is
+/// kept until it is popped with [pop_position](./fn.pop_position.html). This allows nested
+/// components to save the cursor without overwriting the position saved by their caller.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{cursor, execute, style::Print, Result};
+///
+/// fn draw_status(status: &str) -> Result<()> {
+///     cursor::push_position()?;
+///     execute!(stdout(), cursor::MoveTo(0, 0), Print(status))?;
+///     if let Some(saved) = cursor::pop_position() {
+///         execute!(stdout(), saved)?;
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * The position is read with [position](./fn.position.html), queued commands must be flushed
+///   before pushing, otherwise the saved position is outdated.
+/// * The position stack is global.
+pub fn push_position() -> Result<()> {
+    let position = position()?;
+    POSITION_STACK.lock().push(position);
+    Ok(())
+}
+
+/// Removes the last position saved with [push_position](./fn.push_position.html) from the stack.
+///
+/// Returns a [MoveTo](./struct.MoveTo.html) command moving the cursor back to it, or `None` if
+/// the stack is empty.
+pub fn pop_position() -> Option<MoveTo> {
+    POSITION_STACK
+        .lock()
+        .pop()
+        .map(|(column, row)| MoveTo(column, row))
+}
+
 /// A command that hides the terminal cursor.
 ///
+/// Hiding the cursor while redrawing stops it from flickering across the screen, don't forget to
+/// [`Show`](struct.Show.html) it again when you're done.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{cursor::{Hide, MoveTo, Show}, execute, style::Print, Result};
+///
+/// fn main() -> Result<()> {
+///     execute!(stdout(), Hide, MoveTo(0, 0), Print("redrawn"), Show)
+/// }
+/// ```
+///
 /// # Notes
 ///
+/// - The cursor size is kept on Windows.
 /// - Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Hide;
@@ -303,6 +578,8 @@ impl Command for Hide {
 
 /// A command that shows the terminal cursor.
 ///
+/// See the [Hide](./struct.Hide.html) command.
+///
 /// # Notes
 ///
 /// - Commands must be executed/queued for execution otherwise they do nothing.
@@ -322,9 +599,15 @@ impl Command for Show {
 
 /// A command that enables blinking of the terminal cursor.
 ///
+/// This keeps the shape of the cursor, see [SetCursorStyle](./struct.SetCursorStyle.html) to
+/// change both.
+///
 /// # Notes
 ///
-/// - Windows versions lower than Windows 10 do not support this functionality.
+/// - Some terminals ignore this and only support blinking with
+///   [SetCursorStyle](./struct.SetCursorStyle.html).
+/// - Windows versions lower than Windows 10 do not support this functionality, the cursor of the
+///   legacy console always blinks.
 /// - Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct EnableBlinking;
@@ -342,9 +625,12 @@ impl Command for EnableBlinking {
 
 /// A command that disables blinking of the terminal cursor.
 ///
+/// See the [EnableBlinking](./struct.EnableBlinking.html) command.
+///
 /// # Notes
 ///
-/// - Windows versions lower than Windows 10 do not support this functionality.
+/// - Windows versions lower than Windows 10 do not support this functionality, the cursor of the
+///   legacy console always blinks.
 /// - Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct DisableBlinking;
@@ -376,6 +662,8 @@ pub enum CursorShape {
 ///
 /// # Note
 ///
+/// - The `UnderScore` and `Line` shapes blink, the `Block` shape doesn't. Use
+///   [SetCursorStyle](./struct.SetCursorStyle.html) to choose whether the cursor blinks.
 /// - Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SetCursorShape(pub CursorShape);
@@ -396,6 +684,187 @@ impl Command for SetCursorShape {
     }
 }
 
+/// A command that sets the shape of the cursor and whether it blinks (`DECSCUSR`).
+///
+/// Editors can use this to switch between a bar in insert mode and a block in normal mode.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{cursor::{CursorShape, ResetCursorStyle, SetCursorStyle}, execute, Result};
+///
+/// fn main() -> Result<()> {
+///     // insert mode: blinking bar
+///     execute!(stdout(), SetCursorStyle(CursorShape::Line, true))?;
+///     // normal mode: steady block
+///     execute!(stdout(), SetCursorStyle(CursorShape::Block, false))?;
+///     // restore the shape configured by the user on exit
+///     execute!(stdout(), ResetCursorStyle)
+/// }
+/// ```
+///
+/// # Notes
+///
+/// - The second value is whether the cursor blinks.
+/// - Windows versions lower than Windows 10 do not support this functionality.
+/// - Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetCursorStyle(pub CursorShape, pub bool);
+
+impl Command for SetCursorStyle {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let steady = match self.0 {
+            CursorShape::Block => 2,
+            CursorShape::UnderScore => 4,
+            CursorShape::Line => 6,
+        };
+        // The blinking variant of each shape comes right before the steady one
+        let style = if self.1 { steady - 1 } else { steady };
+        write!(f, csi!("{} q"), style)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that restores the cursor shape and blinking configured by the user (or the terminal
+/// default).
+///
+/// See the [SetCursorStyle](./struct.SetCursorStyle.html) command.
+///
+/// # Notes
+///
+/// - Windows versions lower than Windows 10 do not support this functionality.
+/// - Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetCursorStyle;
+
+impl Command for ResetCursorStyle {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("0 q"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that sets the height of the cursor cell filled by the cursor, in percent (1-100).
+///
+/// A small size gives a thin underline, `100` fills the whole cell like a block. This is the
+/// Windows console counterpart of [SetCursorStyle](./struct.SetCursorStyle.html).
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{cursor::SetCursorSize, execute, Result};
+///
+/// fn main() -> Result<()> {
+///     // A block cursor in the Windows console
+///     execute!(stdout(), SetCursorSize(100))
+/// }
+/// ```
+///
+/// # Notes
+///
+/// - The size is clamped to `1..=100`.
+/// - The size is a setting of the Windows console (`CONSOLE_CURSOR_INFO::dwSize`), this command
+///   does nothing on UNIX.
+/// - Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetCursorSize(pub u8);
+
+impl Command for SetCursorSize {
+    fn write_ansi(&self, _f: &mut impl fmt::Write) -> fmt::Result {
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::set_cursor_size(self.0)
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        false
+    }
+}
+
+/// A command that sets the color of the cursor.
+///
+/// Setting the color to `Color::Reset` restores the default cursor color, like
+/// [ResetCursorColor](./struct.ResetCursorColor.html).
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{
+///     cursor::{ResetCursorColor, SetCursorColor},
+///     execute,
+///     style::Color,
+///     Result,
+/// };
+///
+/// fn main() -> Result<()> {
+///     execute!(stdout(), SetCursorColor(Color::Rgb { r: 255, g: 135, b: 0 }))?;
+///     // ...
+///     execute!(stdout(), ResetCursorColor)
+/// }
+/// ```
+///
+/// # Notes
+///
+/// - This uses `OSC 12` which is not supported by all terminals.
+/// - The cursor color can't be changed with the WinAPI.
+/// - Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetCursorColor(pub Color);
+
+impl Command for SetCursorColor {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        match self.0.to_rgb() {
+            Some((r, g, b)) => write!(f, "\x1B]12;rgb:{:02x}/{:02x}/{:02x}\x1B\\", r, g, b),
+            None => ResetCursorColor.write_ansi(f),
+        }
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that restores the default cursor color.
+///
+/// See the [SetCursorColor](./struct.SetCursorColor.html) command.
+///
+/// # Notes
+///
+/// - This uses `OSC 112` which is not supported by all terminals.
+/// - Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetCursorColor;
+
+impl Command for ResetCursorColor {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1B]112\x1B\\")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+}
+
 impl_display!(for MoveTo);
 impl_display!(for MoveToColumn);
 impl_display!(for MoveToRow);
@@ -405,6 +874,8 @@ impl_display!(for MoveUp);
 impl_display!(for MoveDown);
 impl_display!(for MoveLeft);
 impl_display!(for MoveRight);
+impl_display!(for MoveToNextTab);
+impl_display!(for MoveToPreviousTab);
 impl_display!(for SavePosition);
 impl_display!(for RestorePosition);
 impl_display!(for Hide);
@@ -412,17 +883,167 @@ impl_display!(for Show);
 impl_display!(for EnableBlinking);
 impl_display!(for DisableBlinking);
 impl_display!(for SetCursorShape);
+impl_display!(for SetCursorStyle);
+impl_display!(for ResetCursorStyle);
+impl_display!(for SetCursorSize);
+impl_display!(for SetCursorColor);
+impl_display!(for ResetCursorColor);
 
 #[cfg(test)]
 mod tests {
     use std::io::{self, stdout};
 
-    use crate::execute;
+    use crate::{command::tests::ClosedWriter, execute, queue, ErrorKind};
+
+    use crate::style::Color;
 
     use super::{
-        position, MoveDown, MoveLeft, MoveRight, MoveTo, MoveUp, RestorePosition, SavePosition,
+        clamp_position, pop_position, position, CursorShape, DisableBlinking, EnableBlinking, Hide,
+        MoveDown, MoveLeft, MoveRight, MoveTo, MoveToColumn, MoveToNextLine, MoveToNextTab,
+        MoveToPreviousLine, MoveToPreviousTab, MoveToRow, MoveUp, ResetCursorColor,
+        ResetCursorStyle, RestorePosition, SavePosition, SetCursorColor, SetCursorSize,
+        SetCursorStyle, Show, POSITION_STACK,
     };
 
+    #[test]
+    fn test_set_cursor_style_ansi() {
+        assert_eq!(
+            SetCursorStyle(CursorShape::Block, true).to_string(),
+            "\x1B[1 q"
+        );
+        assert_eq!(
+            SetCursorStyle(CursorShape::Block, false).to_string(),
+            "\x1B[2 q"
+        );
+        assert_eq!(
+            SetCursorStyle(CursorShape::UnderScore, true).to_string(),
+            "\x1B[3 q"
+        );
+        assert_eq!(
+            SetCursorStyle(CursorShape::UnderScore, false).to_string(),
+            "\x1B[4 q"
+        );
+        assert_eq!(
+            SetCursorStyle(CursorShape::Line, true).to_string(),
+            "\x1B[5 q"
+        );
+        assert_eq!(
+            SetCursorStyle(CursorShape::Line, false).to_string(),
+            "\x1B[6 q"
+        );
+        assert_eq!(ResetCursorStyle.to_string(), "\x1B[0 q");
+    }
+
+    #[test]
+    fn test_clamp_position() {
+        assert_eq!(clamp_position(5, 6, 80, 24), MoveTo(5, 6));
+        assert_eq!(clamp_position(79, 23, 80, 24), MoveTo(79, 23));
+        assert_eq!(clamp_position(80, 24, 80, 24), MoveTo(79, 23));
+        assert_eq!(clamp_position(200, 3, 80, 24), MoveTo(79, 3));
+        assert_eq!(clamp_position(10, 10, 0, 0), MoveTo(0, 0));
+    }
+
+    #[test]
+    fn test_cursor_commands_return_write_errors() {
+        let is_broken_pipe = |result| match result {
+            Err(ErrorKind::IoError(e)) => e.kind() == io::ErrorKind::BrokenPipe,
+            _ => false,
+        };
+
+        assert!(is_broken_pipe(queue!(ClosedWriter, MoveTo(1, 1))));
+        assert!(is_broken_pipe(queue!(ClosedWriter, MoveUp(1), Hide)));
+        assert!(is_broken_pipe(execute!(ClosedWriter, SavePosition)));
+        assert!(is_broken_pipe(execute!(
+            ClosedWriter,
+            SetCursorStyle(CursorShape::Line, true)
+        )));
+    }
+
+    #[test]
+    fn test_move_relative_ansi() {
+        assert_eq!(MoveUp(3).to_string(), "\x1B[3A");
+        assert_eq!(MoveDown(1).to_string(), "\x1B[1B");
+        assert_eq!(MoveRight(12).to_string(), "\x1B[12C");
+        assert_eq!(MoveLeft(2).to_string(), "\x1B[2D");
+        // `CSI 0 A` would move the cursor one row
+        assert_eq!(MoveUp(0).to_string(), "");
+        assert_eq!(MoveLeft(0).to_string(), "");
+    }
+
+    #[test]
+    fn test_move_to_is_0_based_ansi() {
+        assert_eq!(MoveTo(0, 0).to_string(), "\x1B[1;1H");
+        assert_eq!(MoveTo(4, 9).to_string(), "\x1B[10;5H");
+        assert_eq!(MoveTo(u16::MAX, u16::MAX).to_string(), "\x1B[65536;65536H");
+        assert_eq!(MoveToColumn(0).to_string(), "\x1B[1G");
+        assert_eq!(MoveToColumn(7).to_string(), "\x1B[8G");
+        assert_eq!(MoveToRow(0).to_string(), "\x1B[1d");
+        assert_eq!(MoveToRow(7).to_string(), "\x1B[8d");
+    }
+
+    #[test]
+    fn test_move_to_tab_ansi() {
+        assert_eq!(MoveToNextTab(1).to_string(), "\x1B[1I");
+        assert_eq!(MoveToPreviousTab(2).to_string(), "\x1B[2Z");
+        // `CSI 0 I` would move to the next tab stop
+        assert_eq!(MoveToNextTab(0).to_string(), "");
+        assert_eq!(MoveToPreviousTab(0).to_string(), "");
+    }
+
+    #[test]
+    fn test_move_to_next_previous_line_ansi() {
+        assert_eq!(MoveToNextLine(2).to_string(), "\x1B[2E");
+        assert_eq!(MoveToPreviousLine(3).to_string(), "\x1B[3F");
+        assert_eq!(MoveToNextLine(0).to_string(), "\x1B[1G");
+        assert_eq!(MoveToPreviousLine(0).to_string(), "\x1B[1G");
+    }
+
+    #[test]
+    fn test_blinking_ansi() {
+        assert_eq!(EnableBlinking.to_string(), "\x1B[?12h");
+        assert_eq!(DisableBlinking.to_string(), "\x1B[?12l");
+    }
+
+    #[test]
+    fn test_save_restore_position_ansi() {
+        assert_eq!(SavePosition.to_string(), "\x1B7");
+        assert_eq!(RestorePosition.to_string(), "\x1B8");
+    }
+
+    #[test]
+    fn test_pop_position() {
+        POSITION_STACK.lock().extend(&[(1, 2), (3, 4)]);
+
+        assert_eq!(pop_position(), Some(MoveTo(3, 4)));
+        assert_eq!(pop_position(), Some(MoveTo(1, 2)));
+        assert_eq!(pop_position(), None);
+    }
+
+    #[test]
+    fn test_set_cursor_size_ansi() {
+        assert_eq!(SetCursorSize(25).to_string(), "");
+    }
+
+    #[test]
+    fn test_set_cursor_color_ansi() {
+        assert_eq!(
+            SetCursorColor(Color::Rgb { r: 255, g: 8, b: 0 }).to_string(),
+            "\x1B]12;rgb:ff/08/00\x1B\\"
+        );
+        assert_eq!(
+            SetCursorColor(Color::Green).to_string(),
+            "\x1B]12;rgb:00/ff/00\x1B\\"
+        );
+        assert_eq!(SetCursorColor(Color::Reset).to_string(), "\x1B]112\x1B\\");
+        assert_eq!(ResetCursorColor.to_string(), "\x1B]112\x1B\\");
+    }
+
+    #[test]
+    fn test_hide_show_ansi() {
+        assert_eq!(Hide.to_string(), "\x1B[?25l");
+        assert_eq!(Show.to_string(), "\x1B[?25h");
+    }
+
     // Test is disabled, because it's failing on Travis
     #[test]
     #[ignore]
@@ -1,17 +1,38 @@
-use std::{
-    io::{self, Error, ErrorKind, Write},
-    time::Duration,
-};
+use std::{io::Error, time::Duration};
 
 use crate::{
     event::{filter::CursorPositionFilter, poll_internal, read_internal, InternalEvent},
-    terminal::{disable_raw_mode, enable_raw_mode, sys::is_raw_mode_enabled},
+    terminal::{
+        disable_raw_mode, enable_raw_mode,
+        sys::{is_raw_mode_enabled, write_query},
+    },
     Result,
 };
 
 /// Returns the cursor position (column, row).
 ///
 /// The top left cell is represented `0,0`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{cursor, execute, style::Print, Result};
+///
+/// fn main() -> Result<()> {
+///     // Draw a marker two rows below wherever the prompt left the cursor
+///     let (column, row) = cursor::position()?;
+///     execute!(stdout(), cursor::MoveTo(column, row + 2), Print("^"))
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * On UNIX, the terminal is queried with `ESC [ 6 n` (the raw mode is enabled while waiting for
+///   the answer if it isn't enabled yet). An error is returned if the terminal does not answer
+///   within 2 seconds.
+/// * On Windows, the position is read with `GetConsoleScreenBufferInfo`.
 pub fn position() -> Result<(u16, u16)> {
     if is_raw_mode_enabled() {
         read_position_raw()
@@ -29,9 +50,7 @@ fn read_position() -> Result<(u16, u16)> {
 
 fn read_position_raw() -> Result<(u16, u16)> {
     // Use `ESC [ 6 n` to and retrieve the cursor position.
-    let mut stdout = io::stdout();
-    stdout.write_all(b"\x1B[6n")?;
-    stdout.flush()?;
+    write_query(b"\x1B[6n")?;
 
     loop {
         match poll_internal(Some(Duration::from_millis(2000)), &CursorPositionFilter) {
@@ -43,8 +62,7 @@ fn read_position_raw() -> Result<(u16, u16)> {
                 }
             }
             Ok(false) => {
-                return Err(Error::new(
-                    ErrorKind::Other,
+                return Err(Error::other(
                     "The cursor position could not be read within a normal duration",
                 )
                 .into());
@@ -7,10 +7,13 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use crossterm_winapi::{is_true, Coord, Handle, HandleType, ScreenBuffer};
 use winapi::{
     shared::minwindef::{FALSE, TRUE},
-    um::wincon::{SetConsoleCursorInfo, SetConsoleCursorPosition, CONSOLE_CURSOR_INFO, COORD},
+    um::wincon::{
+        GetConsoleCursorInfo, SetConsoleCursorInfo, SetConsoleCursorPosition, CONSOLE_CURSOR_INFO,
+        COORD,
+    },
 };
 
-use crate::Result;
+use crate::{terminal, Result};
 
 /// The position of the cursor, written when you save the cursor's position.
 ///
@@ -18,65 +21,137 @@ use crate::Result;
 /// times or-ed with the cursor's y position, where both are `i16`s.
 static SAVED_CURSOR_POS: AtomicU64 = AtomicU64::new(u64::MAX);
 
-// The 'y' position of the cursor is not relative to the window but absolute to screen buffer.
-// We can calculate the relative cursor position by subtracting the top position of the terminal window from the y position.
-// This results in an 1-based coord zo subtract 1 to make cursor position 0-based.
-pub fn parse_relative_y(y: i16) -> Result<i16> {
-    let window = ScreenBuffer::current()?.info()?;
-
-    let window_size = window.terminal_window();
-    let screen_size = window.terminal_size();
-
-    if y <= screen_size.height {
-        Ok(y)
-    } else {
-        Ok(y - window_size.top)
-    }
-}
-
 /// Returns the cursor position (column, row).
 ///
 /// The top left cell is represented `0,0`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{cursor, execute, style::Print, Result};
+///
+/// fn main() -> Result<()> {
+///     // Draw a marker two rows below wherever the prompt left the cursor
+///     let (column, row) = cursor::position()?;
+///     execute!(stdout(), cursor::MoveTo(column, row + 2), Print("^"))
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * On UNIX, the terminal is queried with `ESC [ 6 n` (the raw mode is enabled while waiting for
+///   the answer if it isn't enabled yet). An error is returned if the terminal does not answer
+///   within 2 seconds.
+/// * On Windows, the position is read with `GetConsoleScreenBufferInfo`.
 pub fn position() -> Result<(u16, u16)> {
-    let cursor = ScreenBufferCursor::output()?;
-    let mut position = cursor.position()?;
-    //    if position.y != 0 {
-    position.y = parse_relative_y(position.y)?;
-    //    }
-    Ok(position.into())
+    // The console position is absolute to the screen buffer, while the public (and the ANSI)
+    // coordinates are relative to the visible window.
+    let info = ScreenBufferCursor::output()?.screen_buffer.info()?;
+    let position = info.cursor_pos();
+    let window = info.terminal_window();
+
+    Ok((
+        (position.x - window.left).max(0) as u16,
+        (position.y - window.top).max(0) as u16,
+    ))
 }
 
 pub(crate) fn show_cursor(show_cursor: bool) -> Result<()> {
     ScreenBufferCursor::from(Handle::current_out_handle()?).set_visibility(show_cursor)
 }
 
+pub(crate) fn set_cursor_size(size: u8) -> Result<()> {
+    ScreenBufferCursor::output()?.set_size(size)
+}
+
+/// Moves the cursor to the position relative to the visible window, like `position` and the ANSI
+/// escape codes.
 pub(crate) fn move_to(column: u16, row: u16) -> Result<()> {
     let cursor = ScreenBufferCursor::output()?;
-    cursor.move_to(column as i16, row as i16)?;
-    Ok(())
+    let window = cursor.screen_buffer.info()?.terminal_window();
+
+    let x = to_console_coordinate(u32::from(column) + window.left.max(0) as u32)?;
+    let y = to_console_coordinate(u32::from(row) + window.top.max(0) as u32)?;
+    cursor.move_to(x, y)
+}
+
+/// Moves the cursor to the position absolute to the screen buffer.
+pub(crate) fn move_to_buffer_position(column: u16, row: u16) -> Result<()> {
+    let x = to_console_coordinate(u32::from(column))?;
+    let y = to_console_coordinate(u32::from(row))?;
+    ScreenBufferCursor::output()?.move_to(x, y)
+}
+
+// The console coordinates are `i16`s, larger positions must not wrap around.
+fn to_console_coordinate(value: u32) -> Result<i16> {
+    i16::try_from(value).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "The cursor position {} exceeds the console coordinates",
+                value
+            ),
+        )
+        .into()
+    })
 }
 
 pub(crate) fn move_up(count: u16) -> Result<()> {
     let (column, row) = position()?;
-    move_to(column, row - count)?;
+    move_to(column, row.saturating_sub(count))?;
     Ok(())
 }
 
 pub(crate) fn move_right(count: u16) -> Result<()> {
     let (column, row) = position()?;
-    move_to(column + count, row)?;
+    move_to(column.saturating_add(count), row)?;
     Ok(())
 }
 
 pub(crate) fn move_down(count: u16) -> Result<()> {
     let (column, row) = position()?;
-    move_to(column, row + count)?;
+    move_to(column, row.saturating_add(count))?;
     Ok(())
 }
 
 pub(crate) fn move_left(count: u16) -> Result<()> {
     let (column, row) = position()?;
-    move_to(column - count, row)?;
+    move_to(column.saturating_sub(count), row)?;
+    Ok(())
+}
+
+/// The distance between the tab stops of the console.
+const TAB_WIDTH: u16 = 8;
+
+pub(crate) fn move_to_next_tab(count: u16) -> Result<()> {
+    if count == 0 {
+        return Ok(());
+    }
+
+    let (column, row) = position()?;
+    let (columns, _) = terminal::size()?;
+    let tab_width = u32::from(TAB_WIDTH);
+    let column = (u32::from(column) / tab_width + u32::from(count)) * tab_width;
+    let last_column = u32::from(columns.saturating_sub(1));
+    move_to(column.min(last_column) as u16, row)?;
+    Ok(())
+}
+
+pub(crate) fn move_to_previous_tab(count: u16) -> Result<()> {
+    if count == 0 {
+        return Ok(());
+    }
+
+    let (column, row) = position()?;
+    let tab_width = u32::from(TAB_WIDTH);
+    // A cursor at a tab stop moves to the one before it
+    let tab_stop = u32::from(column).div_ceil(tab_width);
+    move_to(
+        (tab_stop.saturating_sub(u32::from(count)) * tab_width) as u16,
+        row,
+    )?;
     Ok(())
 }
 
@@ -94,13 +169,13 @@ pub(crate) fn move_to_row(new_row: u16) -> Result<()> {
 
 pub(crate) fn move_to_next_line(count: u16) -> Result<()> {
     let (_, row) = position()?;
-    move_to(0, row + count)?;
+    move_to(0, row.saturating_add(count))?;
     Ok(())
 }
 
 pub(crate) fn move_to_previous_line(count: u16) -> Result<()> {
     let (_, row) = position()?;
-    move_to(0, row - count)?;
+    move_to(0, row.saturating_sub(count))?;
     Ok(())
 }
 
@@ -132,24 +207,18 @@ impl ScreenBufferCursor {
 
     fn move_to(&self, x: i16, y: i16) -> Result<()> {
         if x < 0 {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
-                format!(
-                    "Argument Out of Range Exception when setting cursor position to X: {}",
-                    x
-                ),
-            )
+            return Err(io::Error::other(format!(
+                "Argument Out of Range Exception when setting cursor position to X: {}",
+                x
+            ))
             .into());
         }
 
         if y < 0 {
-            return Err(io::Error::new(
-                io::ErrorKind::Other,
-                format!(
-                    "Argument Out of Range Exception when setting cursor position to Y: {}",
-                    y
-                ),
-            )
+            return Err(io::Error::other(format!(
+                "Argument Out of Range Exception when setting cursor position to Y: {}",
+                y
+            ))
             .into());
         }
 
@@ -166,10 +235,46 @@ impl ScreenBufferCursor {
         Ok(())
     }
 
+    fn cursor_info(&self) -> Result<CONSOLE_CURSOR_INFO> {
+        let mut cursor_info = CONSOLE_CURSOR_INFO {
+            dwSize: 0,
+            bVisible: FALSE,
+        };
+
+        unsafe {
+            if !is_true(GetConsoleCursorInfo(
+                **self.screen_buffer.handle(),
+                &mut cursor_info,
+            )) {
+                return Err(io::Error::last_os_error().into());
+            }
+        }
+        Ok(cursor_info)
+    }
+
     fn set_visibility(&self, visible: bool) -> Result<()> {
+        // Keep the cursor size, only change the visibility.
         let cursor_info = CONSOLE_CURSOR_INFO {
-            dwSize: 100,
             bVisible: if visible { TRUE } else { FALSE },
+            ..self.cursor_info()?
+        };
+
+        unsafe {
+            if !is_true(SetConsoleCursorInfo(
+                **self.screen_buffer.handle(),
+                &cursor_info,
+            )) {
+                return Err(io::Error::last_os_error().into());
+            }
+        }
+        Ok(())
+    }
+
+    fn set_size(&self, size: u8) -> Result<()> {
+        // Keep the visibility, only change the size.
+        let cursor_info = CONSOLE_CURSOR_INFO {
+            dwSize: u32::from(size.clamp(1, 100)),
+            ..self.cursor_info()?
         };
 
         unsafe {
@@ -216,6 +321,7 @@ mod tests {
     use super::{
         move_down, move_left, move_right, move_to, move_to_column, move_to_next_line,
         move_to_previous_line, move_to_row, move_up, position, restore_position, save_position,
+        set_cursor_size, ScreenBufferCursor,
     };
 
     #[test]
@@ -245,6 +351,16 @@ mod tests {
         assert_eq!(position().unwrap(), (0, 0));
     }
 
+    #[test]
+    fn test_move_past_the_edge_winapi() {
+        move_to(1, 1).unwrap();
+
+        move_left(5).unwrap();
+        move_up(5).unwrap();
+
+        assert_eq!(position().unwrap(), (0, 0));
+    }
+
     #[test]
     fn test_move_up_winapi() {
         move_to(0, 2).unwrap();
@@ -254,6 +370,22 @@ mod tests {
         assert_eq!(position().unwrap(), (0, 0));
     }
 
+    #[test]
+    fn test_set_cursor_size_winapi() {
+        let cursor = ScreenBufferCursor::output().unwrap();
+        let saved_info = cursor.cursor_info().unwrap();
+
+        set_cursor_size(0).unwrap();
+        assert_eq!(cursor.cursor_info().unwrap().dwSize, 1);
+
+        set_cursor_size(255).unwrap();
+        let info = cursor.cursor_info().unwrap();
+        assert_eq!(info.dwSize, 100);
+        assert_eq!(info.bVisible, saved_info.bVisible);
+
+        set_cursor_size(saved_info.dwSize as u8).unwrap();
+    }
+
     #[test]
     fn test_move_to_next_line_winapi() {
         move_to(0, 2).unwrap();
@@ -272,6 +404,21 @@ mod tests {
         assert_eq!(position().unwrap(), (0, 0));
     }
 
+    #[test]
+    fn test_move_to_is_relative_to_window_winapi() {
+        move_to(0, 0).unwrap();
+
+        let info = ScreenBufferCursor::output()
+            .unwrap()
+            .screen_buffer
+            .info()
+            .unwrap();
+        let window = info.terminal_window();
+        assert_eq!(info.cursor_pos().x, window.left);
+        assert_eq!(info.cursor_pos().y, window.top);
+        assert_eq!(position().unwrap(), (0, 0));
+    }
+
     #[test]
     fn test_move_to_column_winapi() {
         move_to(0, 2).unwrap();
@@ -1,6 +1,7 @@
 //! Module containing error handling logic.
 
 use std::{
+    error::Error,
     fmt::{self, Display, Formatter},
     io,
 };
@@ -11,24 +12,44 @@ use crate::impl_from;
 pub type Result<T> = std::result::Result<T, ErrorKind>;
 
 /// Wrapper for all errors that can occur in `crossterm`.
+///
+/// Every fallible operation of the crate returns it. A command which can't be performed by the
+/// platform at all (e.g. the keyboard enhancement flags with the legacy Windows API) returns an
+/// [`IoError`](#variant.IoError) of the
+/// [`io::ErrorKind::Unsupported`](https://doc.rust-lang.org/std/io/enum.ErrorKind.html) kind.
+/// Commands which just have no effect on a platform (e.g. the bracketed paste with the legacy
+/// Windows API) succeed.
+///
+/// The message of the wrapped error is part of the `Display` output, so
+/// [`source`](https://doc.rust-lang.org/std/error/trait.Error.html#method.source) returns the
+/// source of the wrapped error to not report it twice.
+///
+/// It can be converted to an `io::Error` to be returned from a function returning `io::Result`.
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum ErrorKind {
+    /// An I/O error, e.g. writing a command or reading an event failed.
     IoError(io::Error),
+    /// Formatting a value failed.
     FmtError(fmt::Error),
+    /// A terminal response is not valid UTF-8.
     Utf8Error(std::string::FromUtf8Error),
+    /// A number in a terminal response or an environment variable could not be parsed.
     ParseIntError(std::num::ParseIntError),
+    /// The terminal could not be resized, the reason is given.
     ResizingTerminalFailure(String),
+    /// The terminal title could not be set.
     SettingTerminalTitleFailure,
 }
 
-impl std::error::Error for ErrorKind {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+impl Error for ErrorKind {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        // The wrapped error is already part of the message
         match self {
-            ErrorKind::IoError(e) => Some(e),
-            ErrorKind::FmtError(e) => Some(e),
-            ErrorKind::Utf8Error(e) => Some(e),
-            ErrorKind::ParseIntError(e) => Some(e),
+            ErrorKind::IoError(e) => e.source(),
+            ErrorKind::FmtError(e) => e.source(),
+            ErrorKind::Utf8Error(e) => e.source(),
+            ErrorKind::ParseIntError(e) => e.source(),
             _ => None,
         }
     }
@@ -36,10 +57,27 @@ impl std::error::Error for ErrorKind {
 
 impl Display for ErrorKind {
     fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        match *self {
-            ErrorKind::IoError(_) => write!(fmt, "IO-error occurred"),
-            ErrorKind::ResizingTerminalFailure(_) => write!(fmt, "Cannot resize the terminal"),
-            _ => write!(fmt, "Some error has occurred"),
+        match self {
+            ErrorKind::IoError(e) => write!(fmt, "IO-error occurred: {}", e),
+            ErrorKind::FmtError(e) => write!(fmt, "Formatting failed: {}", e),
+            ErrorKind::Utf8Error(e) => write!(fmt, "Invalid UTF-8: {}", e),
+            ErrorKind::ParseIntError(e) => write!(fmt, "Invalid number: {}", e),
+            ErrorKind::ResizingTerminalFailure(reason) => {
+                write!(fmt, "Cannot resize the terminal: {}", reason)
+            }
+            ErrorKind::SettingTerminalTitleFailure => write!(fmt, "Cannot set the terminal title"),
+        }
+    }
+}
+
+impl From<ErrorKind> for io::Error {
+    fn from(error: ErrorKind) -> Self {
+        match error {
+            ErrorKind::IoError(e) => e,
+            ErrorKind::Utf8Error(_) | ErrorKind::ParseIntError(_) => {
+                io::Error::new(io::ErrorKind::InvalidData, error)
+            }
+            _ => io::Error::other(error),
         }
     }
 }
@@ -48,3 +86,44 @@ impl_from!(io::Error, ErrorKind::IoError);
 impl_from!(fmt::Error, ErrorKind::FmtError);
 impl_from!(std::string::FromUtf8Error, ErrorKind::Utf8Error);
 impl_from!(std::num::ParseIntError, ErrorKind::ParseIntError);
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, io};
+
+    use super::ErrorKind;
+
+    #[test]
+    fn test_display_contains_cause() {
+        let error = ErrorKind::from(io::Error::other("closed"));
+        assert_eq!(error.to_string(), "IO-error occurred: closed");
+
+        let error = ErrorKind::ResizingTerminalFailure("too big".to_string());
+        assert_eq!(error.to_string(), "Cannot resize the terminal: too big");
+
+        let error = ErrorKind::from("x".parse::<u16>().unwrap_err());
+        assert!(error.to_string().starts_with("Invalid number: "));
+    }
+
+    #[test]
+    fn test_source_does_not_repeat_cause() {
+        let error = ErrorKind::from(io::Error::other("closed"));
+        assert!(error.source().is_none());
+
+        let error = ErrorKind::from("x".parse::<u16>().unwrap_err());
+        assert!(error.source().is_none());
+    }
+
+    #[test]
+    fn test_into_io_error() {
+        let error = io::Error::from(ErrorKind::from(io::Error::from(io::ErrorKind::BrokenPipe)));
+        assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+
+        let error = io::Error::from(ErrorKind::from("x".parse::<u16>().unwrap_err()));
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        let error = io::Error::from(ErrorKind::SettingTerminalTitleFailure);
+        assert_eq!(error.kind(), io::ErrorKind::Other);
+        assert_eq!(error.to_string(), "Cannot set the terminal title");
+    }
+}
@@ -5,6 +5,13 @@
 //! * The [`read`](fn.read.html) function returns an [`Event`](enum.Event.html) immediately
 //! (if available) or blocks until an [`Event`](enum.Event.html) is available.
 //!
+//! * The [`read_key`](fn.read_key.html) function blocks until a key is pressed, e.g. for a "press
+//! any key to continue" prompt.
+//!
+//! * The [`read_char`](fn.read_char.html) and [`read_line`](fn.read_line.html) functions read a
+//! character or a whole line of text while the raw mode is enabled,
+//! [`read_line_with`](fn.read_line_with.html) echoes the line to another writer.
+//!
 //! * The [`poll`](fn.poll.html) function allows you to check if there is or isn't an [`Event`](enum.Event.html) available
 //! within the given period of time. In other words - if subsequent call to the [`read`](fn.read.html)
 //! function will block or not.
@@ -23,6 +30,51 @@
 //! [`EnableMouseCapture`](struct.EnableMouseCapture.html) command. See [Command API](../index.html#command-api)
 //! for more information.
 //!
+//! The terminals report the button presses, releases and motion only, use the
+//! [`MouseTracker`](struct.MouseTracker.html) to recognize double clicks and drags.
+//!
+//! ## Resize Events
+//!
+//! Terminal size changes are delivered as [`Event::Resize`](enum.Event.html#variant.Resize)
+//! through the same [`read`](fn.read.html) & [`poll`](fn.poll.html) functions as the input
+//! events, there's no need to install a `SIGWINCH` handler or to watch the console buffer on
+//! your own. They are always enabled.
+//!
+//! On Windows, the console reports size changes only with the `ENABLE_WINDOW_INPUT` input mode,
+//! which is enabled once the first event is polled or read. The previous mode is restored when
+//! the event source is dropped, the global event source lives until the process exits.
+//!
+//! ## Keyboard Enhancements
+//!
+//! Terminals implementing the [kitty keyboard
+//! protocol](https://sw.kovidgoyal.net/kitty/keyboard-protocol/) can report key repeats and
+//! releases and unambiguous key combinations (e.g. `Ctrl + I` vs `Tab`) once enabled with the
+//! [`PushKeyboardEnhancementFlags`](struct.PushKeyboardEnhancementFlags.html) command.
+//!
+//! ## Focus Events
+//!
+//! Focus changes are reported as [`Event::FocusGained`](enum.Event.html#variant.FocusGained) and
+//! [`Event::FocusLost`](enum.Event.html#variant.FocusLost) once enabled with the
+//! [`EnableFocusChange`](struct.EnableFocusChange.html) command.
+//!
+//! ## Signal Events
+//!
+//! On UNIX, the `SIGINT`, `SIGTERM` and `SIGHUP` signals can be delivered as
+//! [`Event::Signal`](enum.Event.html#variant.Signal) after calling
+//! [`enable_signal_events`](fn.enable_signal_events.html).
+//!
+//! ## Grapheme Events
+//!
+//! Characters which form a single grapheme cluster (e.g. a letter followed by a combining accent,
+//! emoji with a skin tone modifier) arrive as a single
+//! [`Event::Grapheme`](enum.Event.html#variant.Grapheme) instead of a key event for each of them.
+//!
+//! ## Paste Events
+//!
+//! Pasted text is delivered key by key unless bracketed paste is enabled with the
+//! [`EnableBracketedPaste`](struct.EnableBracketedPaste.html) command, after which the whole
+//! paste arrives as a single [`Event::Paste`](enum.Event.html#variant.Paste).
+//!
 //! ## Examples
 //!
 //! Blocking read:
@@ -37,6 +89,9 @@
 //!             Event::Key(event) => println!("{:?}", event),
 //!             Event::Mouse(event) => println!("{:?}", event),
 //!             Event::Resize(width, height) => println!("New size {}x{}", width, height),
+//!             Event::Grapheme(text) => println!("Typed {:?}", text),
+//!             Event::Paste(text) => println!("Pasted {:?}", text),
+//!             Event::FocusGained | Event::FocusLost | Event::Signal(_) => {}
 //!         }
 //!     }
 //!     Ok(())
@@ -60,6 +115,9 @@
 //!                 Event::Key(event) => println!("{:?}", event),
 //!                 Event::Mouse(event) => println!("{:?}", event),
 //!                 Event::Resize(width, height) => println!("New size {}x{}", width, height),
+//!                 Event::Grapheme(text) => println!("Typed {:?}", text),
+//!                 Event::Paste(text) => println!("Pasted {:?}", text),
+//!                 Event::FocusGained | Event::FocusLost | Event::Signal(_) => {}
 //!             }
 //!         } else {
 //!             // Timeout expired and no `Event` is available
@@ -73,6 +131,10 @@
 //! them (`event-*`).
 
 use std::fmt;
+use std::io::{self, Write};
+#[cfg(unix)]
+use std::os::unix::io::{IntoRawFd, RawFd};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use bitflags::bitflags;
@@ -80,15 +142,28 @@ use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(unix)]
+use crate::style::Colored;
 use crate::{csi, Command, Result};
 use filter::{EventFilter, Filter};
+pub use key_combination::{KeyCombination, ParseKeyCombinationError};
+pub use mouse::{MouseGesture, MouseTracker};
 use read::InternalEventReader;
+pub use recording::{Recorder, Recording};
+#[cfg(unix)]
+use source::unix::UnixInternalEventSource;
 #[cfg(feature = "event-stream")]
 pub use stream::EventStream;
+#[cfg(unix)]
+use sys::unix::file_descriptor::FileDesc;
 use timeout::PollTimeout;
 
 pub(crate) mod filter;
+mod grapheme;
+mod key_combination;
+mod mouse;
 mod read;
+mod recording;
 mod source;
 #[cfg(feature = "event-stream")]
 mod stream;
@@ -99,6 +174,12 @@ mod timeout;
 /// This needs to be static because there can be one event reader.
 static INTERNAL_EVENT_READER: Mutex<Option<InternalEventReader>> = parking_lot::const_mutex(None);
 
+/// Whether the key events carry the Windows virtual-key and scan codes.
+static REPORT_RAW_KEY_CODES: AtomicBool = AtomicBool::new(false);
+
+/// How long to wait for the rest of an escape sequence after a lone `ESC`.
+static ESCAPE_TIMEOUT: Mutex<Duration> = parking_lot::const_mutex(Duration::from_secs(0));
+
 fn lock_internal_event_reader() -> MappedMutexGuard<'static, InternalEventReader> {
     MutexGuard::map(INTERNAL_EVENT_READER.lock(), |reader| {
         reader.get_or_insert_with(InternalEventReader::default)
@@ -153,6 +234,42 @@ fn try_lock_internal_event_reader_for(
 ///     poll(Duration::from_millis(100))
 /// }
 /// ```
+///
+/// Handle the events of a game loop and redraw at 60 frames per second:
+///
+/// ```no_run
+/// use std::time::{Duration, Instant};
+///
+/// use crossterm::{event::{poll, read, Event, KeyCode}, Result};
+///
+/// fn game_loop() -> Result<()> {
+///     let frame = Duration::from_secs(1) / 60;
+///     let mut next_frame = Instant::now() + frame;
+///
+///     loop {
+///         // Wait for an `Event` until the next frame must be drawn
+///         let timeout = next_frame.saturating_duration_since(Instant::now());
+///         if poll(timeout)? {
+///             if let Event::Key(event) = read()? {
+///                 if event.code == KeyCode::Esc {
+///                     return Ok(());
+///                 }
+///             }
+///         } else {
+///             // draw the next frame
+///             next_frame += frame;
+///         }
+///     }
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * On UNIX, the terminal input and the `SIGWINCH` signal are polled with `mio` (`epoll`,
+///   `kqueue`, ...). On Windows, the console input handle is waited for with
+///   `WaitForMultipleObjects`.
+/// * A wait interrupted by a signal is resumed. `Ok(false)` is returned before the timeout
+///   expires only if an [`EventStream`](struct.EventStream.html) wakes the reader up.
 pub fn poll(timeout: Duration) -> Result<bool> {
     poll_internal(Some(timeout), &EventFilter)
 }
@@ -204,6 +321,395 @@ pub fn read() -> Result<Event> {
     }
 }
 
+/// Reads a single [`KeyEvent`](struct.KeyEvent.html).
+///
+/// This function blocks until a key is pressed, the other events read in the meantime (mouse,
+/// resize, key releases) are dropped. Use [`read`](fn.read.html) to handle all the events.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::{
+///     event::{read_key, KeyCode},
+///     terminal, Result,
+/// };
+///
+/// fn main() -> Result<()> {
+///     terminal::enable_raw_mode()?;
+///
+///     println!("Press any key to continue, q to quit\r");
+///     let key = read_key()?;
+///
+///     terminal::disable_raw_mode()?;
+///     if key.code == KeyCode::Char('q') {
+///         return Ok(());
+///     }
+///     // ...
+///     Ok(())
+/// }
+/// ```
+pub fn read_key() -> Result<KeyEvent> {
+    loop {
+        if let Event::Key(event) = read()? {
+            if event.kind != KeyEventKind::Release {
+                return Ok(event);
+            }
+        }
+    }
+}
+
+/// Reads a single character.
+///
+/// This function blocks until a character key is pressed. Non-character keys and keys pressed
+/// together with `Ctrl` or `Alt` are skipped, as are the other events. The character is not
+/// echoed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::{event::read_char, terminal, Result};
+///
+/// fn main() -> Result<()> {
+///     terminal::enable_raw_mode()?;
+///
+///     println!("Continue? [y/n]\r");
+///     let answer = read_char()?;
+///
+///     terminal::disable_raw_mode()?;
+///     println!("{}", answer == 'y');
+///     Ok(())
+/// }
+/// ```
+pub fn read_char() -> Result<char> {
+    loop {
+        let event = read_key()?;
+        if let KeyCode::Char(c) = event.code {
+            if !event
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+            {
+                return Ok(c);
+            }
+        }
+    }
+}
+
+/// Reads a line of text.
+///
+/// This function blocks until `Enter` is pressed and returns the line without the line ending.
+/// It's meant to be used while the raw mode is enabled, it echoes the typed characters to the
+/// standard output and supports deleting them with `Backspace`.
+///
+/// Pressing `Ctrl + C` aborts the reading with an
+/// [`io::ErrorKind::Interrupted`](https://doc.rust-lang.org/std/io/enum.ErrorKind.html) error,
+/// because the raw mode doesn't generate `SIGINT`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::{event::read_line, terminal, Result};
+///
+/// fn main() -> Result<()> {
+///     terminal::enable_raw_mode()?;
+///
+///     print!("Name: ");
+///     let name = read_line();
+///
+///     terminal::disable_raw_mode()?;
+///     println!("Hello {}", name?);
+///     Ok(())
+/// }
+/// ```
+pub fn read_line() -> Result<String> {
+    read_line_with(&mut io::stdout())
+}
+
+/// Reads a line of text and echoes it to the given writer.
+///
+/// It works like [`read_line`](fn.read_line.html), but the typed characters are written to
+/// `writer` instead of the standard output, e.g. to the standard error or to a remote terminal.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::stderr;
+///
+/// use crossterm::{event::read_line_with, terminal, Result};
+///
+/// fn main() -> Result<()> {
+///     terminal::enable_raw_mode()?;
+///
+///     eprint!("Name: ");
+///     let name = read_line_with(&mut stderr());
+///
+///     terminal::disable_raw_mode()?;
+///     eprintln!("Hello {}", name?);
+///     Ok(())
+/// }
+/// ```
+pub fn read_line_with(writer: &mut impl Write) -> Result<String> {
+    read_line_from(writer, read_key)
+}
+
+fn read_line_from(
+    writer: &mut impl Write,
+    mut read_key: impl FnMut() -> Result<KeyEvent>,
+) -> Result<String> {
+    writer.flush()?;
+
+    let mut line = String::new();
+    loop {
+        let event = read_key()?;
+        match event.code {
+            KeyCode::Enter => {
+                writer.write_all(b"\r\n")?;
+                writer.flush()?;
+                return Ok(line);
+            }
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                writer.write_all(b"\r\n")?;
+                writer.flush()?;
+                return Err(io::Error::from(io::ErrorKind::Interrupted).into());
+            }
+            KeyCode::Backspace if line.pop().is_some() => {
+                writer.write_all(b"\x08 \x08")?;
+            }
+            KeyCode::Char(c)
+                if !event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                line.push(c);
+                write!(writer, "{}", c)?;
+            }
+            _ => {}
+        }
+        writer.flush()?;
+    }
+}
+
+/// Queues an [`Event`](enum.Event.html) to be returned by [`read`](fn.read.html) and
+/// [`poll`](fn.poll.html).
+///
+/// The injected events are returned in order, before the events that arrive from the terminal
+/// later. It allows to drive an input-driven application from unit tests without a real
+/// terminal.
+///
+/// # Notes
+///
+/// This function blocks while another thread is waiting in [`read`](fn.read.html) or
+/// [`poll`](fn.poll.html), or while an [`EventStream`](struct.EventStream.html) is waiting
+/// for an event.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::event::{inject, read, Event, KeyCode};
+///
+/// fn main() -> crossterm::Result<()> {
+///     inject(Event::Key(KeyCode::Char('q').into()));
+///
+///     assert_eq!(read()?, Event::Key(KeyCode::Char('q').into()));
+///     Ok(())
+/// }
+/// ```
+pub fn inject(event: Event) {
+    lock_internal_event_reader().inject(InternalEvent::Event(event));
+}
+
+/// Returns the file descriptor the events are read from.
+///
+/// It's the standard input if it's a TTY, `/dev/tty` otherwise. It allows to integrate the
+/// terminal input into another event loop (`mio`, `epoll`, ...): register the file descriptor
+/// for readability and use [`poll`](fn.poll.html) with a zero timeout and [`read`](fn.read.html)
+/// to get the events once it's readable.
+///
+/// # Notes
+///
+/// * Never read from the file descriptor directly, the bytes wouldn't be seen by the event
+///   parser.
+/// * Several events can be decoded from a single readiness notification, drain them with
+///   `poll(Duration::from_secs(0))` before waiting again.
+/// * [`Event::Resize`](enum.Event.html#variant.Resize) events come from the `SIGWINCH` signal,
+///   they don't make the file descriptor readable.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use crossterm::event::{input_fd, poll, read};
+///
+/// fn main() -> crossterm::Result<()> {
+///     let fd = input_fd()?;
+///     // ... register `fd` in your event loop, then once it's readable:
+///     while poll(Duration::from_secs(0))? {
+///         println!("{:?}", read()?);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(unix)]
+pub fn input_fd() -> Result<RawFd> {
+    lock_internal_event_reader().raw_fd()
+}
+
+/// Reads the events from the given input instead of the standard input or `/dev/tty`.
+///
+/// The input can be anything owning a file descriptor which can be polled for readability,
+/// e.g. a `File` opened on another terminal device or a serial port, a `UnixStream` or a
+/// `TcpStream`. It's closed once replaced or when the process exits.
+///
+/// # Notes
+///
+/// * Regular files can't be polled, use [`Recording`](struct.Recording.html) to replay
+///   events from a file.
+/// * The raw mode functions of the [`terminal`](../terminal/index.html) module configure the
+///   controlling terminal, not the given input.
+/// * Call this function before creating an [`EventStream`](struct.EventStream.html).
+/// * The signals enabled with [`enable_signal_events`](fn.enable_signal_events.html) keep being
+///   delivered as events.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::fs::OpenOptions;
+///
+/// use crossterm::event::{read, set_input};
+///
+/// fn main() -> crossterm::Result<()> {
+///     let tty = OpenOptions::new()
+///         .read(true)
+///         .write(true)
+///         .open("/dev/pts/3")?;
+///     set_input(tty)?;
+///
+///     println!("{:?}", read()?);
+///     Ok(())
+/// }
+/// ```
+#[cfg(unix)]
+pub fn set_input(input: impl IntoRawFd) -> Result<()> {
+    let input_fd = FileDesc::new(input.into_raw_fd(), true);
+    let source = UnixInternalEventSource::from_file_descriptor(input_fd)?;
+    lock_internal_event_reader().set_source(Box::new(source))
+}
+
+/// Delivers the `SIGINT`, `SIGTERM` and `SIGHUP` signals as
+/// [`Event::Signal`](enum.Event.html#variant.Signal) events.
+///
+/// It allows the applications with a single event loop to shut down gracefully, e.g. when the
+/// terminal is closed.
+///
+/// # Notes
+///
+/// * Once enabled, the signals don't terminate the process anymore, not even after the event
+///   reader is dropped. It can't be disabled.
+/// * This function blocks while another thread is waiting in [`read`](fn.read.html) or
+///   [`poll`](fn.poll.html).
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::event::{enable_signal_events, read, Event};
+///
+/// fn main() -> crossterm::Result<()> {
+///     enable_signal_events()?;
+///
+///     loop {
+///         match read()? {
+///             Event::Signal(signal) => {
+///                 println!("Shutting down after {:?}", signal);
+///                 break;
+///             }
+///             event => println!("{:?}", event),
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[cfg(unix)]
+pub fn enable_signal_events() -> Result<()> {
+    lock_internal_event_reader().enable_signal_events()
+}
+
+/// Sets whether the key events carry the Windows virtual-key and scan codes in the
+/// [`KeyEvent::raw_codes`](struct.KeyEvent.html#structfield.raw_codes) field.
+///
+/// It's disabled by default, because the key events with raw codes don't compare equal to the
+/// ones constructed from a [`KeyCode`](enum.KeyCode.html).
+///
+/// # Notes
+///
+/// The terminals on UNIX send characters and escape sequences only, the raw codes are set there
+/// only in the win32-input-mode of Windows Terminal, see
+/// [`EnableWin32InputMode`](struct.EnableWin32InputMode.html).
+pub fn set_report_raw_key_codes(enabled: bool) {
+    REPORT_RAW_KEY_CODES.store(enabled, Ordering::SeqCst);
+}
+
+/// Returns whether the key events carry the Windows virtual-key and scan codes, see
+/// [`set_report_raw_key_codes`](fn.set_report_raw_key_codes.html).
+pub fn report_raw_key_codes() -> bool {
+    REPORT_RAW_KEY_CODES.load(Ordering::SeqCst)
+}
+
+/// Discards the input which was received, but not read yet.
+///
+/// Call it before prompts like "Press any key to continue", so they aren't satisfied by the
+/// keys typed during a long computation. The pending input is read and the key, mouse and paste
+/// events are dropped, the other events (e.g. resize, focus and signal events) are kept.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::event::{discard_input, read_key};
+///
+/// fn main() -> crossterm::Result<()> {
+///     // ... a long computation
+///
+///     discard_input()?;
+///     println!("Press any key to continue");
+///     read_key()?;
+///     Ok(())
+/// }
+/// ```
+pub fn discard_input() -> Result<()> {
+    lock_internal_event_reader().discard_events()
+}
+
+/// Sets how long to wait for the rest of an escape sequence after a lone `ESC` byte is read.
+///
+/// The `Esc` key and escape sequences (arrow keys, mouse events, ...) share the same leading
+/// byte. If the `ESC` byte arrives alone and nothing follows within the timeout, it's reported
+/// as the `Esc` key. The default timeout is zero, which reports the `Esc` key as soon as the
+/// `ESC` byte isn't immediately followed by other input.
+///
+/// Lengthen the timeout if the escape sequences are split by a slow connection (e.g. SSH) and
+/// show up as `Esc` followed by other keys. Keep it short to avoid the `Esc` key latency.
+///
+/// # Notes
+///
+/// The Windows console reports keys natively, the timeout is used on UNIX only.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use crossterm::event;
+///
+/// event::set_escape_timeout(Duration::from_millis(50));
+/// ```
+pub fn set_escape_timeout(timeout: Duration) {
+    *ESCAPE_TIMEOUT.lock() = timeout;
+}
+
+/// Returns the timeout set with [`set_escape_timeout`](fn.set_escape_timeout.html).
+pub fn escape_timeout() -> Duration {
+    *ESCAPE_TIMEOUT.lock()
+}
+
 /// Polls to check if there are any `InternalEvent`s that can be read within the given duration.
 pub(crate) fn poll_internal<F>(timeout: Option<Duration>, filter: &F) -> Result<bool>
 where
@@ -293,17 +799,261 @@ impl Command for DisableMouseCapture {
     }
 }
 
+/// A command that enables bracketed paste mode.
+///
+/// While enabled, text pasted into the terminal is reported as a single
+/// [`Event::Paste`](enum.Event.html#variant.Paste) instead of a series of key events.
+///
+/// # Notes
+///
+/// Bracketed paste is only reported by terminals understanding the xterm `?2004` mode. The
+/// Windows console doesn't report pastes at all, they always arrive as key events there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnableBracketedPaste;
+
+impl Command for EnableBracketedPaste {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?2004h"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that disables bracketed paste mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisableBracketedPaste;
+
+impl Command for DisableBracketedPaste {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?2004l"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that enables focus event emission.
+///
+/// It should be paired with [`DisableFocusChange`](struct.DisableFocusChange.html) at the end of
+/// execution.
+///
+/// Focus events can be captured with [read](./fn.read.html)/[poll](./fn.poll.html).
+///
+/// # Notes
+///
+/// The Windows console always reports focus changes, the command is a no-op there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnableFocusChange;
+
+impl Command for EnableFocusChange {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?1004h"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that disables focus event emission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisableFocusChange;
+
+impl Command for DisableFocusChange {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?1004l"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+}
+
+bitflags! {
+    /// Represents special flags that tell compatible terminals to add extra information to
+    /// keyboard events.
+    ///
+    /// See <https://sw.kovidgoyal.net/kitty/keyboard-protocol/#progressive-enhancement> for more
+    /// information.
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub struct KeyboardEnhancementFlags: u8 {
+        /// Represent Escape and modified keys using CSI-u sequences, so they can be unambiguously
+        /// read, e.g. `Ctrl + I` is reported differently than `Tab`.
+        const DISAMBIGUATE_ESCAPE_CODES = 0b0000_0001;
+        /// Add extra events with [`KeyEvent.kind`](struct.KeyEvent.html#structfield.kind) set to
+        /// [`KeyEventKind::Repeat`](enum.KeyEventKind.html#variant.Repeat) or
+        /// [`KeyEventKind::Release`](enum.KeyEventKind.html#variant.Release) when keys are
+        /// autorepeated or released.
+        const REPORT_EVENT_TYPES = 0b0000_0010;
+        /// Send the shifted variant of the key along with the key itself.
+        const REPORT_ALTERNATE_KEYS = 0b0000_0100;
+        /// Represent all keyboard events as CSI-u sequences, including the plain text ones.
+        const REPORT_ALL_KEYS_AS_ESCAPE_CODES = 0b0000_1000;
+    }
+}
+
+/// A command that enables the [kitty keyboard
+/// protocol](https://sw.kovidgoyal.net/kitty/keyboard-protocol/) and pushes the given flags to
+/// the terminal's stack of enhancement flags.
+///
+/// It should be paired with [`PopKeyboardEnhancementFlags`](struct.PopKeyboardEnhancementFlags.html)
+/// at the end of execution.
+///
+/// # Notes
+///
+/// * Terminals not implementing the protocol ignore the command and keep sending the legacy
+///   sequences.
+/// * Not supported by the legacy Windows API, an error is returned there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PushKeyboardEnhancementFlags(pub KeyboardEnhancementFlags);
+
+impl Command for PushKeyboardEnhancementFlags {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, csi!(">{}u"), self.0.bits())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Keyboard enhancement flags are not supported by the legacy Windows API.",
+        )
+        .into())
+    }
+}
+
+/// A command that pops the last keyboard enhancement flags pushed with
+/// [`PushKeyboardEnhancementFlags`](struct.PushKeyboardEnhancementFlags.html).
+///
+/// # Notes
+///
+/// Not supported by the legacy Windows API, an error is returned there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PopKeyboardEnhancementFlags;
+
+impl Command for PopKeyboardEnhancementFlags {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("<1u"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Keyboard enhancement flags are not supported by the legacy Windows API.",
+        )
+        .into())
+    }
+}
+
+/// A command that enables the win32-input-mode of Windows Terminal.
+///
+/// Windows Terminal then reports every key press and release with its Windows virtual-key and
+/// scan codes, so the keys without an escape sequence and the
+/// [`KeyEventKind::Release`](enum.KeyEventKind.html#variant.Release) events arrive even
+/// though the input is delivered as a VT stream (e.g. over SSH). It should be paired with
+/// [`DisableWin32InputMode`](struct.DisableWin32InputMode.html) at the end of execution.
+///
+/// # Notes
+///
+/// * Other terminals ignore the command.
+/// * The Windows console reports the keys natively, the command is a no-op there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnableWin32InputMode;
+
+impl Command for EnableWin32InputMode {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?9001h"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        false
+    }
+}
+
+/// A command that disables the win32-input-mode of Windows Terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisableWin32InputMode;
+
+impl Command for DisableWin32InputMode {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?9001l"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        false
+    }
+}
+
 /// Represents an event.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Hash)]
 pub enum Event {
+    /// The terminal gained focus.
+    FocusGained,
+    /// The terminal lost focus.
+    FocusLost,
     /// A single key event with additional pressed modifiers.
     Key(KeyEvent),
+    /// A grapheme cluster typed at once, which consists of several characters (e.g. a letter
+    /// followed by a combining accent, emoji with a skin tone modifier, a flag).
+    ///
+    /// Emitted instead of a [`KeyCode::Char`](enum.KeyCode.html#variant.Char) key event for each
+    /// of the characters, e.g. when an input method commits the cluster. The characters are
+    /// assembled only if they arrive together and no modifiers other than Shift are pressed.
+    Grapheme(String),
     /// A single mouse event with additional pressed modifiers.
     Mouse(MouseEvent),
     /// An resize event with new dimensions after resize (columns, rows).
-    /// **Note** that resize events can be occur in batches.
+    ///
+    /// The dimensions are the ones returned by [`terminal::size`](../terminal/fn.size.html). On
+    /// UNIX this is triggered by the `SIGWINCH` signal, on Windows by the
+    /// `WINDOW_BUFFER_SIZE_EVENT` input record.
+    ///
+    /// **Note** that resize events can be occur in batches, e.g. while the user drags the window
+    /// border. Only the last one matters for the layout.
     Resize(u16, u16),
+    /// A string that was pasted into the terminal.
+    ///
+    /// Only emitted while bracketed paste is enabled, see
+    /// [`EnableBracketedPaste`](struct.EnableBracketedPaste.html).
+    Paste(String),
+    /// A signal was delivered to the process.
+    ///
+    /// Only emitted on UNIX after calling
+    /// [`enable_signal_events`](fn.enable_signal_events.html).
+    Signal(Signal),
+}
+
+/// Represents a signal delivered to the process.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Signal {
+    /// `SIGINT`, sent by the terminal for `Ctrl + C` unless the raw mode is enabled.
+    Interrupt,
+    /// `SIGTERM`, a request to terminate the process.
+    Terminate,
+    /// `SIGHUP`, the terminal was closed.
+    Hangup,
 }
 
 /// Represents a mouse event.
@@ -383,6 +1133,22 @@ bitflags! {
     }
 }
 
+/// Represents a keyboard event kind.
+///
+/// Only the [`Press`](enum.KeyEventKind.html#variant.Press) kind is reported unless the
+/// [`KeyboardEnhancementFlags::REPORT_EVENT_TYPES`](struct.KeyboardEnhancementFlags.html) flag
+/// was pushed with [`PushKeyboardEnhancementFlags`](struct.PushKeyboardEnhancementFlags.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum KeyEventKind {
+    /// The key was pressed.
+    Press,
+    /// The key is held down and the press is repeated.
+    Repeat,
+    /// The key was released.
+    Release,
+}
+
 /// Represents a key event.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
@@ -391,23 +1157,52 @@ pub struct KeyEvent {
     pub code: KeyCode,
     /// Additional key modifiers.
     pub modifiers: KeyModifiers,
+    /// Kind of the event.
+    pub kind: KeyEventKind,
+    /// The platform specific codes of the key.
+    ///
+    /// Only set on Windows or in the win32-input-mode (see
+    /// [`EnableWin32InputMode`](struct.EnableWin32InputMode.html)), after enabling them with
+    /// [`set_report_raw_key_codes`](fn.set_report_raw_key_codes.html).
+    pub raw_codes: Option<RawKeyCodes>,
 }
 
 impl KeyEvent {
+    /// Creates a key press event of the given key and modifiers.
     pub fn new(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
-        KeyEvent { code, modifiers }
+        KeyEvent::new_with_kind(code, modifiers, KeyEventKind::Press)
     }
-}
 
-impl From<KeyCode> for KeyEvent {
-    fn from(code: KeyCode) -> Self {
+    /// Creates a key event of the given key, modifiers and kind.
+    pub fn new_with_kind(code: KeyCode, modifiers: KeyModifiers, kind: KeyEventKind) -> KeyEvent {
         KeyEvent {
             code,
-            modifiers: KeyModifiers::empty(),
+            modifiers,
+            kind,
+            raw_codes: None,
         }
     }
 }
 
+/// Represents the platform specific codes of a key.
+///
+/// The scan code identifies the physical key regardless of the keyboard layout, e.g. to bind
+/// the keys in the `W`, `A`, `S`, `D` positions on the AZERTY keyboards too.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct RawKeyCodes {
+    /// The virtual-key code (`wVirtualKeyCode`), depends on the keyboard layout.
+    pub virtual_key_code: u16,
+    /// The virtual scan code (`wVirtualScanCode`), depends on the keyboard hardware.
+    pub scan_code: u16,
+}
+
+impl From<KeyCode> for KeyEvent {
+    fn from(code: KeyCode) -> Self {
+        KeyEvent::new(code, KeyModifiers::empty())
+    }
+}
+
 /// Represents a key.
 #[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -447,6 +1242,12 @@ pub enum KeyCode {
     /// A character.
     ///
     /// `KeyCode::Char('c')` represents `c` character, etc.
+    ///
+    /// The character is a single Unicode scalar value. Grapheme clusters composed of several of
+    /// them (e.g. a letter followed by a combining accent, emoji with skin tone modifiers) are
+    /// reported as [`Event::Grapheme`](enum.Event.html#variant.Grapheme). Enable bracketed paste
+    /// with [`EnableBracketedPaste`](struct.EnableBracketedPaste.html) to receive pasted text as
+    /// a whole.
     Char(char),
     /// Null.
     Null,
@@ -465,4 +1266,96 @@ pub(crate) enum InternalEvent {
     /// A cursor position (`col`, `row`).
     #[cfg(unix)]
     CursorPosition(u16, u16),
+    /// The default foreground or background color of the terminal.
+    #[cfg(unix)]
+    DefaultColor(Colored),
+    /// The state of a terminal mode (`mode`, `status`), the answer to a `DECRQM` query.
+    #[cfg(unix)]
+    ModeReport(u16, u8),
+    /// The primary device attributes, the answer to a `DA1` query.
+    #[cfg(unix)]
+    PrimaryDeviceAttributes,
+    /// Whether a terminfo capability (`name`, `valid`) is known by the terminal, the answer to an
+    /// `XTGETTCAP` query.
+    #[cfg(unix)]
+    TerminfoCapability(String, bool),
+    /// The size of the text area in pixels (`width`, `height`).
+    #[cfg(unix)]
+    PixelSize(u16, u16),
+    /// The text of the clipboard, the answer to an `OSC 52` query.
+    #[cfg(unix)]
+    ClipboardContents(String),
+    /// The window title, the answer to a `CSI 21 t` query.
+    #[cfg(unix)]
+    Title(String),
+    /// A half of a UTF-16 surrogate pair in the win32-input-mode, the key event holds the
+    /// modifiers, kind and raw codes of the record. The parser combines the halves into a
+    /// `KeyCode::Char` key event.
+    #[cfg(unix)]
+    Utf16Surrogate(u16, KeyEvent),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::io;
+
+    use super::{read_line_from, KeyCode, KeyEvent, KeyModifiers};
+    use crate::{ErrorKind, Result};
+
+    fn keys(events: Vec<KeyEvent>) -> impl FnMut() -> Result<KeyEvent> {
+        let mut events: VecDeque<KeyEvent> = events.into();
+        move || Ok(events.pop_front().expect("no more keys"))
+    }
+
+    #[test]
+    fn test_read_line_echoes_and_edits() {
+        let mut output = Vec::new();
+        let line = read_line_from(
+            &mut output,
+            keys(vec![
+                KeyCode::Char('a').into(),
+                KeyCode::Char('x').into(),
+                KeyCode::Backspace.into(),
+                KeyCode::Char('ž').into(),
+                KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL),
+                KeyCode::Left.into(),
+                KeyCode::Enter.into(),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(line, "až");
+        assert_eq!(output, "ax\x08 \x08ž\r\n".as_bytes());
+    }
+
+    #[test]
+    fn test_read_line_backspace_on_empty_line() {
+        let mut output = Vec::new();
+        let line = read_line_from(
+            &mut output,
+            keys(vec![KeyCode::Backspace.into(), KeyCode::Enter.into()]),
+        )
+        .unwrap();
+
+        assert_eq!(line, "");
+        assert_eq!(output, b"\r\n");
+    }
+
+    #[test]
+    fn test_read_line_interrupted() {
+        let mut output = Vec::new();
+        let result = read_line_from(
+            &mut output,
+            keys(vec![
+                KeyCode::Char('a').into(),
+                KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            ]),
+        );
+
+        match result {
+            Err(ErrorKind::IoError(e)) => assert_eq!(e.kind(), io::ErrorKind::Interrupted),
+            _ => panic!("expected an interrupted error"),
+        }
+    }
 }
@@ -17,6 +17,66 @@ impl Filter for CursorPositionFilter {
     }
 }
 
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) struct DefaultColorFilter;
+
+#[cfg(unix)]
+impl Filter for DefaultColorFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        matches!(*event, InternalEvent::DefaultColor(_))
+    }
+}
+
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) struct CapabilitiesFilter;
+
+#[cfg(unix)]
+impl Filter for CapabilitiesFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        matches!(
+            *event,
+            InternalEvent::ModeReport(_, _)
+                | InternalEvent::TerminfoCapability(_, _)
+                | InternalEvent::PrimaryDeviceAttributes
+        )
+    }
+}
+
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) struct PixelSizeFilter;
+
+#[cfg(unix)]
+impl Filter for PixelSizeFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        matches!(*event, InternalEvent::PixelSize(_, _))
+    }
+}
+
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) struct ClipboardFilter;
+
+#[cfg(unix)]
+impl Filter for ClipboardFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        matches!(*event, InternalEvent::ClipboardContents(_))
+    }
+}
+
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) struct TitleFilter;
+
+#[cfg(unix)]
+impl Filter for TitleFilter {
+    fn eval(&self, event: &InternalEvent) -> bool {
+        matches!(*event, InternalEvent::Title(_))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct EventFilter;
 
@@ -32,9 +92,11 @@ impl Filter for EventFilter {
     }
 }
 
+#[cfg(test)]
 #[derive(Debug, Clone)]
 pub(crate) struct InternalEventFilter;
 
+#[cfg(test)]
 impl Filter for InternalEventFilter {
     fn eval(&self, _: &InternalEvent) -> bool {
         true
@@ -44,8 +106,12 @@ impl Filter for InternalEventFilter {
 #[cfg(test)]
 #[cfg(unix)]
 mod tests {
+    use crate::style::{Color, Colored};
+
     use super::{
-        super::Event, CursorPositionFilter, EventFilter, Filter, InternalEvent, InternalEventFilter,
+        super::Event, CapabilitiesFilter, ClipboardFilter, CursorPositionFilter,
+        DefaultColorFilter, EventFilter, Filter, InternalEvent, InternalEventFilter,
+        PixelSizeFilter, TitleFilter,
     };
 
     #[test]
@@ -54,6 +120,43 @@ mod tests {
         assert!(CursorPositionFilter.eval(&InternalEvent::CursorPosition(0, 0)));
     }
 
+    #[test]
+    fn test_default_color_filter_filters_default_color() {
+        assert!(!DefaultColorFilter.eval(&InternalEvent::Event(Event::Resize(10, 10))));
+        assert!(!DefaultColorFilter.eval(&InternalEvent::CursorPosition(0, 0)));
+        assert!(
+            DefaultColorFilter.eval(&InternalEvent::DefaultColor(Colored::ForegroundColor(
+                Color::Reset
+            )))
+        );
+    }
+
+    #[test]
+    fn test_capabilities_filter_filters_capabilities() {
+        assert!(!CapabilitiesFilter.eval(&InternalEvent::Event(Event::Resize(10, 10))));
+        assert!(!CapabilitiesFilter.eval(&InternalEvent::CursorPosition(0, 0)));
+        assert!(CapabilitiesFilter.eval(&InternalEvent::ModeReport(2026, 2)));
+        assert!(CapabilitiesFilter.eval(&InternalEvent::PrimaryDeviceAttributes));
+    }
+
+    #[test]
+    fn test_pixel_size_filter_filters_pixel_size() {
+        assert!(!PixelSizeFilter.eval(&InternalEvent::Event(Event::Resize(10, 10))));
+        assert!(PixelSizeFilter.eval(&InternalEvent::PixelSize(800, 600)));
+    }
+
+    #[test]
+    fn test_clipboard_filter_filters_clipboard_contents() {
+        assert!(!ClipboardFilter.eval(&InternalEvent::Event(Event::Resize(10, 10))));
+        assert!(ClipboardFilter.eval(&InternalEvent::ClipboardContents(String::from("text"))));
+    }
+
+    #[test]
+    fn test_title_filter_filters_title() {
+        assert!(!TitleFilter.eval(&InternalEvent::Event(Event::Resize(10, 10))));
+        assert!(TitleFilter.eval(&InternalEvent::Title(String::from("vim"))));
+    }
+
     #[test]
     fn test_event_filter_filters_events() {
         assert!(EventFilter.eval(&InternalEvent::Event(Event::Resize(10, 10))));
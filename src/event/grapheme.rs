@@ -0,0 +1,180 @@
+use super::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+/// A grapheme cluster assembled from the characters of consecutive key events.
+///
+/// The clusters are assembled with a subset of the Unicode segmentation rules (UAX #29), which
+/// covers the combining diacritical marks, the variation selectors, the emoji modifiers, the
+/// emoji tag sequences, the zero width joiner sequences and the regional indicator (flag) pairs.
+#[derive(Debug)]
+pub(crate) struct Grapheme {
+    // The key event of the first character
+    key_event: KeyEvent,
+    text: String,
+}
+
+impl Grapheme {
+    /// Starts a cluster with the character of the key event, `None` if the key event doesn't
+    /// type a character (e.g. it's a release or the Control key is pressed).
+    pub(crate) fn start(key_event: KeyEvent) -> Option<Grapheme> {
+        let character = typed_character(&key_event)?;
+
+        Some(Grapheme {
+            key_event,
+            text: character.to_string(),
+        })
+    }
+
+    /// Appends the character of the key event if it continues the cluster, returns `false`
+    /// otherwise.
+    pub(crate) fn extend(&mut self, key_event: &KeyEvent) -> bool {
+        match typed_character(key_event) {
+            Some(character) if continues_cluster(&self.text, character) => {
+                self.text.push(character);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the key event of the character if the cluster consists of a single one,
+    /// `Event::Grapheme` otherwise.
+    pub(crate) fn into_event(self) -> Event {
+        if self.text.chars().nth(1).is_none() {
+            Event::Key(self.key_event)
+        } else {
+            Event::Grapheme(self.text)
+        }
+    }
+}
+
+fn typed_character(key_event: &KeyEvent) -> Option<char> {
+    match key_event.code {
+        KeyCode::Char(character)
+            if key_event.kind == KeyEventKind::Press
+                && (key_event.modifiers - KeyModifiers::SHIFT).is_empty() =>
+        {
+            Some(character)
+        }
+        _ => None,
+    }
+}
+
+fn continues_cluster(text: &str, character: char) -> bool {
+    if character == ZERO_WIDTH_JOINER || text.ends_with(ZERO_WIDTH_JOINER) {
+        return true;
+    }
+
+    if is_regional_indicator(character) {
+        // The regional indicators form pairs (e.g. `🇨🇿`)
+        return text.chars().all(is_regional_indicator) && text.chars().count() % 2 == 1;
+    }
+
+    matches!(
+        character,
+        // Combining diacritical marks (e.g. `e` + U+0301 = `é`)
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+            // Variation selectors (e.g. the emoji presentation U+FE0F)
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{E0100}'..='\u{E01EF}'
+            // Emoji modifiers (skin tones)
+            | '\u{1F3FB}'..='\u{1F3FF}'
+            // Tags (e.g. the subdivision flags)
+            | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+fn is_regional_indicator(character: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&character)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grapheme, KeyCode, KeyEvent, KeyModifiers};
+    use crate::event::Event;
+
+    fn assemble(text: &str) -> Vec<Event> {
+        let mut events = Vec::new();
+        let mut grapheme: Option<Grapheme> = None;
+
+        for character in text.chars() {
+            let key_event = KeyEvent::from(KeyCode::Char(character));
+            if let Some(grapheme) = grapheme.as_mut() {
+                if grapheme.extend(&key_event) {
+                    continue;
+                }
+            }
+            events.extend(grapheme.take().map(Grapheme::into_event));
+            grapheme = Grapheme::start(key_event);
+        }
+        events.extend(grapheme.map(Grapheme::into_event));
+
+        events
+    }
+
+    #[test]
+    fn test_single_characters() {
+        assert_eq!(
+            assemble("ab"),
+            vec![
+                Event::Key(KeyCode::Char('a').into()),
+                Event::Key(KeyCode::Char('b').into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_combining_mark() {
+        assert_eq!(
+            assemble("e\u{0301}a"),
+            vec![
+                Event::Grapheme(String::from("e\u{0301}")),
+                Event::Key(KeyCode::Char('a').into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_emoji_sequences() {
+        // Thumbs up with a skin tone
+        assert_eq!(
+            assemble("\u{1F44D}\u{1F3FD}"),
+            vec![Event::Grapheme(String::from("\u{1F44D}\u{1F3FD}"))]
+        );
+        // Family (zero width joiner sequence)
+        assert_eq!(
+            assemble("\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}"),
+            vec![Event::Grapheme(String::from(
+                "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}"
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_regional_indicator_pairs() {
+        // Two flags
+        assert_eq!(
+            assemble("\u{1F1E8}\u{1F1FF}\u{1F1E9}\u{1F1EA}"),
+            vec![
+                Event::Grapheme(String::from("\u{1F1E8}\u{1F1FF}")),
+                Event::Grapheme(String::from("\u{1F1E9}\u{1F1EA}"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_modified_key_is_not_assembled() {
+        let mut grapheme = Grapheme::start(KeyCode::Char('e').into()).unwrap();
+        assert!(!grapheme.extend(&KeyEvent::new(
+            KeyCode::Char('\u{0301}'),
+            KeyModifiers::CONTROL
+        )));
+        assert!(Grapheme::start(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::ALT)).is_none());
+        assert!(Grapheme::start(KeyCode::Enter.into()).is_none());
+    }
+}
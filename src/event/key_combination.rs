@@ -0,0 +1,327 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+/// Represents a keyboard shortcut, a key together with the modifiers which have to be held.
+///
+/// It can be parsed from strings like `ctrl+shift+p`, `alt+enter` or `f5` and matched against
+/// the incoming [`KeyEvent`](struct.KeyEvent.html)s, which allows to load the key bindings from
+/// configuration files.
+///
+/// The modifiers are `ctrl` (or `control`), `alt` and `shift`. The key is a single character
+/// or one of `enter`, `esc`, `tab`, `backtab`, `backspace`, `delete`, `insert`, `home`, `end`,
+/// `pageup`, `pagedown`, `up`, `down`, `left`, `right`, `space`, `null` and `f1`, `f2`, ...
+/// Use `ctrl++` to bind the `+` key. The names are case insensitive.
+///
+/// # Examples
+///
+/// ```
+/// use crossterm::event::{KeyCode, KeyCombination, KeyEvent, KeyModifiers};
+///
+/// let palette: KeyCombination = "ctrl+shift+p".parse().unwrap();
+///
+/// assert!(palette.matches(&KeyEvent::new(
+///     KeyCode::Char('P'),
+///     KeyModifiers::CONTROL | KeyModifiers::SHIFT
+/// )));
+/// assert!(!palette.matches(&KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)));
+/// assert_eq!(palette.to_string(), "ctrl+shift+p");
+/// ```
+///
+/// # Notes
+///
+/// The terminals report the shifted characters inconsistently, the matching is forgiving:
+///
+/// * Letters match regardless of their case, `shift+a` matches `A` with or without the `Shift`
+///   modifier.
+/// * The `Shift` modifier is ignored for other characters, `?` matches `Shift + ?` too.
+/// * `shift+tab` matches the `BackTab` key.
+/// * Key release events never match.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct KeyCombination {
+    /// The key itself.
+    pub code: KeyCode,
+    /// The modifiers which have to be held.
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyCombination {
+    /// Creates a key combination of the given key and modifiers.
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> KeyCombination {
+        KeyCombination { code, modifiers }
+    }
+
+    /// Returns whether the given key event triggers this key combination.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        event.kind != KeyEventKind::Release
+            && normalize(self.code, self.modifiers) == normalize(event.code, event.modifiers)
+    }
+}
+
+impl From<KeyCode> for KeyCombination {
+    fn from(code: KeyCode) -> Self {
+        KeyCombination::new(code, KeyModifiers::empty())
+    }
+}
+
+impl From<KeyEvent> for KeyCombination {
+    fn from(event: KeyEvent) -> Self {
+        KeyCombination::new(event.code, event.modifiers)
+    }
+}
+
+// Brings the different ways terminals report the same key combination to a single one.
+fn normalize(code: KeyCode, modifiers: KeyModifiers) -> (KeyCode, KeyModifiers) {
+    match code {
+        KeyCode::BackTab => (KeyCode::Tab, modifiers | KeyModifiers::SHIFT),
+        KeyCode::Char(c) if c.is_uppercase() => (
+            KeyCode::Char(c.to_lowercase().next().unwrap_or(c)),
+            modifiers | KeyModifiers::SHIFT,
+        ),
+        KeyCode::Char(c) if !c.is_lowercase() => (code, modifiers - KeyModifiers::SHIFT),
+        _ => (code, modifiers),
+    }
+}
+
+impl FromStr for KeyCombination {
+    type Err = ParseKeyCombinationError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let error = || ParseKeyCombinationError {
+            input: input.to_string(),
+        };
+
+        let src = input.trim();
+        let (modifier_names, key_name) = if let Some(modifier_names) = src.strip_suffix("++") {
+            (modifier_names, "+")
+        } else if src == "+" {
+            ("", "+")
+        } else {
+            match src.rfind('+') {
+                Some(idx) => (&src[..idx], &src[idx + 1..]),
+                None => ("", src),
+            }
+        };
+
+        let modifiers = parse_modifiers(modifier_names).ok_or_else(error)?;
+        let code = parse_key_code(key_name.trim()).ok_or_else(error)?;
+
+        Ok(KeyCombination::new(code, modifiers))
+    }
+}
+
+// Parses `+` separated modifier names, e.g. `ctrl+shift`.
+pub(super) fn parse_modifiers(names: &str) -> Option<KeyModifiers> {
+    let mut modifiers = KeyModifiers::empty();
+    if !names.is_empty() {
+        for name in names.split('+') {
+            modifiers |= match name.trim().to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return None,
+            };
+        }
+    }
+    Some(modifiers)
+}
+
+// Writes the modifier names, each one followed by `+`.
+pub(super) fn write_modifiers(f: &mut impl fmt::Write, modifiers: KeyModifiers) -> fmt::Result {
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        f.write_str("ctrl+")?;
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        f.write_str("alt+")?;
+    }
+    if modifiers.contains(KeyModifiers::SHIFT) {
+        f.write_str("shift+")?;
+    }
+    Ok(())
+}
+
+fn parse_key_code(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+
+    let code = match name.to_lowercase().as_str() {
+        "backspace" => KeyCode::Backspace,
+        "enter" | "return" => KeyCode::Enter,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "space" => KeyCode::Char(' '),
+        "null" => KeyCode::Null,
+        "esc" | "escape" => KeyCode::Esc,
+        name => match name.strip_prefix('f')?.parse::<u8>().ok()? {
+            0 => return None,
+            n => KeyCode::F(n),
+        },
+    };
+    Some(code)
+}
+
+impl Display for KeyCombination {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_modifiers(f, self.modifiers)?;
+
+        match self.code {
+            KeyCode::Backspace => f.write_str("backspace"),
+            KeyCode::Enter => f.write_str("enter"),
+            KeyCode::Left => f.write_str("left"),
+            KeyCode::Right => f.write_str("right"),
+            KeyCode::Up => f.write_str("up"),
+            KeyCode::Down => f.write_str("down"),
+            KeyCode::Home => f.write_str("home"),
+            KeyCode::End => f.write_str("end"),
+            KeyCode::PageUp => f.write_str("pageup"),
+            KeyCode::PageDown => f.write_str("pagedown"),
+            KeyCode::Tab => f.write_str("tab"),
+            KeyCode::BackTab => f.write_str("backtab"),
+            KeyCode::Delete => f.write_str("delete"),
+            KeyCode::Insert => f.write_str("insert"),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Null => f.write_str("null"),
+            KeyCode::Esc => f.write_str("esc"),
+        }
+    }
+}
+
+/// The error returned when a string can't be parsed into a
+/// [`KeyCombination`](struct.KeyCombination.html).
+///
+/// # Examples
+///
+/// ```
+/// use crossterm::event::KeyCombination;
+///
+/// let error = "ctrl+hyper+x".parse::<KeyCombination>().unwrap_err();
+///
+/// assert_eq!(error.input(), "ctrl+hyper+x");
+/// assert_eq!(error.to_string(), "invalid key combination `ctrl+hyper+x`");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseKeyCombinationError {
+    input: String,
+}
+
+impl ParseKeyCombinationError {
+    /// Returns the string which couldn't be parsed.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl Display for ParseKeyCombinationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid key combination `{}`", self.input)
+    }
+}
+
+impl std::error::Error for ParseKeyCombinationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::KeyCombination;
+    use crate::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+    fn parse(input: &str) -> KeyCombination {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_key_combination() {
+        assert_eq!(
+            parse("ctrl+shift+p"),
+            KeyCombination::new(
+                KeyCode::Char('p'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            )
+        );
+        assert_eq!(
+            parse("Alt+Enter"),
+            KeyCombination::new(KeyCode::Enter, KeyModifiers::ALT)
+        );
+        assert_eq!(parse("f12"), KeyCode::F(12).into());
+        assert_eq!(parse("space"), KeyCode::Char(' ').into());
+        assert_eq!(parse("+"), KeyCode::Char('+').into());
+        assert_eq!(
+            parse("control++"),
+            KeyCombination::new(KeyCode::Char('+'), KeyModifiers::CONTROL)
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_key_combination() {
+        for input in &["", "ctrl+", "ctrl+foo", "super+a", "f0", "f256", "ctrl++a"] {
+            assert!(
+                input.parse::<KeyCombination>().is_err(),
+                "`{}` should not parse",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_key_combination_display_round_trip() {
+        for input in &[
+            "ctrl+alt+shift+delete",
+            "shift+tab",
+            "f5",
+            "space",
+            "ctrl++",
+            "ž",
+        ] {
+            assert_eq!(parse(input).to_string(), *input);
+        }
+    }
+
+    #[test]
+    fn test_key_combination_matches() {
+        let ctrl_c = parse("ctrl+c");
+        assert!(ctrl_c.matches(&KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+        assert!(!ctrl_c.matches(&KeyEvent::new(
+            KeyCode::Char('c'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT
+        )));
+        assert!(!ctrl_c.matches(&KeyCode::Char('c').into()));
+        assert!(!ctrl_c.matches(&KeyEvent::new_with_kind(
+            KeyCode::Char('c'),
+            KeyModifiers::CONTROL,
+            KeyEventKind::Release
+        )));
+
+        let shift_a = parse("shift+a");
+        assert!(shift_a.matches(&KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT)));
+        assert!(shift_a.matches(&KeyEvent::new(KeyCode::Char('A'), KeyModifiers::NONE)));
+        assert!(!shift_a.matches(&KeyCode::Char('a').into()));
+        assert!(parse("A").matches(&KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT)));
+
+        let question = parse("?");
+        assert!(question.matches(&KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT)));
+        assert!(question.matches(&KeyCode::Char('?').into()));
+
+        assert!(parse("shift+tab").matches(&KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT)));
+        assert!(parse("alt+enter").matches(&KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT)));
+    }
+}
@@ -0,0 +1,294 @@
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{MouseButton, MouseEvent, MouseEventKind};
+
+/// The default maximum interval between two clicks of a double click.
+const DEFAULT_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A gesture recognized by the [`MouseTracker`](struct.MouseTracker.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum MouseGesture {
+    /// The mouse button was pressed. `count` is `1` for a single click, `2` for a double click,
+    /// `3` for a triple click, etc.
+    Click {
+        button: MouseButton,
+        column: u16,
+        row: u16,
+        count: u8,
+    },
+    /// The mouse started to move while the button was held, the position is where the button
+    /// was pressed.
+    DragStart {
+        button: MouseButton,
+        column: u16,
+        row: u16,
+    },
+    /// The button was released after dragging, the position is where it was released.
+    DragEnd {
+        button: MouseButton,
+        column: u16,
+        row: u16,
+    },
+}
+
+/// Recognizes multi-clicks and drags in a sequence of [`MouseEvent`](struct.MouseEvent.html)s.
+///
+/// The terminals report the mouse button presses, releases and motion only. Feed all the mouse
+/// events to the tracker to find out whether a press is a double click or where a drag started
+/// and ended.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::event::{read, Event, MouseGesture, MouseTracker};
+///
+/// fn main() -> crossterm::Result<()> {
+///     let mut tracker = MouseTracker::new();
+///
+///     loop {
+///         if let Event::Mouse(event) = read()? {
+///             match tracker.track(&event) {
+///                 Some(MouseGesture::Click { count: 2, .. }) => println!("Double click\r"),
+///                 Some(MouseGesture::DragEnd { column, row, .. }) => {
+///                     println!("Dropped at {}x{}\r", column, row)
+///                 }
+///                 _ => {}
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MouseTracker {
+    click_interval: Duration,
+    last_click: Option<Click>,
+    pressed: Option<Press>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Click {
+    button: MouseButton,
+    column: u16,
+    row: u16,
+    count: u8,
+    time: Instant,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Press {
+    button: MouseButton,
+    column: u16,
+    row: u16,
+    dragging: bool,
+}
+
+impl Default for MouseTracker {
+    fn default() -> Self {
+        MouseTracker::with_click_interval(DEFAULT_CLICK_INTERVAL)
+    }
+}
+
+impl MouseTracker {
+    /// Constructs a new `MouseTracker` with the default 500ms double click interval.
+    pub fn new() -> MouseTracker {
+        MouseTracker::default()
+    }
+
+    /// Constructs a new `MouseTracker` with the given maximum interval between the clicks of a
+    /// multi-click.
+    pub fn with_click_interval(click_interval: Duration) -> MouseTracker {
+        MouseTracker {
+            click_interval,
+            last_click: None,
+            pressed: None,
+        }
+    }
+
+    /// Returns the maximum interval between the clicks of a multi-click.
+    pub fn click_interval(&self) -> Duration {
+        self.click_interval
+    }
+
+    /// Tracks the given mouse event and returns the gesture it completes, if any.
+    pub fn track(&mut self, event: &MouseEvent) -> Option<MouseGesture> {
+        self.track_at(event, Instant::now())
+    }
+
+    fn track_at(&mut self, event: &MouseEvent, time: Instant) -> Option<MouseGesture> {
+        let (column, row) = (event.column, event.row);
+
+        match event.kind {
+            MouseEventKind::Down(button) => {
+                let count = match self.last_click {
+                    Some(click)
+                        if click.button == button
+                            && click.column == column
+                            && click.row == row
+                            && time.duration_since(click.time) <= self.click_interval =>
+                    {
+                        click.count.saturating_add(1)
+                    }
+                    _ => 1,
+                };
+
+                self.last_click = Some(Click {
+                    button,
+                    column,
+                    row,
+                    count,
+                    time,
+                });
+                self.pressed = Some(Press {
+                    button,
+                    column,
+                    row,
+                    dragging: false,
+                });
+
+                Some(MouseGesture::Click {
+                    button,
+                    column,
+                    row,
+                    count,
+                })
+            }
+            MouseEventKind::Drag(_) => match self.pressed.as_mut() {
+                Some(press) if !press.dragging => {
+                    press.dragging = true;
+                    // Dragging ends the multi-click
+                    self.last_click = None;
+
+                    Some(MouseGesture::DragStart {
+                        button: press.button,
+                        column: press.column,
+                        row: press.row,
+                    })
+                }
+                _ => None,
+            },
+            // Some terminals don't report which button was released, use the pressed one
+            MouseEventKind::Up(_) => match self.pressed.take() {
+                Some(press) if press.dragging => Some(MouseGesture::DragEnd {
+                    button: press.button,
+                    column,
+                    row,
+                }),
+                _ => None,
+            },
+            MouseEventKind::Moved | MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{MouseGesture, MouseTracker};
+    use crate::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+    fn event(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_multi_click() {
+        let mut tracker = MouseTracker::new();
+        let start = Instant::now();
+        let down = event(MouseEventKind::Down(MouseButton::Left), 3, 4);
+        let up = event(MouseEventKind::Up(MouseButton::Left), 3, 4);
+
+        for (count, millis) in [(1, 0), (2, 200), (3, 400)].iter() {
+            let time = start + Duration::from_millis(*millis);
+            assert_eq!(
+                tracker.track_at(&down, time),
+                Some(MouseGesture::Click {
+                    button: MouseButton::Left,
+                    column: 3,
+                    row: 4,
+                    count: *count
+                })
+            );
+            assert_eq!(tracker.track_at(&up, time), None);
+        }
+
+        // Too late for another click of the same multi-click
+        assert_eq!(
+            tracker.track_at(&down, start + Duration::from_millis(1_000)),
+            Some(MouseGesture::Click {
+                button: MouseButton::Left,
+                column: 3,
+                row: 4,
+                count: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_click_elsewhere_resets_count() {
+        let mut tracker = MouseTracker::new();
+        let now = Instant::now();
+
+        tracker.track_at(&event(MouseEventKind::Down(MouseButton::Left), 3, 4), now);
+        assert_eq!(
+            tracker.track_at(&event(MouseEventKind::Down(MouseButton::Left), 5, 4), now),
+            Some(MouseGesture::Click {
+                button: MouseButton::Left,
+                column: 5,
+                row: 4,
+                count: 1
+            })
+        );
+        assert_eq!(
+            tracker.track_at(&event(MouseEventKind::Down(MouseButton::Right), 5, 4), now),
+            Some(MouseGesture::Click {
+                button: MouseButton::Right,
+                column: 5,
+                row: 4,
+                count: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_drag() {
+        let mut tracker = MouseTracker::new();
+        let now = Instant::now();
+
+        tracker.track_at(&event(MouseEventKind::Down(MouseButton::Right), 1, 2), now);
+        assert_eq!(
+            tracker.track_at(&event(MouseEventKind::Drag(MouseButton::Right), 2, 2), now),
+            Some(MouseGesture::DragStart {
+                button: MouseButton::Right,
+                column: 1,
+                row: 2
+            })
+        );
+        assert_eq!(
+            tracker.track_at(&event(MouseEventKind::Drag(MouseButton::Right), 3, 2), now),
+            None
+        );
+        // The released button isn't always known
+        assert_eq!(
+            tracker.track_at(&event(MouseEventKind::Up(MouseButton::Left), 4, 5), now),
+            Some(MouseGesture::DragEnd {
+                button: MouseButton::Right,
+                column: 4,
+                row: 5
+            })
+        );
+        assert_eq!(
+            tracker.track_at(&event(MouseEventKind::Up(MouseButton::Left), 4, 5), now),
+            None
+        );
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(unix)]
+use std::os::unix::io::RawFd;
 use std::{collections::vec_deque::VecDeque, io, time::Duration};
 
 #[cfg(unix)]
@@ -6,7 +8,9 @@ use super::source::unix::UnixInternalEventSource;
 use super::source::windows::WindowsEventSource;
 #[cfg(feature = "event-stream")]
 use super::sys::Waker;
-use super::{filter::Filter, source::EventSource, timeout::PollTimeout, InternalEvent, Result};
+use super::{
+    filter::Filter, source::EventSource, timeout::PollTimeout, Event, InternalEvent, Result,
+};
 
 use crate::ErrorKind;
 
@@ -41,25 +45,76 @@ impl InternalEventReader {
         self.source.as_ref().expect("reader source not set").waker()
     }
 
+    /// Returns the file descriptor the event source reads from.
+    #[cfg(unix)]
+    pub(crate) fn raw_fd(&self) -> Result<RawFd> {
+        self.source
+            .as_ref()
+            .and_then(|source| source.raw_fd())
+            .ok_or_else(|| std::io::Error::other("Failed to initialize input reader").into())
+    }
+
+    /// Replaces the event source, the already read events are kept.
+    ///
+    /// The signals are reported by the new source as well if they were reported by the old one.
+    #[cfg(unix)]
+    pub(crate) fn set_source(&mut self, mut source: Box<dyn EventSource>) -> Result<()> {
+        // The signals are registered before the old source (and its handlers) is dropped, so
+        // that they never terminate the process in between
+        if self
+            .source
+            .as_ref()
+            .is_some_and(|source| source.signal_events_enabled())
+        {
+            source.enable_signal_events()?;
+        }
+        self.source = Some(source);
+        Ok(())
+    }
+
+    /// Discards the events which were received, but not read yet.
+    ///
+    /// The internal events (e.g. the answers to the terminal queries) are kept.
+    pub(crate) fn discard_events(&mut self) -> Result<()> {
+        let event_source = match self.source.as_mut() {
+            Some(source) => source,
+            None => return Err(std::io::Error::other("Failed to initialize input reader").into()),
+        };
+
+        // The pending input is read, so that the other events (e.g. a resize) aren't lost
+        while let Some(event) = event_source.try_read(Some(Duration::from_secs(0)))? {
+            if !is_input_event(&event) {
+                self.events.push_back(event);
+            }
+        }
+        event_source.discard_incomplete_input();
+        self.events.retain(|event| !is_input_event(event));
+
+        Ok(())
+    }
+
+    /// Starts delivering the termination signals as events.
+    #[cfg(unix)]
+    pub(crate) fn enable_signal_events(&mut self) -> Result<()> {
+        match self.source.as_mut() {
+            Some(source) => source.enable_signal_events(),
+            None => Err(std::io::Error::other("Failed to initialize input reader").into()),
+        }
+    }
+
     pub(crate) fn poll<F>(&mut self, timeout: Option<Duration>, filter: &F) -> Result<bool>
     where
         F: Filter,
     {
         for event in &self.events {
-            if filter.eval(&event) {
+            if filter.eval(event) {
                 return Ok(true);
             }
         }
 
         let event_source = match self.source.as_mut() {
             Some(source) => source,
-            None => {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    "Failed to initialize input reader",
-                )
-                .into())
-            }
+            None => return Err(std::io::Error::other("Failed to initialize input reader").into()),
         };
 
         let poll_timeout = PollTimeout::new(timeout);
@@ -98,6 +153,11 @@ impl InternalEventReader {
         }
     }
 
+    /// Queues the given event, it's returned by `read` after the already queued events.
+    pub(crate) fn inject(&mut self, event: InternalEvent) {
+        self.events.push_back(event);
+    }
+
     pub(crate) fn read<F>(&mut self, filter: &F) -> Result<InternalEvent>
     where
         F: Filter,
@@ -129,6 +189,18 @@ impl InternalEventReader {
     }
 }
 
+/// Returns whether the event was generated by the user input, which is discarded by
+/// `discard_events`.
+fn is_input_event(event: &InternalEvent) -> bool {
+    matches!(
+        event,
+        InternalEvent::Event(Event::Key(_))
+            | InternalEvent::Event(Event::Mouse(_))
+            | InternalEvent::Event(Event::Grapheme(_))
+            | InternalEvent::Event(Event::Paste(_))
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use std::{collections::VecDeque, time::Duration};
@@ -141,6 +213,34 @@ mod tests {
         super::{filter::InternalEventFilter, Event},
         EventSource, InternalEvent, InternalEventReader,
     };
+    #[cfg(unix)]
+    use crate::{
+        event::KeyCode,
+        style::{Color, Colored},
+    };
+
+    #[test]
+    fn test_injected_events_are_read_without_event_source() {
+        let mut reader = InternalEventReader {
+            events: VecDeque::new(),
+            source: None,
+            skipped_events: Vec::with_capacity(32),
+        };
+
+        reader.inject(InternalEvent::Event(Event::Resize(10, 10)));
+        reader.inject(InternalEvent::Event(Event::FocusLost));
+
+        assert!(reader.poll(None, &InternalEventFilter).unwrap());
+        assert_eq!(
+            reader.read(&InternalEventFilter).unwrap(),
+            InternalEvent::Event(Event::Resize(10, 10))
+        );
+        assert_eq!(
+            reader.read(&InternalEventFilter).unwrap(),
+            InternalEvent::Event(Event::FocusLost)
+        );
+        assert!(reader.poll(None, &InternalEventFilter).is_err());
+    }
 
     #[test]
     fn test_poll_fails_without_event_source() {
@@ -186,6 +286,42 @@ mod tests {
         assert!(reader.poll(None, &CursorPositionFilter).unwrap());
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_discard_events_keeps_other_events() {
+        const CURSOR_EVENT: InternalEvent = InternalEvent::CursorPosition(10, 20);
+        let key_event = InternalEvent::Event(Event::Key(KeyCode::Enter.into()));
+
+        let source = FakeSource::with_events(&[
+            key_event.clone(),
+            InternalEvent::Event(Event::FocusLost),
+            InternalEvent::Event(Event::Paste("text".to_string())),
+            InternalEvent::DefaultColor(Colored::ForegroundColor(Color::Red)),
+        ]);
+        let mut reader = InternalEventReader {
+            events: vec![
+                InternalEvent::Event(Event::Resize(10, 10)),
+                key_event,
+                CURSOR_EVENT,
+            ]
+            .into(),
+            source: Some(Box::new(source)),
+            skipped_events: Vec::with_capacity(32),
+        };
+
+        reader.discard_events().unwrap();
+
+        assert_eq!(
+            reader.events,
+            vec![
+                InternalEvent::Event(Event::Resize(10, 10)),
+                CURSOR_EVENT,
+                InternalEvent::Event(Event::FocusLost),
+                InternalEvent::DefaultColor(Colored::ForegroundColor(Color::Red))
+            ]
+        );
+    }
+
     #[test]
     fn test_read_returns_matching_event_in_queue_at_front() {
         const EVENT: InternalEvent = InternalEvent::Event(Event::Resize(10, 10));
@@ -398,31 +534,50 @@ mod tests {
         assert_eq!(reader.read(&InternalEventFilter).unwrap(), EVENT);
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_set_source_keeps_signal_events() {
+        let mut reader = InternalEventReader {
+            events: VecDeque::new(),
+            source: Some(Box::new(FakeSource::default())),
+            skipped_events: Vec::with_capacity(32),
+        };
+
+        reader.set_source(Box::new(FakeSource::default())).unwrap();
+        assert!(!reader.source.as_ref().unwrap().signal_events_enabled());
+
+        reader.enable_signal_events().unwrap();
+        reader.set_source(Box::new(FakeSource::default())).unwrap();
+        assert!(reader.source.as_ref().unwrap().signal_events_enabled());
+    }
+
     #[derive(Default)]
     struct FakeSource {
         events: VecDeque<InternalEvent>,
         error: Option<ErrorKind>,
+        #[cfg(unix)]
+        signal_events: bool,
     }
 
     impl FakeSource {
         fn new(events: &[InternalEvent], error: ErrorKind) -> FakeSource {
             FakeSource {
-                events: events.to_vec().into(),
                 error: Some(error),
+                ..FakeSource::with_events(events)
             }
         }
 
         fn with_events(events: &[InternalEvent]) -> FakeSource {
             FakeSource {
                 events: events.to_vec().into(),
-                error: None,
+                ..FakeSource::default()
             }
         }
 
         fn with_error(error: ErrorKind) -> FakeSource {
             FakeSource {
-                events: VecDeque::new(),
                 error: Some(error),
+                ..FakeSource::default()
             }
         }
     }
@@ -453,6 +608,17 @@ mod tests {
             Ok(None)
         }
 
+        #[cfg(unix)]
+        fn enable_signal_events(&mut self) -> crate::Result<()> {
+            self.signal_events = true;
+            Ok(())
+        }
+
+        #[cfg(unix)]
+        fn signal_events_enabled(&self) -> bool {
+            self.signal_events
+        }
+
         #[cfg(feature = "event-stream")]
         fn waker(&self) -> super::super::sys::Waker {
             unimplemented!();
@@ -0,0 +1,469 @@
+use std::{
+    fmt::{self, Write as _},
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use super::{
+    inject,
+    key_combination::{parse_modifiers, write_modifiers},
+    Event, KeyCode, KeyCombination, KeyEvent, KeyEventKind, MouseButton, MouseEvent,
+    MouseEventKind, Signal,
+};
+use crate::Result;
+
+/// A sequence of [`Event`](enum.Event.html)s with the time they arrived at.
+///
+/// Recordings allow to reproduce bug reports and to write end-to-end tests of interactive
+/// applications. Record the events with the [`Recorder`](struct.Recorder.html), save them
+/// to a file and [`replay`](struct.Recording.html#method.replay) them later.
+///
+/// # Format
+///
+/// Recordings are saved as text, one event per line, prefixed with the number of milliseconds
+/// since the recording started:
+///
+/// ```text
+/// 0 key ctrl+shift+p
+/// 150 key a release
+/// 420 mouse ctrl+down-left 10 4
+/// 900 resize 80 24
+/// 920 grapheme "e\u{301}"
+/// 950 paste "hello\nworld"
+/// 1000 focus-lost
+/// 1200 signal hangup
+/// ```
+///
+/// The keys are written as [`KeyCombination`](struct.KeyCombination.html)s followed by
+/// `repeat` or `release` for the non-press key events. Empty lines and lines starting with `#`
+/// are skipped. The raw key codes aren't recorded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recording {
+    events: Vec<(Duration, Event)>,
+}
+
+impl Recording {
+    /// Constructs a new empty `Recording`.
+    pub fn new() -> Recording {
+        Recording::default()
+    }
+
+    /// Appends an event which arrived at the given time since the recording started.
+    pub fn push(&mut self, timestamp: Duration, event: Event) {
+        self.events.push((timestamp, event));
+    }
+
+    /// Returns the recorded events with their timestamps.
+    pub fn events(&self) -> &[(Duration, Event)] {
+        &self.events
+    }
+
+    /// Queues all the recorded events with [`inject`](fn.inject.html), they're returned by
+    /// [`read`](fn.read.html) in the recorded order.
+    ///
+    /// The events are available immediately, the timestamps aren't waited for.
+    pub fn replay(&self) {
+        for (_, event) in &self.events {
+            inject(event.clone());
+        }
+    }
+
+    /// Writes the recording to the given writer.
+    pub fn write_to(&self, mut writer: impl Write) -> Result<()> {
+        let mut line = String::new();
+        for (timestamp, event) in &self.events {
+            line.clear();
+            write!(line, "{} ", timestamp.as_millis())?;
+            write_event(&mut line, event)?;
+            writeln!(writer, "{}", line)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads a recording written by [`write_to`](struct.Recording.html#method.write_to).
+    pub fn read_from(reader: impl BufRead) -> Result<Recording> {
+        let mut recording = Recording::new();
+
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (timestamp, event) = parse_line(line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid recorded event on line {}: `{}`", idx + 1, line),
+                )
+            })?;
+            recording.push(timestamp, event);
+        }
+
+        Ok(recording)
+    }
+
+    /// Saves the recording to the file at the given path.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.write_to(BufWriter::new(File::create(path)?))
+    }
+
+    /// Loads a recording from the file at the given path.
+    pub fn load(path: impl AsRef<Path>) -> Result<Recording> {
+        Recording::read_from(BufReader::new(File::open(path)?))
+    }
+}
+
+/// Records [`Event`](enum.Event.html)s with the time they arrived at.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::event::{read, Event, KeyCode, Recorder};
+///
+/// fn main() -> crossterm::Result<()> {
+///     let mut recorder = Recorder::new();
+///
+///     loop {
+///         let event = read()?;
+///         recorder.record(&event);
+///
+///         if event == Event::Key(KeyCode::Esc.into()) {
+///             break;
+///         }
+///     }
+///
+///     recorder.recording().save("session.events")
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    start: Instant,
+    recording: Recording,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Recorder {
+            start: Instant::now(),
+            recording: Recording::new(),
+        }
+    }
+}
+
+impl Recorder {
+    /// Constructs a new `Recorder`, the timestamps are relative to this moment.
+    pub fn new() -> Recorder {
+        Recorder::default()
+    }
+
+    /// Records the given event with the current time.
+    pub fn record(&mut self, event: &Event) {
+        self.recording.push(self.start.elapsed(), event.clone());
+    }
+
+    /// Returns the events recorded so far.
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Stops recording and returns the recorded events.
+    pub fn into_recording(self) -> Recording {
+        self.recording
+    }
+}
+
+fn write_event(f: &mut impl fmt::Write, event: &Event) -> fmt::Result {
+    match event {
+        Event::Key(event) => {
+            // Control characters would break the line based format, the parsers never
+            // report them as characters.
+            if let KeyCode::Char(c) = event.code {
+                if c.is_control() {
+                    return Err(fmt::Error);
+                }
+            }
+
+            write!(f, "key {}", KeyCombination::from(*event))?;
+            match event.kind {
+                KeyEventKind::Press => Ok(()),
+                KeyEventKind::Repeat => f.write_str(" repeat"),
+                KeyEventKind::Release => f.write_str(" release"),
+            }
+        }
+        Event::Mouse(event) => {
+            f.write_str("mouse ")?;
+            write_modifiers(f, event.modifiers)?;
+            let (kind, button) = match event.kind {
+                MouseEventKind::Down(button) => ("down", Some(button)),
+                MouseEventKind::Up(button) => ("up", Some(button)),
+                MouseEventKind::Drag(button) => ("drag", Some(button)),
+                MouseEventKind::Moved => ("moved", None),
+                MouseEventKind::ScrollDown => ("scroll-down", None),
+                MouseEventKind::ScrollUp => ("scroll-up", None),
+            };
+            f.write_str(kind)?;
+            match button {
+                Some(MouseButton::Left) => f.write_str("-left")?,
+                Some(MouseButton::Right) => f.write_str("-right")?,
+                Some(MouseButton::Middle) => f.write_str("-middle")?,
+                None => {}
+            }
+            write!(f, " {} {}", event.column, event.row)
+        }
+        Event::Resize(columns, rows) => write!(f, "resize {} {}", columns, rows),
+        Event::Grapheme(text) => write!(f, "grapheme {:?}", text),
+        Event::Paste(text) => write!(f, "paste {:?}", text),
+        Event::FocusGained => f.write_str("focus-gained"),
+        Event::FocusLost => f.write_str("focus-lost"),
+        Event::Signal(Signal::Interrupt) => f.write_str("signal interrupt"),
+        Event::Signal(Signal::Terminate) => f.write_str("signal terminate"),
+        Event::Signal(Signal::Hangup) => f.write_str("signal hangup"),
+    }
+}
+
+fn parse_line(line: &str) -> Option<(Duration, Event)> {
+    let (timestamp, rest) = split_word(line);
+    let timestamp = Duration::from_millis(timestamp.parse().ok()?);
+    let (name, args) = split_word(rest);
+
+    let event = match name {
+        "key" => {
+            let (combination, kind) = match args.strip_suffix(" repeat") {
+                Some(combination) => (combination, KeyEventKind::Repeat),
+                None => match args.strip_suffix(" release") {
+                    Some(combination) => (combination, KeyEventKind::Release),
+                    None => (args, KeyEventKind::Press),
+                },
+            };
+            let combination: KeyCombination = combination.parse().ok()?;
+            Event::Key(KeyEvent::new_with_kind(
+                combination.code,
+                combination.modifiers,
+                kind,
+            ))
+        }
+        "mouse" => {
+            let mut words = args.split_whitespace();
+            let kind = words.next()?;
+            let column = words.next()?.parse().ok()?;
+            let row = words.next()?.parse().ok()?;
+            if words.next().is_some() {
+                return None;
+            }
+
+            let (modifiers, kind) = match kind.rfind('+') {
+                Some(idx) => (parse_modifiers(&kind[..idx])?, &kind[idx + 1..]),
+                None => (parse_modifiers("")?, kind),
+            };
+            let kind = match kind {
+                "moved" => MouseEventKind::Moved,
+                "scroll-down" => MouseEventKind::ScrollDown,
+                "scroll-up" => MouseEventKind::ScrollUp,
+                _ => {
+                    let (kind, button) = kind.split_at(kind.find('-')?);
+                    let button = match button {
+                        "-left" => MouseButton::Left,
+                        "-right" => MouseButton::Right,
+                        "-middle" => MouseButton::Middle,
+                        _ => return None,
+                    };
+                    match kind {
+                        "down" => MouseEventKind::Down(button),
+                        "up" => MouseEventKind::Up(button),
+                        "drag" => MouseEventKind::Drag(button),
+                        _ => return None,
+                    }
+                }
+            };
+
+            Event::Mouse(MouseEvent {
+                kind,
+                column,
+                row,
+                modifiers,
+            })
+        }
+        "resize" => {
+            let mut words = args.split_whitespace();
+            let columns = words.next()?.parse().ok()?;
+            let rows = words.next()?.parse().ok()?;
+            if words.next().is_some() {
+                return None;
+            }
+            Event::Resize(columns, rows)
+        }
+        "grapheme" => Event::Grapheme(unescape(args)?),
+        "paste" => Event::Paste(unescape(args)?),
+        "focus-gained" if args.is_empty() => Event::FocusGained,
+        "focus-lost" if args.is_empty() => Event::FocusLost,
+        "signal" => Event::Signal(match args {
+            "interrupt" => Signal::Interrupt,
+            "terminate" => Signal::Terminate,
+            "hangup" => Signal::Hangup,
+            _ => return None,
+        }),
+        _ => return None,
+    };
+
+    Some((timestamp, event))
+}
+
+// Splits the first whitespace separated word from the rest of the line.
+fn split_word(line: &str) -> (&str, &str) {
+    match line.find(' ') {
+        Some(idx) => (&line[..idx], line[idx + 1..].trim_start()),
+        None => (line, ""),
+    }
+}
+
+// Parses a string written with the `Debug` formatting.
+fn unescape(quoted: &str) -> Option<String> {
+    let mut chars = quoted.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut text = String::new();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+
+        let c = match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            c @ '\\' | c @ '"' | c @ '\'' => c,
+            'u' => {
+                if chars.next()? != '{' {
+                    return None;
+                }
+                let mut hex = String::new();
+                loop {
+                    match chars.next()? {
+                        '}' => break,
+                        c => hex.push(c),
+                    }
+                }
+                std::char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?
+            }
+            _ => return None,
+        };
+        text.push(c);
+    }
+
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Recording;
+    use crate::event::{
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind, Signal,
+    };
+
+    fn recording() -> Recording {
+        let mut recording = Recording::new();
+        let events = vec![
+            Event::Key(KeyEvent::new(
+                KeyCode::Char('p'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+            )),
+            Event::Key(KeyEvent::new_with_kind(
+                KeyCode::Char(' '),
+                KeyModifiers::NONE,
+                KeyEventKind::Release,
+            )),
+            Event::Key(KeyEvent::new_with_kind(
+                KeyCode::Char('+'),
+                KeyModifiers::ALT,
+                KeyEventKind::Repeat,
+            )),
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column: 10,
+                row: 4,
+                modifiers: KeyModifiers::CONTROL,
+            }),
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::ScrollUp,
+                column: 0,
+                row: 0,
+                modifiers: KeyModifiers::NONE,
+            }),
+            Event::Resize(80, 24),
+            Event::Grapheme("e\u{301}".to_string()),
+            Event::Paste("hello\n\"world\" \u{1b}[A ž".to_string()),
+            Event::FocusGained,
+            Event::FocusLost,
+            Event::Signal(Signal::Hangup),
+        ];
+        for (idx, event) in events.into_iter().enumerate() {
+            recording.push(Duration::from_millis(idx as u64 * 150), event);
+        }
+        recording
+    }
+
+    #[test]
+    fn test_recording_round_trip() {
+        let mut output = Vec::new();
+        recording().write_to(&mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output.clone()).unwrap(),
+            concat!(
+                "0 key ctrl+shift+p\n",
+                "150 key space release\n",
+                "300 key alt++ repeat\n",
+                "450 mouse ctrl+down-left 10 4\n",
+                "600 mouse scroll-up 0 0\n",
+                "750 resize 80 24\n",
+                "900 grapheme \"e\\u{301}\"\n",
+                "1050 paste \"hello\\n\\\"world\\\" \\u{1b}[A ž\"\n",
+                "1200 focus-gained\n",
+                "1350 focus-lost\n",
+                "1500 signal hangup\n",
+            )
+        );
+        assert_eq!(Recording::read_from(&output[..]).unwrap(), recording());
+    }
+
+    #[test]
+    fn test_read_recording_skips_comments() {
+        let input = b"# recorded by a test\n\n10 key esc\n";
+        let recording = Recording::read_from(&input[..]).unwrap();
+
+        assert_eq!(
+            recording.events(),
+            &[(Duration::from_millis(10), Event::Key(KeyCode::Esc.into()))]
+        );
+    }
+
+    #[test]
+    fn test_read_invalid_recording() {
+        for input in &[
+            "key a",
+            "10 key hyper+a",
+            "10 mouse down 1 1",
+            "10 mouse down-left 1",
+            "10 resize 80",
+            "10 paste hello",
+            "10 paste \"\\q\"",
+            "10 focus-lost now",
+            "10 signal kill",
+            "10 blink",
+        ] {
+            assert!(
+                Recording::read_from(input.as_bytes()).is_err(),
+                "`{}` should not parse",
+                input
+            );
+        }
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(unix)]
+use std::os::unix::io::RawFd;
 use std::time::Duration;
 
 #[cfg(feature = "event-stream")]
@@ -16,11 +18,32 @@ pub(crate) trait EventSource: Sync + Send {
     /// # Arguments
     ///
     /// * `timeout` - `None` block indefinitely until an event is available, `Some(duration)` blocks
-    ///               for the given timeout
+    ///   for the given timeout
     ///
     /// Returns `Ok(None)` if there's no event available and timeout expires.
     fn try_read(&mut self, timeout: Option<Duration>) -> crate::Result<Option<InternalEvent>>;
 
+    /// Returns the file descriptor the events are read from, if there's any.
+    #[cfg(unix)]
+    fn raw_fd(&self) -> Option<RawFd> {
+        None
+    }
+
+    /// Discards the input which was received partially, e.g. an incomplete escape sequence.
+    fn discard_incomplete_input(&mut self) {}
+
+    /// Starts reporting the `SIGINT`, `SIGTERM` and `SIGHUP` signals as events.
+    #[cfg(unix)]
+    fn enable_signal_events(&mut self) -> crate::Result<()> {
+        Ok(())
+    }
+
+    /// Returns whether the signals are reported as events.
+    #[cfg(unix)]
+    fn signal_events_enabled(&self) -> bool {
+        false
+    }
+
     /// Returns a `Waker` allowing to wake/force the `try_read` method to return `Ok(None)`.
     #[cfg(feature = "event-stream")]
     fn waker(&self) -> Waker;
@@ -1,4 +1,9 @@
-use std::{collections::VecDeque, io, time::Duration};
+use std::{
+    collections::VecDeque,
+    io,
+    os::unix::io::RawFd,
+    time::{Duration, Instant},
+};
 
 use mio::{unix::SourceFd, Events, Interest, Poll, Token};
 use signal_hook::iterator::Signals;
@@ -8,13 +13,15 @@ use crate::{ErrorKind, Result};
 #[cfg(feature = "event-stream")]
 use super::super::sys::Waker;
 use super::super::{
+    escape_timeout,
+    grapheme::Grapheme,
     source::EventSource,
     sys::unix::{
         file_descriptor::{tty_fd, FileDesc},
         parse::parse_event,
     },
     timeout::PollTimeout,
-    Event, InternalEvent,
+    Event, InternalEvent, KeyCode, KeyEvent, KeyEventKind, Signal,
 };
 
 // Tokens to identify file descriptor
@@ -35,6 +42,7 @@ pub(crate) struct UnixInternalEventSource {
     tty_buffer: [u8; TTY_BUFFER_SIZE],
     tty_fd: FileDesc,
     signals: Signals,
+    signal_events: bool,
     #[cfg(feature = "event-stream")]
     waker: Waker,
 }
@@ -52,7 +60,7 @@ impl UnixInternalEventSource {
         let mut tty_ev = SourceFd(&tty_raw_fd);
         registry.register(&mut tty_ev, TTY_TOKEN, Interest::READABLE)?;
 
-        let mut signals = Signals::new(&[signal_hook::SIGWINCH])?;
+        let mut signals = Signals::new([signal_hook::SIGWINCH])?;
         registry.register(&mut signals, SIGNAL_TOKEN, Interest::READABLE)?;
 
         #[cfg(feature = "event-stream")]
@@ -65,6 +73,7 @@ impl UnixInternalEventSource {
             tty_buffer: [0u8; TTY_BUFFER_SIZE],
             tty_fd: input_fd,
             signals,
+            signal_events: false,
             #[cfg(feature = "event-stream")]
             waker,
         })
@@ -78,9 +87,20 @@ impl EventSource for UnixInternalEventSource {
         }
 
         let timeout = PollTimeout::new(timeout);
+        let escape_timeout = escape_timeout();
 
         loop {
-            if let Err(e) = self.poll.poll(&mut self.events, timeout.leftover()) {
+            // Don't sleep past the moment a pending `ESC` turns into the `Esc` key.
+            let poll_timeout = match self.parser.escape_timeout_leftover(escape_timeout) {
+                Some(escape_leftover) => Some(
+                    timeout
+                        .leftover()
+                        .map_or(escape_leftover, |leftover| leftover.min(escape_leftover)),
+                ),
+                None => timeout.leftover(),
+            };
+
+            if let Err(e) = self.poll.poll(&mut self.events, poll_timeout) {
                 // Mio will throw an interrupted error in case of cursor position retrieval. We need to retry until it succeeds.
                 // Previous versions of Mio (< 0.7) would automatically retry the poll call if it was interrupted (if EINTR was returned).
                 // https://docs.rs/mio/0.7.0/mio/struct.Poll.html#notes
@@ -93,7 +113,18 @@ impl EventSource for UnixInternalEventSource {
 
             if self.events.is_empty() {
                 // No readiness events = timeout
-                return Ok(None);
+                if self.parser.escape_timeout_leftover(escape_timeout) == Some(Duration::ZERO) {
+                    self.parser.flush();
+
+                    if let Some(event) = self.parser.next() {
+                        return Ok(Some(event));
+                    }
+                }
+
+                if timeout.elapsed() {
+                    return Ok(None);
+                }
+                continue;
             }
 
             for token in self.events.iter().map(|x| x.token()) {
@@ -105,7 +136,8 @@ impl EventSource for UnixInternalEventSource {
                                     if read_count > 0 {
                                         self.parser.advance(
                                             &self.tty_buffer[..read_count],
-                                            read_count == TTY_BUFFER_SIZE,
+                                            read_count == TTY_BUFFER_SIZE
+                                                || !escape_timeout.is_zero(),
                                         );
                                     }
                                 }
@@ -128,8 +160,8 @@ impl EventSource for UnixInternalEventSource {
                         }
                     }
                     SIGNAL_TOKEN => {
-                        for signal in &self.signals {
-                            match signal as libc::c_int {
+                        for signal in self.signals.pending() {
+                            let event = match signal as libc::c_int {
                                 signal_hook::SIGWINCH => {
                                     // TODO Should we remove tput?
                                     //
@@ -139,12 +171,20 @@ impl EventSource for UnixInternalEventSource {
                                     // it's a really long time from the mio, async-std/tokio executor, ...
                                     // point of view.
                                     let new_size = crate::terminal::size()?;
-                                    return Ok(Some(InternalEvent::Event(Event::Resize(
-                                        new_size.0, new_size.1,
-                                    ))));
+                                    Event::Resize(new_size.0, new_size.1)
                                 }
+                                signal_hook::SIGINT => Event::Signal(Signal::Interrupt),
+                                signal_hook::SIGTERM => Event::Signal(Signal::Terminate),
+                                signal_hook::SIGHUP => Event::Signal(Signal::Hangup),
                                 _ => unreachable!("Synchronize signal registration & handling"),
                             };
+                            self.parser
+                                .internal_events
+                                .push_back(InternalEvent::Event(event));
+                        }
+
+                        if let Some(event) = self.parser.next() {
+                            return Ok(Some(event));
                         }
                     }
                     #[cfg(feature = "event-stream")]
@@ -166,6 +206,30 @@ impl EventSource for UnixInternalEventSource {
         }
     }
 
+    fn raw_fd(&self) -> Option<RawFd> {
+        Some(self.tty_fd.raw_fd())
+    }
+
+    fn discard_incomplete_input(&mut self) {
+        self.parser.discard_incomplete();
+    }
+
+    fn enable_signal_events(&mut self) -> Result<()> {
+        for signal in &[
+            signal_hook::SIGINT,
+            signal_hook::SIGTERM,
+            signal_hook::SIGHUP,
+        ] {
+            self.signals.add_signal(*signal)?;
+        }
+        self.signal_events = true;
+        Ok(())
+    }
+
+    fn signal_events_enabled(&self) -> bool {
+        self.signal_events
+    }
+
     #[cfg(feature = "event-stream")]
     fn waker(&self) -> Waker {
         self.waker.clone()
@@ -182,6 +246,13 @@ impl EventSource for UnixInternalEventSource {
 struct Parser {
     buffer: Vec<u8>,
     internal_events: VecDeque<InternalEvent>,
+    // The moment the buffer started to hold an ambiguous sequence (e.g. a lone `ESC`),
+    // which can be an event on its own or a beginning of another one.
+    ambiguous_since: Option<Instant>,
+    // The grapheme cluster assembled from the character key events of the current read.
+    grapheme: Option<Grapheme>,
+    // The high surrogate of a character which arrives as two records (win32-input-mode).
+    surrogate_buffer: Option<u16>,
 }
 
 impl Default for Parser {
@@ -204,6 +275,9 @@ impl Default for Parser {
             // method implementation, all events are consumed before the next TTY_BUFFER
             // is processed -> events pushed.
             internal_events: VecDeque::with_capacity(128),
+            ambiguous_since: None,
+            grapheme: None,
+            surrogate_buffer: None,
         }
     }
 }
@@ -217,7 +291,7 @@ impl Parser {
 
             match parse_event(&self.buffer, more) {
                 Ok(Some(ie)) => {
-                    self.internal_events.push_back(ie);
+                    self.push(ie);
                     self.buffer.clear();
                 }
                 Ok(None) => {
@@ -231,6 +305,100 @@ impl Parser {
                 }
             }
         }
+        // The characters of a cluster arrive together, e.g. when an input method commits it
+        self.finish_grapheme();
+
+        self.ambiguous_since = match parse_event(&self.buffer, false) {
+            Ok(Some(_)) => Some(Instant::now()),
+            _ => None,
+        };
+    }
+
+    fn push(&mut self, ie: InternalEvent) {
+        let ie = match ie {
+            InternalEvent::Utf16Surrogate(unit, key_event) => {
+                match self.combine_surrogates(unit, key_event) {
+                    Some(key_event) => InternalEvent::Event(Event::Key(key_event)),
+                    None => return,
+                }
+            }
+            ie => ie,
+        };
+
+        if let InternalEvent::Event(Event::Key(key_event)) = &ie {
+            if let Some(grapheme) = self.grapheme.as_mut() {
+                if grapheme.extend(key_event) {
+                    return;
+                }
+            }
+
+            self.finish_grapheme();
+            self.grapheme = Grapheme::start(*key_event);
+            if self.grapheme.is_some() {
+                return;
+            }
+        } else {
+            self.finish_grapheme();
+        }
+
+        self.internal_events.push_back(ie);
+    }
+
+    /// Combines the halves of a UTF-16 surrogate pair into the key event of the character.
+    ///
+    /// Only the presses are combined, the releases of the halves are dropped.
+    fn combine_surrogates(&mut self, unit: u16, key_event: KeyEvent) -> Option<KeyEvent> {
+        if key_event.kind == KeyEventKind::Release {
+            return None;
+        }
+
+        match unit {
+            0xD800..=0xDBFF => {
+                self.surrogate_buffer = Some(unit);
+                None
+            }
+            _ => {
+                let high_surrogate = self.surrogate_buffer.take()?;
+                let character = std::char::decode_utf16([high_surrogate, unit].iter().copied())
+                    .next()?
+                    .ok()?;
+
+                Some(KeyEvent {
+                    code: KeyCode::Char(character),
+                    ..key_event
+                })
+            }
+        }
+    }
+
+    fn finish_grapheme(&mut self) {
+        if let Some(grapheme) = self.grapheme.take() {
+            self.internal_events
+                .push_back(InternalEvent::Event(grapheme.into_event()));
+        }
+    }
+
+    /// Returns how long to wait for more input before the ambiguous buffer is flushed, `None` if
+    /// the buffer isn't ambiguous.
+    fn escape_timeout_leftover(&self, escape_timeout: Duration) -> Option<Duration> {
+        self.ambiguous_since
+            .map(|since| escape_timeout.saturating_sub(since.elapsed()))
+    }
+
+    /// Drops the bytes of a sequence which wasn't completed yet.
+    fn discard_incomplete(&mut self) {
+        self.buffer.clear();
+        self.surrogate_buffer = None;
+        self.ambiguous_since = None;
+    }
+
+    /// Parses the ambiguous buffer as if no more input is available.
+    fn flush(&mut self) {
+        if let Ok(Some(ie)) = parse_event(&self.buffer, false) {
+            self.internal_events.push_back(ie);
+        }
+        self.buffer.clear();
+        self.ambiguous_since = None;
     }
 }
 
@@ -241,3 +409,152 @@ impl Iterator for Parser {
         self.internal_events.pop_front()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        os::unix::{io::IntoRawFd, net::UnixStream},
+        time::Duration,
+    };
+
+    use super::{EventSource, FileDesc, Parser, UnixInternalEventSource};
+    use crate::event::{Event, InternalEvent, KeyCode, KeyEvent, KeyModifiers};
+
+    #[test]
+    fn test_read_from_socket() {
+        let (mut writer, reader) = UnixStream::pair().unwrap();
+        let mut source = UnixInternalEventSource::from_file_descriptor(FileDesc::new(
+            reader.into_raw_fd(),
+            true,
+        ))
+        .unwrap();
+
+        assert_eq!(
+            source.try_read(Some(Duration::from_millis(0))).unwrap(),
+            None
+        );
+
+        writer.write_all(b"a\x1B[A").unwrap();
+        assert_eq!(
+            source.try_read(Some(Duration::from_secs(1))).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Char('a').into())))
+        );
+        assert_eq!(
+            source.try_read(Some(Duration::from_secs(1))).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Up.into())))
+        );
+    }
+
+    #[test]
+    fn test_lone_escape_is_ambiguous_until_flushed() {
+        let mut parser = Parser::default();
+        parser.advance(b"\x1B", true);
+
+        assert_eq!(parser.next(), None);
+        assert_eq!(
+            parser.escape_timeout_leftover(Duration::from_secs(0)),
+            Some(Duration::from_secs(0))
+        );
+
+        parser.flush();
+        assert_eq!(
+            parser.next(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Esc.into())))
+        );
+        assert_eq!(parser.escape_timeout_leftover(Duration::from_secs(0)), None);
+    }
+
+    #[test]
+    fn test_multi_codepoint_input() {
+        let mut parser = Parser::default();
+        // U+1F44D U+1F3FD (thumbs up with a skin tone), split in the middle of a code point
+        let input = "\u{1F44D}\u{1F3FD}".as_bytes();
+        parser.advance(&input[..2], false);
+        assert_eq!(parser.next(), None);
+        parser.advance(&input[2..], false);
+
+        assert_eq!(
+            parser.next(),
+            Some(InternalEvent::Event(Event::Grapheme(String::from(
+                "\u{1F44D}\u{1F3FD}"
+            ))))
+        );
+        assert_eq!(parser.next(), None);
+    }
+
+    #[test]
+    fn test_win32_input_mode_surrogate_pair() {
+        let mut parser = Parser::default();
+        // U+1F44D (thumbs up) as two records, pressed and released
+        parser.advance(b"\x1B[0;0;55357;1;0;1_\x1B[0;0;56397;1;0;1_", false);
+        parser.advance(b"\x1B[0;0;55357;0;0;1_\x1B[0;0;56397;0;0;1_", false);
+
+        assert_eq!(
+            parser.next(),
+            Some(InternalEvent::Event(Event::Key(
+                KeyCode::Char('\u{1F44D}').into()
+            )))
+        );
+        assert_eq!(parser.next(), None);
+
+        // A low surrogate without the high one
+        parser.advance(b"\x1B[0;0;56397;1;0;1_", false);
+        assert_eq!(parser.next(), None);
+    }
+
+    #[test]
+    fn test_grapheme_followed_by_keys() {
+        let mut parser = Parser::default();
+        // `e` + U+0301 (combining acute accent), `a`, Up
+        parser.advance("e\u{301}a\x1B[A".as_bytes(), false);
+
+        assert_eq!(
+            parser.next(),
+            Some(InternalEvent::Event(Event::Grapheme(String::from(
+                "e\u{301}"
+            ))))
+        );
+        assert_eq!(
+            parser.next(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Char('a').into())))
+        );
+        assert_eq!(
+            parser.next(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Up.into())))
+        );
+        assert_eq!(parser.next(), None);
+    }
+
+    #[test]
+    fn test_alt_closing_bracket_followed_by_key() {
+        let mut parser = Parser::default();
+        parser.advance(b"\x1B]a", false);
+
+        assert_eq!(
+            parser.next(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char(']'),
+                KeyModifiers::ALT
+            ))))
+        );
+        assert_eq!(
+            parser.next(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Char('a').into())))
+        );
+        assert_eq!(parser.next(), None);
+    }
+
+    #[test]
+    fn test_escape_sequence_split_across_reads() {
+        let mut parser = Parser::default();
+        parser.advance(b"\x1B", true);
+        parser.advance(b"[A", true);
+
+        assert_eq!(
+            parser.next(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Up.into())))
+        );
+        assert_eq!(parser.escape_timeout_leftover(Duration::from_secs(1)), None);
+    }
+}
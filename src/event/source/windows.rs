@@ -1,12 +1,14 @@
 use std::time::Duration;
 
-use crossterm_winapi::{Console, Handle, InputRecord};
+use crossterm_winapi::{Console, ConsoleMode, Handle, InputRecord};
+use winapi::um::wincon::ENABLE_WINDOW_INPUT;
 
 use crate::event::{sys::windows::poll::WinApiPoll, Event};
 
 #[cfg(feature = "event-stream")]
 use super::super::sys::Waker;
 use super::super::{
+    grapheme::Grapheme,
     source::EventSource,
     sys::windows::parse::{handle_key_event, handle_mouse_event},
     timeout::PollTimeout,
@@ -16,11 +18,22 @@ use super::super::{
 pub(crate) struct WindowsEventSource {
     console: Console,
     poll: WinApiPoll,
+    surrogate_buffer: Option<u16>,
+    // The event read after a grapheme cluster, which didn't continue it.
+    pending_event: Option<Event>,
+    // Whether the window input mode was enabled before the source enabled it.
+    window_input_was_enabled: bool,
 }
 
 impl WindowsEventSource {
     pub(crate) fn new() -> Result<WindowsEventSource> {
         let console = Console::from(Handle::current_in_handle()?);
+
+        // The console reports size changes only with the window input mode
+        let mode = ConsoleMode::from(Handle::current_in_handle()?);
+        let previous_mode = mode.mode()?;
+        mode.set_mode(previous_mode | ENABLE_WINDOW_INPUT)?;
+
         Ok(WindowsEventSource {
             console,
 
@@ -28,28 +41,99 @@ impl WindowsEventSource {
             poll: WinApiPoll::new(),
             #[cfg(feature = "event-stream")]
             poll: WinApiPoll::new()?,
+
+            surrogate_buffer: None,
+            pending_event: None,
+            window_input_was_enabled: previous_mode & ENABLE_WINDOW_INPUT != 0,
         })
     }
 }
 
+impl Drop for WindowsEventSource {
+    fn drop(&mut self) {
+        if self.window_input_was_enabled {
+            return;
+        }
+
+        // Only the window input mode is restored, the other modes (e.g. the raw mode) may have
+        // been changed since
+        if let Ok(handle) = Handle::current_in_handle() {
+            let mode = ConsoleMode::from(handle);
+            if let Ok(current_mode) = mode.mode() {
+                let _ = mode.set_mode(current_mode & !ENABLE_WINDOW_INPUT);
+            }
+        }
+    }
+}
+
+impl WindowsEventSource {
+    fn read_event(&mut self) -> Result<Option<Event>> {
+        let event = match self.console.read_single_input_event()? {
+            InputRecord::KeyEvent(record) => handle_key_event(record, &mut self.surrogate_buffer),
+            InputRecord::MouseEvent(record) => handle_mouse_event(record),
+            InputRecord::WindowBufferSizeEvent(_) => {
+                // The record contains the screen buffer size (including the
+                // scrollback), report the window size like on UNIX.
+                let (columns, rows) = crate::terminal::size()?;
+                Some(Event::Resize(columns, rows))
+            }
+            InputRecord::FocusEvent(record) => {
+                let event = if record.set_focus {
+                    Event::FocusGained
+                } else {
+                    Event::FocusLost
+                };
+                Some(event)
+            }
+            _ => None,
+        };
+
+        Ok(event)
+    }
+
+    /// Appends the characters of the records which are already available to the grapheme
+    /// cluster started by the key event.
+    fn assemble_grapheme(&mut self, event: Event) -> Result<Event> {
+        let mut grapheme = match &event {
+            Event::Key(key_event) => match Grapheme::start(*key_event) {
+                Some(grapheme) => grapheme,
+                None => return Ok(event),
+            },
+            _ => return Ok(event),
+        };
+
+        // The characters of a cluster arrive together, e.g. when an input method commits it
+        while self.console.number_of_console_input_events()? != 0 {
+            match self.read_event()? {
+                Some(Event::Key(key_event)) if grapheme.extend(&key_event) => {}
+                Some(event) => {
+                    self.pending_event = Some(event);
+                    break;
+                }
+                // Key releases, halves of surrogate pairs, ...
+                None => {}
+            }
+        }
+
+        Ok(grapheme.into_event())
+    }
+}
+
 impl EventSource for WindowsEventSource {
     fn try_read(&mut self, timeout: Option<Duration>) -> Result<Option<InternalEvent>> {
+        if let Some(event) = self.pending_event.take() {
+            let event = self.assemble_grapheme(event)?;
+            return Ok(Some(InternalEvent::Event(event)));
+        }
+
         let poll_timeout = PollTimeout::new(timeout);
 
         loop {
             if let Some(event_ready) = self.poll.poll(poll_timeout.leftover())? {
                 let number = self.console.number_of_console_input_events()?;
                 if event_ready && number != 0 {
-                    let event = match self.console.read_single_input_event()? {
-                        InputRecord::KeyEvent(record) => handle_key_event(record),
-                        InputRecord::MouseEvent(record) => handle_mouse_event(record),
-                        InputRecord::WindowBufferSizeEvent(record) => {
-                            Some(Event::Resize(record.size.x as u16, record.size.y as u16))
-                        }
-                        _ => None,
-                    };
-
-                    if let Some(event) = event {
+                    if let Some(event) = self.read_event()? {
+                        let event = self.assemble_grapheme(event)?;
                         return Ok(Some(InternalEvent::Event(event)));
                     }
                 }
@@ -61,6 +145,10 @@ impl EventSource for WindowsEventSource {
         }
     }
 
+    fn discard_incomplete_input(&mut self) {
+        self.surrogate_buffer = None;
+    }
+
     #[cfg(feature = "event-stream")]
     fn waker(&self) -> Waker {
         self.poll.waker()
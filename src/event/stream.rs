@@ -30,6 +30,39 @@ use super::{
 ///
 /// Check the [examples](https://github.com/crossterm-rs/crossterm/tree/master/examples) folder to see how to use
 /// it (`event-stream-*`).
+///
+/// # Examples
+///
+/// Waiting for the terminal input and a network connection at the same time, without spawning
+/// a blocking thread:
+///
+/// ```no_run
+/// use crossterm::event::{Event, EventStream, KeyCode};
+/// use futures::StreamExt;
+/// use tokio::net::TcpListener;
+///
+/// #[tokio::main]
+/// async fn main() -> crossterm::Result<()> {
+///     let listener = TcpListener::bind("127.0.0.1:8080").await?;
+///     let mut events = EventStream::new();
+///
+///     loop {
+///         tokio::select! {
+///             connection = listener.accept() => {
+///                 let (_socket, address) = connection?;
+///                 println!("Connection from {}\r", address);
+///             }
+///             event = events.next() => match event {
+///                 Some(Ok(Event::Key(key))) if key.code == KeyCode::Esc => break,
+///                 Some(Ok(event)) => println!("{:?}\r", event),
+///                 Some(Err(e)) => return Err(e),
+///                 None => break,
+///             }
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
 #[derive(Debug)]
 pub struct EventStream {
     poll_internal_waker: Waker,
@@ -1,7 +1,15 @@
-use std::io;
+use std::{
+    io,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use crate::{
-    event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
+    event::{
+        report_raw_key_codes, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton,
+        MouseEvent, MouseEventKind, RawKeyCodes,
+    },
+    style::{Color, Colored},
+    terminal::base64,
     ErrorKind, Result,
 };
 
@@ -19,11 +27,18 @@ use super::super::super::InternalEvent;
 // Ok(Some(event)) -> we have event, clear the buffer
 //
 
+// Set while a query which the terminal answers with a control string (OSC or DCS) is pending.
+// `ESC ]` and `ESC P` are parsed as Alt + ] and Alt + P otherwise, without waiting for the next
+// bytes.
+static STRING_ANSWER_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether a query which the terminal answers with a control string (OSC or DCS) is pending.
+pub(crate) fn set_string_answer_pending(pending: bool) {
+    STRING_ANSWER_PENDING.store(pending, Ordering::SeqCst);
+}
+
 fn could_not_parse_event_error() -> ErrorKind {
-    ErrorKind::IoError(io::Error::new(
-        io::ErrorKind::Other,
-        "Could not parse an event.",
-    ))
+    ErrorKind::IoError(io::Error::other("Could not parse an event."))
 }
 
 pub(crate) fn parse_event(buffer: &[u8], input_available: bool) -> Result<Option<InternalEvent>> {
@@ -56,6 +71,20 @@ pub(crate) fn parse_event(buffer: &[u8], input_available: bool) -> Result<Option
                         }
                     }
                     b'[' => parse_csi(buffer),
+                    // `ESC ]` is Alt + ] unless it starts the answer to a pending query
+                    b']' if could_be_osc_answer(&buffer[2..])
+                        && (buffer.len() > 2
+                            || input_available && STRING_ANSWER_PENDING.load(Ordering::SeqCst)) =>
+                    {
+                        parse_osc(buffer)
+                    }
+                    // `ESC P` is Alt + P unless it starts the answer to a pending query
+                    b'P' if could_be_dcs_answer(&buffer[2..])
+                        && (buffer.len() > 2
+                            || input_available && STRING_ANSWER_PENDING.load(Ordering::SeqCst)) =>
+                    {
+                        parse_dcs(buffer)
+                    }
                     b'\x1B' => Ok(Some(InternalEvent::Event(Event::Key(KeyCode::Esc.into())))),
                     _ => parse_event(&buffer[1..], input_available).map(|event_option| {
                         event_option.map(|event| {
@@ -86,11 +115,11 @@ pub(crate) fn parse_event(buffer: &[u8], input_available: bool) -> Result<Option
             KeyCode::Backspace.into(),
         )))),
         c @ b'\x01'..=b'\x1A' => Ok(Some(InternalEvent::Event(Event::Key(KeyEvent::new(
-            KeyCode::Char((c as u8 - 0x1 + b'a') as char),
+            KeyCode::Char((c - 0x1 + b'a') as char),
             KeyModifiers::CONTROL,
         ))))),
         c @ b'\x1C'..=b'\x1F' => Ok(Some(InternalEvent::Event(Event::Key(KeyEvent::new(
-            KeyCode::Char((c as u8 - 0x1C + b'4') as char),
+            KeyCode::Char((c - 0x1C + b'4') as char),
             KeyModifiers::CONTROL,
         ))))),
         b'\0' => Ok(Some(InternalEvent::Event(Event::Key(KeyEvent::new(
@@ -117,7 +146,7 @@ fn char_code_to_event(code: KeyCode) -> KeyEvent {
 }
 
 pub(crate) fn parse_csi(buffer: &[u8]) -> Result<Option<InternalEvent>> {
-    assert!(buffer.starts_with(&[b'\x1B', b'['])); // ESC [
+    assert!(buffer.starts_with(b"\x1B[")); // ESC [
 
     if buffer.len() == 2 {
         return Ok(None);
@@ -142,12 +171,16 @@ pub(crate) fn parse_csi(buffer: &[u8]) -> Result<Option<InternalEvent>> {
         b'B' => Some(Event::Key(KeyCode::Down.into())),
         b'H' => Some(Event::Key(KeyCode::Home.into())),
         b'F' => Some(Event::Key(KeyCode::End.into())),
-        b'Z' => Some(Event::Key(KeyEvent {
-            code: KeyCode::BackTab,
-            modifiers: KeyModifiers::SHIFT,
-        })),
+        b'Z' => Some(Event::Key(KeyEvent::new(
+            KeyCode::BackTab,
+            KeyModifiers::SHIFT,
+        ))),
+        b'I' => Some(Event::FocusGained),
+        b'O' => Some(Event::FocusLost),
         b'M' => return parse_csi_normal_mouse(buffer),
         b'<' => return parse_csi_sgr_mouse(buffer),
+        b'?' => return parse_csi_private(buffer),
+        b'2' if buffer.starts_with(b"\x1B[200~") => return parse_csi_bracketed_paste(buffer),
         b'0'..=b'9' => {
             // Numbered escape code.
             if buffer.len() == 3 {
@@ -162,7 +195,10 @@ pub(crate) fn parse_csi(buffer: &[u8]) -> Result<Option<InternalEvent>> {
                     match buffer[buffer.len() - 1] {
                         b'M' => return parse_csi_rxvt_mouse(buffer),
                         b'~' => return parse_csi_special_key_code(buffer),
+                        b'u' => return parse_csi_u_encoded_key_code(buffer),
+                        b'_' => return parse_csi_win32_input_mode(buffer),
                         b'R' => return parse_csi_cursor_position(buffer),
+                        b't' => return parse_csi_window_report(buffer),
                         _ => return parse_csi_modifier_key_code(buffer),
                     }
                 }
@@ -174,6 +210,143 @@ pub(crate) fn parse_csi(buffer: &[u8]) -> Result<Option<InternalEvent>> {
     Ok(input_event.map(InternalEvent::Event))
 }
 
+// Returns `false` if the bytes after `ESC ]` can't start an answer to a query, which is either
+// `l` (window title) or a number followed by `;`.
+fn could_be_osc_answer(payload: &[u8]) -> bool {
+    let digits = payload.iter().take_while(|b| b.is_ascii_digit()).count();
+
+    match payload.get(digits) {
+        None => true,
+        Some(b'l') => digits == 0,
+        Some(b';') => digits > 0,
+        Some(_) => false,
+    }
+}
+
+pub(crate) fn parse_osc(buffer: &[u8]) -> Result<Option<InternalEvent>> {
+    // ESC ] Ps ; Pt BEL
+    // ESC ] Ps ; Pt ESC \
+    assert!(buffer.starts_with(b"\x1B]")); // ESC ]
+
+    let content = if let Some(content) = buffer[2..].strip_suffix(b"\x07") {
+        content
+    } else if let Some(content) = buffer[2..].strip_suffix(b"\x1B\\") {
+        content
+    } else {
+        // Wait for the string terminator
+        return Ok(None);
+    };
+
+    let s = std::str::from_utf8(content).map_err(|_| could_not_parse_event_error())?;
+
+    // ESC ] l title ST - window title
+    if let Some(title) = s.strip_prefix('l') {
+        return Ok(Some(InternalEvent::Title(
+            title.replace(char::is_control, ""),
+        )));
+    }
+
+    let (code, value) = s.split_once(';').ok_or_else(could_not_parse_event_error)?;
+
+    let event = match code {
+        // ESC ] 10 ; rgb:RRRR/GGGG/BBBB ST - default foreground color
+        "10" => InternalEvent::DefaultColor(Colored::ForegroundColor(parse_osc_rgb(value)?)),
+        // ESC ] 11 ; rgb:RRRR/GGGG/BBBB ST - default background color
+        "11" => InternalEvent::DefaultColor(Colored::BackgroundColor(parse_osc_rgb(value)?)),
+        // ESC ] 52 ; Pc ; base64 ST - clipboard contents
+        "52" => {
+            let (_, data) = value
+                .split_once(';')
+                .ok_or_else(could_not_parse_event_error)?;
+            let bytes = base64::decode(data).ok_or_else(could_not_parse_event_error)?;
+            InternalEvent::ClipboardContents(String::from_utf8_lossy(&bytes).into_owned())
+        }
+        _ => return Err(could_not_parse_event_error()),
+    };
+
+    Ok(Some(event))
+}
+
+// Returns `false` if the bytes after `ESC P` can't start an answer to a query, which is
+// `1 + r` or `0 + r` (`XTGETTCAP`).
+fn could_be_dcs_answer(payload: &[u8]) -> bool {
+    payload
+        .iter()
+        .zip(b"?+r")
+        .enumerate()
+        .all(|(idx, (byte, expected))| match idx {
+            0 => *byte == b'0' || *byte == b'1',
+            _ => byte == expected,
+        })
+}
+
+pub(crate) fn parse_dcs(buffer: &[u8]) -> Result<Option<InternalEvent>> {
+    // ESC P 1 + r Pt ESC \ - valid terminfo capability (`XTGETTCAP` answer)
+    // ESC P 0 + r Pt ESC \ - invalid terminfo capability
+    assert!(buffer.starts_with(b"\x1BP")); // ESC P
+
+    let content = match buffer[2..].strip_suffix(b"\x1B\\") {
+        Some(content) => content,
+        // Wait for the string terminator
+        None => return Ok(None),
+    };
+
+    let (valid, pt) = match content {
+        [b'1', b'+', b'r', pt @ ..] => (true, pt),
+        [b'0', b'+', b'r', pt @ ..] => (false, pt),
+        _ => return Err(could_not_parse_event_error()),
+    };
+
+    // Pt is the hex encoded name, followed by `=` and the hex encoded value if it's valid
+    let name = pt.split(|b| *b == b'=').next().unwrap_or_default();
+    let name = decode_hex(name).ok_or_else(could_not_parse_event_error)?;
+
+    Ok(Some(InternalEvent::TerminfoCapability(name, valid)))
+}
+
+/// Decodes a hex encoded string (e.g. `524742` - `RGB`).
+fn decode_hex(hex: &[u8]) -> Option<String> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+
+    let bytes = hex
+        .chunks(2)
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    String::from_utf8(bytes).ok()
+}
+
+/// Parses the X11 `rgb:R/G/B` color specification (1 - 4 hex digits per component).
+fn parse_osc_rgb(spec: &str) -> Result<Color> {
+    let components = spec
+        .strip_prefix("rgb:")
+        .ok_or_else(could_not_parse_event_error)?;
+
+    let mut split = components.split('/');
+    let mut next_component = || -> Result<u8> {
+        let hex = split.next().ok_or_else(could_not_parse_event_error)?;
+        if hex.is_empty() || hex.len() > 4 {
+            return Err(could_not_parse_event_error());
+        }
+        let value = u32::from_str_radix(hex, 16).map_err(|_| could_not_parse_event_error())?;
+        let max = (1 << (4 * hex.len())) - 1;
+        Ok((value * 255 / max) as u8)
+    };
+
+    let color = Color::Rgb {
+        r: next_component()?,
+        g: next_component()?,
+        b: next_component()?,
+    };
+
+    if split.next().is_some() {
+        return Err(could_not_parse_event_error());
+    }
+
+    Ok(color)
+}
+
 pub(crate) fn next_parsed<T>(iter: &mut dyn Iterator<Item = &str>) -> Result<T>
 where
     T: std::str::FromStr,
@@ -184,12 +357,43 @@ where
         .map_err(|_| could_not_parse_event_error())
 }
 
+pub(crate) fn parse_csi_private(buffer: &[u8]) -> Result<Option<InternalEvent>> {
+    // ESC [ ? Pm ; Ps $ y - mode report (`DECRQM` answer)
+    // ESC [ ? Ps ; ... c  - primary device attributes (`DA1` answer)
+    assert!(buffer.starts_with(b"\x1B[?")); // ESC [ ?
+
+    // Wait for the final byte, `$` is an intermediate byte
+    let last_byte = *buffer.last().unwrap();
+    if buffer.len() == 3 || !(64..=126).contains(&last_byte) {
+        return Ok(None);
+    }
+
+    let s = std::str::from_utf8(&buffer[3..buffer.len() - 1])
+        .map_err(|_| could_not_parse_event_error())?;
+
+    match last_byte {
+        b'c' => Ok(Some(InternalEvent::PrimaryDeviceAttributes)),
+        b'y' => {
+            let s = s
+                .strip_suffix('$')
+                .ok_or_else(could_not_parse_event_error)?;
+            let mut split = s.split(';');
+
+            let mode = next_parsed::<u16>(&mut split)?;
+            let status = next_parsed::<u8>(&mut split)?;
+
+            Ok(Some(InternalEvent::ModeReport(mode, status)))
+        }
+        _ => Err(could_not_parse_event_error()),
+    }
+}
+
 pub(crate) fn parse_csi_cursor_position(buffer: &[u8]) -> Result<Option<InternalEvent>> {
     // ESC [ Cy ; Cx R
     //   Cy - cursor row number (starting from 1)
     //   Cx - cursor column number (starting from 1)
-    assert!(buffer.starts_with(&[b'\x1B', b'['])); // ESC [
-    assert!(buffer.ends_with(&[b'R']));
+    assert!(buffer.starts_with(b"\x1B[")); // ESC [
+    assert!(buffer.ends_with(b"R"));
 
     let s = std::str::from_utf8(&buffer[2..buffer.len() - 1])
         .map_err(|_| could_not_parse_event_error())?;
@@ -202,6 +406,26 @@ pub(crate) fn parse_csi_cursor_position(buffer: &[u8]) -> Result<Option<Internal
     Ok(Some(InternalEvent::CursorPosition(x, y)))
 }
 
+pub(crate) fn parse_csi_window_report(buffer: &[u8]) -> Result<Option<InternalEvent>> {
+    // ESC [ 4 ; height ; width t - text area size in pixels
+    assert!(buffer.starts_with(b"\x1B[")); // ESC [
+    assert!(buffer.ends_with(b"t"));
+
+    let s = std::str::from_utf8(&buffer[2..buffer.len() - 1])
+        .map_err(|_| could_not_parse_event_error())?;
+
+    let mut split = s.split(';');
+
+    match next_parsed::<u16>(&mut split)? {
+        4 => {
+            let height = next_parsed::<u16>(&mut split)?;
+            let width = next_parsed::<u16>(&mut split)?;
+            Ok(Some(InternalEvent::PixelSize(width, height)))
+        }
+        _ => Err(could_not_parse_event_error()),
+    }
+}
+
 fn parse_modifiers(mask: u8) -> KeyModifiers {
     let modifier_mask = mask.saturating_sub(1);
     let mut modifiers = KeyModifiers::empty();
@@ -217,13 +441,42 @@ fn parse_modifiers(mask: u8) -> KeyModifiers {
     modifiers
 }
 
+fn parse_key_event_kind(kind: u8) -> KeyEventKind {
+    match kind {
+        2 => KeyEventKind::Repeat,
+        3 => KeyEventKind::Release,
+        _ => KeyEventKind::Press,
+    }
+}
+
+// Parses the `modifiers[:event-type]` parameter, the event type is only sent by terminals
+// implementing the kitty keyboard protocol.
+fn modifier_and_kind_parsed(param: &str) -> Result<(u8, u8)> {
+    let mut sub_split = param.split(':');
+    let modifier_mask = next_parsed::<u8>(&mut sub_split)?;
+
+    if let Ok(kind_code) = next_parsed::<u8>(&mut sub_split) {
+        Ok((modifier_mask, kind_code))
+    } else {
+        Ok((modifier_mask, 1))
+    }
+}
+
 pub(crate) fn parse_csi_modifier_key_code(buffer: &[u8]) -> Result<Option<InternalEvent>> {
-    assert!(buffer.starts_with(&[b'\x1B', b'['])); // ESC [
+    assert!(buffer.starts_with(b"\x1B[")); // ESC [
 
-    let modifier_mask = buffer[buffer.len() - 2];
+    let s = std::str::from_utf8(&buffer[2..buffer.len() - 1])
+        .map_err(|_| could_not_parse_event_error())?;
+    let mut split = s.split(';');
+
+    // xterm sends either `ESC [ 1 ; <modifiers> <key>` or the shorter
+    // `ESC [ <modifiers> <key>`, the modifier parameter is always the last one.
+    let first = split.next().ok_or_else(could_not_parse_event_error)?;
+    let (modifier_mask, kind_code) = modifier_and_kind_parsed(split.next().unwrap_or(first))?;
     let key = buffer[buffer.len() - 1];
 
     let modifiers = parse_modifiers(modifier_mask);
+    let kind = parse_key_event_kind(kind_code);
 
     let keycode = match key {
         b'A' => KeyCode::Up,
@@ -239,14 +492,14 @@ pub(crate) fn parse_csi_modifier_key_code(buffer: &[u8]) -> Result<Option<Intern
         _ => return Err(could_not_parse_event_error()),
     };
 
-    let input_event = Event::Key(KeyEvent::new(keycode, modifiers));
+    let input_event = Event::Key(KeyEvent::new_with_kind(keycode, modifiers, kind));
 
     Ok(Some(InternalEvent::Event(input_event)))
 }
 
 pub(crate) fn parse_csi_special_key_code(buffer: &[u8]) -> Result<Option<InternalEvent>> {
-    assert!(buffer.starts_with(&[b'\x1B', b'['])); // ESC [
-    assert!(buffer.ends_with(&[b'~']));
+    assert!(buffer.starts_with(b"\x1B[")); // ESC [
+    assert!(buffer.ends_with(b"~"));
 
     let s = std::str::from_utf8(&buffer[2..buffer.len() - 1])
         .map_err(|_| could_not_parse_event_error())?;
@@ -255,11 +508,15 @@ pub(crate) fn parse_csi_special_key_code(buffer: &[u8]) -> Result<Option<Interna
     // This CSI sequence can be a list of semicolon-separated numbers.
     let first = next_parsed::<u8>(&mut split)?;
 
-    let modifiers = if let Ok(modifier_mask) = next_parsed::<u8>(&mut split) {
-        parse_modifiers(modifier_mask)
-    } else {
-        KeyModifiers::NONE
-    };
+    let (modifiers, kind) =
+        if let Some(Ok((modifier_mask, kind_code))) = split.next().map(modifier_and_kind_parsed) {
+            (
+                parse_modifiers(modifier_mask),
+                parse_key_event_kind(kind_code),
+            )
+        } else {
+            (KeyModifiers::NONE, KeyEventKind::Press)
+        };
 
     let keycode = match first {
         1 | 7 => KeyCode::Home,
@@ -276,17 +533,194 @@ pub(crate) fn parse_csi_special_key_code(buffer: &[u8]) -> Result<Option<Interna
         _ => return Err(could_not_parse_event_error()),
     };
 
-    let input_event = Event::Key(KeyEvent::new(keycode, modifiers));
+    let input_event = Event::Key(KeyEvent::new_with_kind(keycode, modifiers, kind));
+
+    Ok(Some(InternalEvent::Event(input_event)))
+}
+
+// Maps the functional keys the kitty keyboard protocol encodes in the Unicode Private Use Area.
+fn translate_functional_key_code(codepoint: u32) -> Option<KeyCode> {
+    let keycode = match codepoint {
+        57376..=57398 => KeyCode::F((codepoint - 57376 + 13) as u8),
+        57399..=57408 => KeyCode::Char(std::char::from_digit(codepoint - 57399, 10)?),
+        57409 => KeyCode::Char('.'),
+        57410 => KeyCode::Char('/'),
+        57411 => KeyCode::Char('*'),
+        57412 => KeyCode::Char('-'),
+        57413 => KeyCode::Char('+'),
+        57414 => KeyCode::Enter,
+        57415 => KeyCode::Char('='),
+        57417 => KeyCode::Left,
+        57418 => KeyCode::Right,
+        57419 => KeyCode::Up,
+        57420 => KeyCode::Down,
+        57421 => KeyCode::PageUp,
+        57422 => KeyCode::PageDown,
+        57423 => KeyCode::Home,
+        57424 => KeyCode::End,
+        57425 => KeyCode::Insert,
+        57426 => KeyCode::Delete,
+        _ => return None,
+    };
+    Some(keycode)
+}
+
+pub(crate) fn parse_csi_u_encoded_key_code(buffer: &[u8]) -> Result<Option<InternalEvent>> {
+    // ESC [ codepoint [: shifted-codepoint] ; modifiers [: event-type] u
+    assert!(buffer.starts_with(b"\x1B[")); // ESC [
+    assert!(buffer.ends_with(b"u"));
+
+    let s = std::str::from_utf8(&buffer[2..buffer.len() - 1])
+        .map_err(|_| could_not_parse_event_error())?;
+    let mut split = s.split(';');
+
+    let mut codepoints = split
+        .next()
+        .ok_or_else(could_not_parse_event_error)?
+        .split(':');
+    let codepoint = next_parsed::<u32>(&mut codepoints)?;
+    let shifted_char = codepoints
+        .next()
+        .and_then(|shifted| shifted.parse::<u32>().ok())
+        .and_then(std::char::from_u32);
+
+    let (modifier_mask, kind_code) = match split.next() {
+        Some(param) if !param.is_empty() => modifier_and_kind_parsed(param)?,
+        _ => (1, 1),
+    };
+    let modifiers = parse_modifiers(modifier_mask);
+    let kind = parse_key_event_kind(kind_code);
+
+    let keycode = match codepoint {
+        27 => KeyCode::Esc,
+        13 => KeyCode::Enter,
+        9 if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        9 => KeyCode::Tab,
+        127 => KeyCode::Backspace,
+        57344..=63743 => {
+            translate_functional_key_code(codepoint).ok_or_else(could_not_parse_event_error)?
+        }
+        _ => {
+            let c = std::char::from_u32(codepoint).ok_or_else(could_not_parse_event_error)?;
+            match shifted_char {
+                Some(shifted) if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::Char(shifted),
+                _ => KeyCode::Char(c),
+            }
+        }
+    };
+
+    let input_event = Event::Key(KeyEvent::new_with_kind(keycode, modifiers, kind));
 
     Ok(Some(InternalEvent::Event(input_event)))
 }
 
+pub(crate) fn parse_csi_win32_input_mode(buffer: &[u8]) -> Result<Option<InternalEvent>> {
+    // ESC [ Vk ; Sc ; Uc ; Kd ; Cs ; Rc _
+    assert!(buffer.starts_with(b"\x1B[")); // ESC [
+    assert!(buffer.ends_with(b"_"));
+
+    let s = std::str::from_utf8(&buffer[2..buffer.len() - 1])
+        .map_err(|_| could_not_parse_event_error())?;
+    let mut split = s.split(';');
+
+    // Omitted parameters default to zero.
+    let mut next_param = || -> Result<u16> {
+        match split.next() {
+            None | Some("") => Ok(0),
+            Some(param) => param
+                .parse::<u16>()
+                .map_err(|_| could_not_parse_event_error()),
+        }
+    };
+    let virtual_key_code = next_param()?;
+    let scan_code = next_param()?;
+    let unicode_char = next_param()?;
+    let key_down = next_param()?;
+    let control_key_state = next_param()?;
+
+    // The `dwControlKeyState` flags of the `KEY_EVENT_RECORD`.
+    let mut modifiers = KeyModifiers::empty();
+    if control_key_state & 0x0010 != 0 {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    if control_key_state & (0x0004 | 0x0008) != 0 {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+    if control_key_state & (0x0001 | 0x0002) != 0 {
+        modifiers |= KeyModifiers::ALT;
+    }
+
+    let kind = if key_down == 0 {
+        KeyEventKind::Release
+    } else {
+        KeyEventKind::Press
+    };
+
+    let keycode = match virtual_key_code {
+        0x08 => KeyCode::Backspace,
+        0x09 if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        0x09 => KeyCode::Tab,
+        0x0D => KeyCode::Enter,
+        // The modifier keys alone
+        0x10..=0x12 => return Err(could_not_parse_event_error()),
+        0x1B => KeyCode::Esc,
+        0x21 => KeyCode::PageUp,
+        0x22 => KeyCode::PageDown,
+        0x23 => KeyCode::End,
+        0x24 => KeyCode::Home,
+        0x25 => KeyCode::Left,
+        0x26 => KeyCode::Up,
+        0x27 => KeyCode::Right,
+        0x28 => KeyCode::Down,
+        0x2D => KeyCode::Insert,
+        0x2E => KeyCode::Delete,
+        v @ 0x70..=0x87 => KeyCode::F((v - 0x6F) as u8),
+        // Characters outside of the Basic Multilingual Plane (e.g. emoji) arrive as two records,
+        // each one holding a half of the UTF-16 surrogate pair.
+        _ if (0xD800..=0xDFFF).contains(&unicode_char) => KeyCode::Null,
+        v => match std::char::from_u32(unicode_char as u32) {
+            Some(c) if !c.is_control() => KeyCode::Char(c),
+            // `Ctrl` turns the letters into control characters, use the key itself.
+            _ if (0x30..=0x39).contains(&v) || (0x41..=0x5A).contains(&v) => {
+                KeyCode::Char((v as u8).to_ascii_lowercase() as char)
+            }
+            _ => return Err(could_not_parse_event_error()),
+        },
+    };
+
+    let mut key_event = KeyEvent::new_with_kind(keycode, modifiers, kind);
+    if report_raw_key_codes() {
+        key_event.raw_codes = Some(RawKeyCodes {
+            virtual_key_code,
+            scan_code,
+        });
+    }
+
+    if (0xD800..=0xDFFF).contains(&unicode_char) {
+        return Ok(Some(InternalEvent::Utf16Surrogate(unicode_char, key_event)));
+    }
+
+    Ok(Some(InternalEvent::Event(Event::Key(key_event))))
+}
+
+pub(crate) fn parse_csi_bracketed_paste(buffer: &[u8]) -> Result<Option<InternalEvent>> {
+    // ESC [ 2 0 0 ~ pasted text ESC [ 2 0 1 ~
+    assert!(buffer.starts_with(b"\x1B[200~"));
+
+    if !buffer.ends_with(b"\x1B[201~") {
+        Ok(None)
+    } else {
+        let paste = String::from_utf8_lossy(&buffer[6..buffer.len() - 6]).to_string();
+        Ok(Some(InternalEvent::Event(Event::Paste(paste))))
+    }
+}
+
 pub(crate) fn parse_csi_rxvt_mouse(buffer: &[u8]) -> Result<Option<InternalEvent>> {
     // rxvt mouse encoding:
     // ESC [ Cb ; Cx ; Cy ; M
 
-    assert!(buffer.starts_with(&[b'\x1B', b'['])); // ESC [
-    assert!(buffer.ends_with(&[b'M']));
+    assert!(buffer.starts_with(b"\x1B[")); // ESC [
+    assert!(buffer.ends_with(b"M"));
 
     let s = std::str::from_utf8(&buffer[2..buffer.len() - 1])
         .map_err(|_| could_not_parse_event_error())?;
@@ -297,8 +731,8 @@ pub(crate) fn parse_csi_rxvt_mouse(buffer: &[u8]) -> Result<Option<InternalEvent
         .ok_or_else(could_not_parse_event_error)?;
     let (kind, modifiers) = parse_cb(cb)?;
 
-    let cx = next_parsed::<u16>(&mut split)? - 1;
-    let cy = next_parsed::<u16>(&mut split)? - 1;
+    let cx = next_parsed::<u16>(&mut split)?.saturating_sub(1);
+    let cy = next_parsed::<u16>(&mut split)?.saturating_sub(1);
 
     Ok(Some(InternalEvent::Event(Event::Mouse(MouseEvent {
         kind,
@@ -311,7 +745,7 @@ pub(crate) fn parse_csi_rxvt_mouse(buffer: &[u8]) -> Result<Option<InternalEvent
 pub(crate) fn parse_csi_normal_mouse(buffer: &[u8]) -> Result<Option<InternalEvent>> {
     // Normal mouse encoding: ESC [ M CB Cx Cy (6 characters only).
 
-    assert!(buffer.starts_with(&[b'\x1B', b'[', b'M'])); // ESC [ M
+    assert!(buffer.starts_with(b"\x1B[M")); // ESC [ M
 
     if buffer.len() < 6 {
         return Ok(None);
@@ -325,8 +759,8 @@ pub(crate) fn parse_csi_normal_mouse(buffer: &[u8]) -> Result<Option<InternalEve
     // See http://www.xfree86.org/current/ctlseqs.html#Mouse%20Tracking
     // The upper left character position on the terminal is denoted as 1,1.
     // Subtract 1 to keep it synced with cursor
-    let cx = u16::from(buffer[4].saturating_sub(32)) - 1;
-    let cy = u16::from(buffer[5].saturating_sub(32)) - 1;
+    let cx = u16::from(buffer[4].saturating_sub(32)).saturating_sub(1);
+    let cy = u16::from(buffer[5].saturating_sub(32)).saturating_sub(1);
 
     Ok(Some(InternalEvent::Event(Event::Mouse(MouseEvent {
         kind,
@@ -339,9 +773,9 @@ pub(crate) fn parse_csi_normal_mouse(buffer: &[u8]) -> Result<Option<InternalEve
 pub(crate) fn parse_csi_sgr_mouse(buffer: &[u8]) -> Result<Option<InternalEvent>> {
     // ESC [ < Cb ; Cx ; Cy (;) (M or m)
 
-    assert!(buffer.starts_with(&[b'\x1B', b'[', b'<'])); // ESC [ <
+    assert!(buffer.starts_with(b"\x1B[<")); // ESC [ <
 
-    if !buffer.ends_with(&[b'm']) && !buffer.ends_with(&[b'M']) {
+    if !buffer.ends_with(b"m") && !buffer.ends_with(b"M") {
         return Ok(None);
     }
 
@@ -355,8 +789,8 @@ pub(crate) fn parse_csi_sgr_mouse(buffer: &[u8]) -> Result<Option<InternalEvent>
     // See http://www.xfree86.org/current/ctlseqs.html#Mouse%20Tracking
     // The upper left character position on the terminal is denoted as 1,1.
     // Subtract 1 to keep it synced with cursor
-    let cx = next_parsed::<u16>(&mut split)? - 1;
-    let cy = next_parsed::<u16>(&mut split)? - 1;
+    let cx = next_parsed::<u16>(&mut split)?.saturating_sub(1);
+    let cy = next_parsed::<u16>(&mut split)?.saturating_sub(1);
 
     // When button 3 in Cb is used to represent mouse release, you can't tell which button was
     // released. SGR mode solves this by having the sequence end with a lowercase m if it's a
@@ -610,6 +1044,174 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_csi_mode_report() {
+        assert_eq!(
+            parse_event(b"\x1B[?2026;2$y", false).unwrap(),
+            Some(InternalEvent::ModeReport(2026, 2))
+        );
+        assert_eq!(
+            parse_event(b"\x1B[?1006;0$y", false).unwrap(),
+            Some(InternalEvent::ModeReport(1006, 0))
+        );
+        // Wait for the final byte
+        assert_eq!(parse_event(b"\x1B[?2026;2$", true).unwrap(), None);
+        assert!(parse_event(b"\x1B[?2026;2y", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_csi_primary_device_attributes() {
+        assert_eq!(
+            parse_event(b"\x1B[?62;1;4;22c", false).unwrap(),
+            Some(InternalEvent::PrimaryDeviceAttributes)
+        );
+        assert_eq!(
+            parse_event(b"\x1B[?6c", false).unwrap(),
+            Some(InternalEvent::PrimaryDeviceAttributes)
+        );
+        assert_eq!(parse_event(b"\x1B[?62;1", true).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_csi_window_report() {
+        assert_eq!(
+            parse_event(b"\x1B[4;600;800t", false).unwrap(),
+            Some(InternalEvent::PixelSize(800, 600))
+        );
+        assert!(parse_event(b"\x1B[8;24;80t", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_osc_default_color() {
+        assert_eq!(
+            parse_event(b"\x1B]10;rgb:ffff/8888/0000\x07", false).unwrap(),
+            Some(InternalEvent::DefaultColor(Colored::ForegroundColor(
+                Color::Rgb {
+                    r: 255,
+                    g: 136,
+                    b: 0
+                }
+            )))
+        );
+        assert_eq!(
+            parse_event(b"\x1B]11;rgb:1e/1e/2e\x1B\\", false).unwrap(),
+            Some(InternalEvent::DefaultColor(Colored::BackgroundColor(
+                Color::Rgb {
+                    r: 30,
+                    g: 30,
+                    b: 46
+                }
+            )))
+        );
+    }
+
+    #[test]
+    fn test_parse_osc_clipboard_contents() {
+        assert_eq!(
+            parse_event(b"\x1B]52;c;aGVsbG8=\x1B\\", false).unwrap(),
+            Some(InternalEvent::ClipboardContents(String::from("hello")))
+        );
+        assert_eq!(
+            parse_event(b"\x1B]52;c;\x07", false).unwrap(),
+            Some(InternalEvent::ClipboardContents(String::new()))
+        );
+        assert!(parse_event(b"\x1B]52;c;a!\x07", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_osc_title() {
+        assert_eq!(
+            parse_event(b"\x1B]lvim - main.rs\x1B\\", false).unwrap(),
+            Some(InternalEvent::Title(String::from("vim - main.rs")))
+        );
+        assert_eq!(
+            parse_event(b"\x1B]l\x1B\\", false).unwrap(),
+            Some(InternalEvent::Title(String::new()))
+        );
+    }
+
+    #[test]
+    fn test_parse_osc_waits_for_string_terminator() {
+        assert_eq!(parse_event(b"\x1B]11;rgb:1e/1e", true).unwrap(), None);
+        assert_eq!(
+            parse_event(b"\x1B]11;rgb:1e/1e/2e\x1B", true).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_osc_invalid() {
+        assert!(parse_event(b"\x1B]11;rgb:1e/1e\x07", false).is_err());
+        assert!(parse_event(b"\x1B]11;#1e1e2e\x07", false).is_err());
+        assert!(parse_event(b"\x1B]999;foo\x07", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_dcs_terminfo_capability() {
+        assert_eq!(
+            parse_event(b"\x1BP1+r524742=382F382F38\x1B\\", false).unwrap(),
+            Some(InternalEvent::TerminfoCapability(String::from("RGB"), true))
+        );
+        assert_eq!(
+            parse_event(b"\x1BP1+r5463\x1B\\", false).unwrap(),
+            Some(InternalEvent::TerminfoCapability(String::from("Tc"), true))
+        );
+        assert_eq!(
+            parse_event(b"\x1BP0+r5463\x1B\\", false).unwrap(),
+            Some(InternalEvent::TerminfoCapability(String::from("Tc"), false))
+        );
+        assert_eq!(parse_event(b"\x1BP1+r5463", true).unwrap(), None);
+        assert!(parse_event(b"\x1BP1+r546\x1B\\", false).is_err());
+    }
+
+    #[test]
+    fn test_alt_p_is_not_a_dcs_answer() {
+        // No query is pending
+        assert_eq!(
+            parse_event(b"\x1BP", true).unwrap(),
+            parse_event(b"P", false).unwrap().map(|event| match event {
+                InternalEvent::Event(Event::Key(mut key_event)) => {
+                    key_event.modifiers |= KeyModifiers::ALT;
+                    InternalEvent::Event(Event::Key(key_event))
+                }
+                event => event,
+            })
+        );
+        assert!(!could_be_dcs_answer(b"a"));
+        assert!(could_be_dcs_answer(b"1+"));
+    }
+
+    #[test]
+    fn test_alt_closing_bracket() {
+        assert_eq!(
+            parse_event(b"\x1B]", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char(']'),
+                KeyModifiers::ALT
+            )))),
+        );
+        // No query is pending, the next bytes are other keys
+        assert_eq!(
+            parse_event(b"\x1B]", true).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char(']'),
+                KeyModifiers::ALT
+            )))),
+        );
+    }
+
+    #[test]
+    fn test_could_be_osc_answer() {
+        assert!(could_be_osc_answer(b""));
+        assert!(could_be_osc_answer(b"1"));
+        assert!(could_be_osc_answer(b"52;c;"));
+        assert!(could_be_osc_answer(b"lvim"));
+        assert!(!could_be_osc_answer(b"a"));
+        assert!(!could_be_osc_answer(b";"));
+        assert!(!could_be_osc_answer(b"1a"));
+        assert!(!could_be_osc_answer(b"1l"));
+    }
+
     #[test]
     fn test_parse_csi() {
         assert_eq!(
@@ -629,6 +1231,207 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_csi_modifier_key_code_with_parameters() {
+        assert_eq!(
+            parse_csi_modifier_key_code(b"\x1B[1;5A").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Up,
+                KeyModifiers::CONTROL
+            )))),
+        );
+        assert_eq!(
+            parse_csi_modifier_key_code(b"\x1B[1;3C").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Right,
+                KeyModifiers::ALT
+            )))),
+        );
+        assert_eq!(
+            parse_csi_modifier_key_code(b"\x1B[1;6H").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Home,
+                KeyModifiers::SHIFT | KeyModifiers::CONTROL
+            )))),
+        );
+        assert_eq!(
+            parse_csi_modifier_key_code(b"\x1B[1;8P").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::F(1),
+                KeyModifiers::SHIFT | KeyModifiers::ALT | KeyModifiers::CONTROL
+            )))),
+        );
+        assert!(parse_csi_modifier_key_code(b"\x1B[1;xA").is_err());
+    }
+
+    #[test]
+    fn test_parse_event_modified_keys() {
+        assert_eq!(
+            parse_event(b"\x1B[1;5D", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Left,
+                KeyModifiers::CONTROL
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[15;2~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::F(5),
+                KeyModifiers::SHIFT
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B\r", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Enter,
+                KeyModifiers::ALT
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[Z", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::BackTab,
+                KeyModifiers::SHIFT
+            )))),
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_focus() {
+        assert_eq!(
+            parse_csi(b"\x1B[I").unwrap(),
+            Some(InternalEvent::Event(Event::FocusGained))
+        );
+        assert_eq!(
+            parse_csi(b"\x1B[O").unwrap(),
+            Some(InternalEvent::Event(Event::FocusLost))
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_win32_input_mode() {
+        // `a` pressed and released
+        assert_eq!(
+            parse_event(b"\x1B[65;30;97;1;0;1_", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Char('a').into()))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[65;30;97;0;0;1_", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new_with_kind(
+                KeyCode::Char('a'),
+                KeyModifiers::empty(),
+                KeyEventKind::Release
+            )))),
+        );
+        // Ctrl + A sends the `0x01` control character
+        assert_eq!(
+            parse_csi_win32_input_mode(b"\x1B[65;30;1;1;8;1_").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('a'),
+                KeyModifiers::CONTROL
+            )))),
+        );
+        assert_eq!(
+            parse_csi_win32_input_mode(b"\x1B[38;72;0;1;18;1_").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Up,
+                KeyModifiers::SHIFT | KeyModifiers::ALT
+            )))),
+        );
+        assert_eq!(
+            parse_csi_win32_input_mode(b"\x1B[116;63;0;1;0;1_").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::F(5).into()))),
+        );
+        // Shift alone
+        assert!(parse_csi_win32_input_mode(b"\x1B[16;42;0;1;16;1_").is_err());
+        // The high surrogate of U+1F44D
+        assert_eq!(
+            parse_csi_win32_input_mode(b"\x1B[0;0;55357;1;0;1_").unwrap(),
+            Some(InternalEvent::Utf16Surrogate(0xD83D, KeyCode::Null.into())),
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_bracketed_paste() {
+        assert_eq!(parse_event(b"\x1B[200~on", true).unwrap(), None);
+        assert_eq!(
+            parse_event(b"\x1B[200~o\x1B[2D\x1B[201~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Paste("o\x1B[2D".to_string())))
+        );
+        assert_eq!(
+            parse_event(b"\x1B[200~\x1B[201~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Paste(String::new())))
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_u_encoded_key_code() {
+        assert_eq!(
+            parse_event(b"\x1B[27u", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Esc.into()))),
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[105;5u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('i'),
+                KeyModifiers::CONTROL
+            )))),
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[9u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Tab.into()))),
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[97:65;2u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new(
+                KeyCode::Char('A'),
+                KeyModifiers::SHIFT
+            )))),
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[57399u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyCode::Char('0').into()))),
+        );
+        // Left Shift has no `KeyCode`
+        assert!(parse_csi_u_encoded_key_code(b"\x1B[57441u").is_err());
+    }
+
+    #[test]
+    fn test_parse_key_event_kinds() {
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[97;1:2u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new_with_kind(
+                KeyCode::Char('a'),
+                KeyModifiers::empty(),
+                KeyEventKind::Repeat
+            )))),
+        );
+        assert_eq!(
+            parse_csi_u_encoded_key_code(b"\x1B[97;5:3u").unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new_with_kind(
+                KeyCode::Char('a'),
+                KeyModifiers::CONTROL,
+                KeyEventKind::Release
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[1;1:3A", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new_with_kind(
+                KeyCode::Up,
+                KeyModifiers::empty(),
+                KeyEventKind::Release
+            )))),
+        );
+        assert_eq!(
+            parse_event(b"\x1B[3;1:2~", false).unwrap(),
+            Some(InternalEvent::Event(Event::Key(KeyEvent::new_with_kind(
+                KeyCode::Delete,
+                KeyModifiers::empty(),
+                KeyEventKind::Repeat
+            )))),
+        );
+    }
+
     #[test]
     fn test_parse_csi_special_key_code() {
         assert_eq!(
@@ -714,6 +1517,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_csi_sgr_mouse_scroll_and_drag() {
+        assert_eq!(
+            parse_csi_sgr_mouse(b"\x1B[<64;5;3M").unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::ScrollUp,
+                column: 4,
+                row: 2,
+                modifiers: KeyModifiers::empty(),
+            })))
+        );
+        assert_eq!(
+            parse_csi_sgr_mouse(b"\x1B[<65;5;3M").unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::ScrollDown,
+                column: 4,
+                row: 2,
+                modifiers: KeyModifiers::empty(),
+            })))
+        );
+        assert_eq!(
+            parse_csi_sgr_mouse(b"\x1B[<34;300;200M").unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Drag(MouseButton::Right),
+                column: 299,
+                row: 199,
+                modifiers: KeyModifiers::empty(),
+            })))
+        );
+        assert_eq!(
+            parse_csi_sgr_mouse(b"\x1B[<35;1;1M").unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Moved,
+                column: 0,
+                row: 0,
+                modifiers: KeyModifiers::empty(),
+            })))
+        );
+        assert_eq!(
+            parse_csi_sgr_mouse(b"\x1B[<22;2;2m").unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Up(MouseButton::Right),
+                column: 1,
+                row: 1,
+                modifiers: KeyModifiers::SHIFT | KeyModifiers::CONTROL,
+            })))
+        );
+    }
+
+    #[test]
+    fn test_parse_csi_sgr_mouse_zero_coordinates() {
+        // Some terminals report 0 for coordinates outside of the window
+        assert_eq!(
+            parse_csi_sgr_mouse(b"\x1B[<0;0;0M").unwrap(),
+            Some(InternalEvent::Event(Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column: 0,
+                row: 0,
+                modifiers: KeyModifiers::empty(),
+            })))
+        );
+    }
+
     #[test]
     fn test_utf8() {
         // https://www.php.net/manual/en/reference.pcre.pattern.modifiers.php#54805
@@ -33,7 +33,7 @@ impl Waker {
     /// Resets the state so the same waker can be reused.
     ///
     /// This function is not impl
-    #[allow(dead_code, clippy::clippy::unnecessary_wraps)]
+    #[allow(dead_code, clippy::unnecessary_wraps)]
     pub(crate) fn reset(&self) -> Result<()> {
         Ok(())
     }
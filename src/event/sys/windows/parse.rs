@@ -10,7 +10,10 @@ use winapi::um::{
 };
 
 use crate::{
-    event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind},
+    event::{
+        report_raw_key_codes, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind,
+        RawKeyCodes,
+    },
     Result,
 };
 
@@ -22,9 +25,12 @@ pub(crate) fn handle_mouse_event(mouse_event: MouseEvent) -> Option<Event> {
     None
 }
 
-pub(crate) fn handle_key_event(key_event: KeyEventRecord) -> Option<Event> {
+pub(crate) fn handle_key_event(
+    key_event: KeyEventRecord,
+    surrogate_buffer: &mut Option<u16>,
+) -> Option<Event> {
     if key_event.key_down {
-        if let Some(event) = parse_key_event_record(&key_event) {
+        if let Some(event) = parse_key_event_record(&key_event, surrogate_buffer) {
             return Some(Event::Key(event));
         }
     }
@@ -54,7 +60,10 @@ impl From<ControlKeyState> for KeyModifiers {
     }
 }
 
-fn parse_key_event_record(key_event: &KeyEventRecord) -> Option<KeyEvent> {
+fn parse_key_event_record(
+    key_event: &KeyEventRecord,
+    surrogate_buffer: &mut Option<u16>,
+) -> Option<KeyEvent> {
     let modifiers = KeyModifiers::from(key_event.control_key_state);
 
     let key_code = key_event.virtual_key_code as i32;
@@ -92,8 +101,8 @@ fn parse_key_event_record(key_event: &KeyEventRecord) -> Option<KeyEvent> {
                 {
                     // we need to do some parsing
                     character = match character_raw as u8 {
-                        c @ b'\x01'..=b'\x1A' => (c as u8 - 0x1 + b'a') as char,
-                        c @ b'\x1C'..=b'\x1F' => (c as u8 - 0x1C + b'4') as char,
+                        c @ b'\x01'..=b'\x1A' => (c - 0x1 + b'a') as char,
+                        c @ b'\x1C'..=b'\x1F' => (c - 0x1C + b'4') as char,
                         _ => return None,
                     }
                 }
@@ -106,16 +115,38 @@ fn parse_key_event_record(key_event: &KeyEventRecord) -> Option<KeyEvent> {
                     Some(KeyCode::Char(character))
                 }
             } else {
-                match std::char::from_u32(character_raw as u32) {
-                    Some(ch) => Some(KeyCode::Char(ch)),
-                    None => None,
+                // Characters outside of the Basic Multilingual Plane (e.g. emoji) arrive as two
+                // records, each one holding a half of the UTF-16 surrogate pair.
+                match character_raw {
+                    0xD800..=0xDBFF => {
+                        *surrogate_buffer = Some(character_raw);
+                        return None;
+                    }
+                    0xDC00..=0xDFFF => {
+                        let high_surrogate = surrogate_buffer.take()?;
+                        std::char::decode_utf16([high_surrogate, character_raw].iter().copied())
+                            .next()?
+                            .ok()
+                            .map(KeyCode::Char)
+                    }
+                    _ => {
+                        *surrogate_buffer = None;
+                        std::char::from_u32(character_raw as u32).map(KeyCode::Char)
+                    }
                 }
             }
         }
     };
 
     if let Some(key_code) = parse_result {
-        return Some(KeyEvent::new(key_code, modifiers));
+        let mut event = KeyEvent::new(key_code, modifiers);
+        if report_raw_key_codes() {
+            event.raw_codes = Some(RawKeyCodes {
+                virtual_key_code: key_event.virtual_key_code,
+                scan_code: key_event.virtual_scan_code,
+            });
+        }
+        return Some(event);
     }
 
     None
@@ -144,7 +175,9 @@ fn parse_mouse_event_record(event: &MouseEvent) -> Result<Option<crate::event::M
     };
 
     let kind = match event.event_flags {
-        EventFlags::PressOrRelease => {
+        // The console reports the second press of a double click with its own flag, it's
+        // still a button press.
+        EventFlags::PressOrRelease | EventFlags::DoubleClick => {
             if button_state.release_button() {
                 // in order to read the up button type, we have to check the last down input record.
                 Some(MouseEventKind::Up(MouseButton::Left))
@@ -171,7 +204,6 @@ fn parse_mouse_event_record(event: &MouseEvent) -> Result<Option<crate::event::M
                 None
             }
         }
-        EventFlags::DoubleClick => None, // double click not supported by unix terminals
         EventFlags::MouseHwheeled => None, // horizontal scroll not supported by unix terminals
     };
 
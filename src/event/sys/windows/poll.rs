@@ -75,11 +75,7 @@ impl WinApiPoll {
                 Ok(None)
             }
             WAIT_FAILED => Err(io::Error::last_os_error().into()),
-            _ => Err(io::Error::new(
-                io::ErrorKind::Other,
-                "WaitForMultipleObjects returned unexpected result.",
-            )
-            .into()),
+            _ => Err(io::Error::other("WaitForMultipleObjects returned unexpected result.").into()),
         }
     }
 
@@ -41,32 +41,76 @@
 //! - Module [`cursor`](cursor/index.html)
 //!   - Visibility - [`Show`](cursor/struct.Show.html), [`Hide`](cursor/struct.Hide.html)
 //!   - Appearance - [`EnableBlinking`](cursor/struct.EnableBlinking.html),
-//!     [`DisableBlinking`](cursor/struct.DisableBlinking.html)
+//!     [`DisableBlinking`](cursor/struct.DisableBlinking.html),
+//!     [`SetCursorShape`](cursor/struct.SetCursorShape.html),
+//!     [`SetCursorStyle`](cursor/struct.SetCursorStyle.html),
+//!     [`ResetCursorStyle`](cursor/struct.ResetCursorStyle.html),
+//!     [`SetCursorSize`](cursor/struct.SetCursorSize.html),
+//!     [`SetCursorColor`](cursor/struct.SetCursorColor.html),
+//!     [`ResetCursorColor`](cursor/struct.ResetCursorColor.html)
 //!   - Position -
 //!     [`SavePosition`](cursor/struct.SavePosition.html), [`RestorePosition`](cursor/struct.RestorePosition.html),
 //!     [`MoveUp`](cursor/struct.MoveUp.html), [`MoveDown`](cursor/struct.MoveDown.html),
 //!     [`MoveLeft`](cursor/struct.MoveLeft.html), [`MoveRight`](cursor/struct.MoveRight.html),
 //!     [`MoveTo`](cursor/struct.MoveTo.html), [`MoveToColumn`](cursor/struct.MoveToColumn.html),[`MoveToRow`](cursor/struct.MoveToRow.html),
 //!     [`MoveToNextLine`](cursor/struct.MoveToNextLine.html), [`MoveToPreviousLine`](cursor/struct.MoveToPreviousLine.html),
+//!     [`MoveToNextTab`](cursor/struct.MoveToNextTab.html), [`MoveToPreviousTab`](cursor/struct.MoveToPreviousTab.html)
 //! - Module [`event`](event/index.html)
 //!   - Mouse events - [`EnableMouseCapture`](event/struct.EnableMouseCapture.html),
 //!     [`DisableMouseCapture`](event/struct.DisableMouseCapture.html)
+//!   - Paste events - [`EnableBracketedPaste`](event/struct.EnableBracketedPaste.html),
+//!     [`DisableBracketedPaste`](event/struct.DisableBracketedPaste.html)
+//!   - Focus events - [`EnableFocusChange`](event/struct.EnableFocusChange.html),
+//!     [`DisableFocusChange`](event/struct.DisableFocusChange.html)
+//!   - Keyboard protocols -
+//!     [`PushKeyboardEnhancementFlags`](event/struct.PushKeyboardEnhancementFlags.html),
+//!     [`PopKeyboardEnhancementFlags`](event/struct.PopKeyboardEnhancementFlags.html),
+//!     [`EnableWin32InputMode`](event/struct.EnableWin32InputMode.html),
+//!     [`DisableWin32InputMode`](event/struct.DisableWin32InputMode.html)
 //! - Module [`style`](style/index.html)
 //!   - Colors - [`SetForegroundColor`](style/struct.SetForegroundColor.html),
 //!     [`SetBackgroundColor`](style/struct.SetBackgroundColor.html),
-//!     [`ResetColor`](style/struct.ResetColor.html), [`SetColors`](style/struct.SetColors.html)
+//!     [`ResetColor`](style/struct.ResetColor.html), [`SetColors`](style/struct.SetColors.html),
+//!     [`SetUnderlineColor`](style/struct.SetUnderlineColor.html)
 //!   - Attributes - [`SetAttribute`](style/struct.SetAttribute.html), [`SetAttributes`](style/struct.SetAttributes.html),
-//!     [`PrintStyledContent`](style/struct.PrintStyledContent.html)
+//!     [`PrintStyledContent`](style/struct.PrintStyledContent.html),
+//!     [`SetStyle`](style/struct.SetStyle.html), [`UpdateStyle`](style/struct.UpdateStyle.html)
+//!   - Hyperlinks - [`PrintHyperlink`](style/struct.PrintHyperlink.html)
+//!   - Style stack - [`PushStyle`](style/struct.PushStyle.html), [`PopStyle`](style/struct.PopStyle.html)
+//!   - Palette - [`SetPaletteColor`](style/struct.SetPaletteColor.html),
+//!     [`ResetPalette`](style/struct.ResetPalette.html)
 //! - Module [`terminal`](terminal/index.html)
 //!   - Scrolling - [`ScrollUp`](terminal/struct.ScrollUp.html),
-//!     [`ScrollDown`](terminal/struct.ScrollDown.html)
+//!     [`ScrollDown`](terminal/struct.ScrollDown.html),
+//!     [`SetScrollRegion`](terminal/struct.SetScrollRegion.html),
+//!     [`ResetScrollRegion`](terminal/struct.ResetScrollRegion.html)
+//!   - Editing - [`InsertLines`](terminal/struct.InsertLines.html),
+//!     [`DeleteLines`](terminal/struct.DeleteLines.html),
+//!     [`InsertChars`](terminal/struct.InsertChars.html),
+//!     [`DeleteChars`](terminal/struct.DeleteChars.html)
+//!   - Tab stops - [`SetTabStop`](terminal/struct.SetTabStop.html),
+//!     [`ClearTabStop`](terminal/struct.ClearTabStop.html),
+//!     [`ClearAllTabStops`](terminal/struct.ClearAllTabStops.html)
 //!   - Miscellaneous - [`Clear`](terminal/struct.Clear.html),
 //!     [`SetSize`](terminal/struct.SetSize.html)
 //!     [`SetTitle`](terminal/struct.SetTitle.html)
+//!     [`Bell`](terminal/struct.Bell.html)
+//!     [`SoftReset`](terminal/struct.SoftReset.html)
+//!     [`HardReset`](terminal/struct.HardReset.html)
+//!     [`SetWorkingDirectory`](terminal/struct.SetWorkingDirectory.html)
+//!     [`Notify`](terminal/struct.Notify.html)
+//!     [`CopyToClipboard`](terminal/struct.CopyToClipboard.html)
 //!     [`DisableLineWrap`](terminal/struct.DisableLineWrap.html)
 //!     [`EnableLineWrap`](terminal/struct.EnableLineWrap.html)
+//!   - Window - [`MinimizeWindow`](terminal/struct.MinimizeWindow.html),
+//!     [`MaximizeWindow`](terminal/struct.MaximizeWindow.html),
+//!     [`RestoreWindow`](terminal/struct.RestoreWindow.html),
+//!     [`RaiseWindow`](terminal/struct.RaiseWindow.html),
+//!     [`MoveWindow`](terminal/struct.MoveWindow.html)
 //!   - Alternate screen - [`EnterAlternateScreen`](terminal/struct.EnterAlternateScreen.html),
 //!     [`LeaveAlternateScreen`](terminal/struct.LeaveAlternateScreen.html)
+//!   - Synchronized output - [`BeginSynchronizedUpdate`](terminal/struct.BeginSynchronizedUpdate.html),
+//!     [`EndSynchronizedUpdate`](terminal/struct.EndSynchronizedUpdate.html)
 //!
 //! ### Command Execution
 //!
@@ -165,6 +209,38 @@
 //! `execute!(stdout, MoveTo(5, 5), Clear(ClearType::All))` and they will be executed in the given order from
 //! left to right.
 //!
+//! ### Output Targets
+//!
+//! The commands are not bound to the standard output, they can be queued and executed on any type
+//! implementing [std::io::Write][write], e.g. a file, a pipe, a network stream or an in-memory
+//! buffer. This allows to render to a remote terminal or to check the emitted escape codes in
+//! tests:
+//!
+//! ```
+//! use crossterm::{cursor, queue, set_plain_output, style::Print, Result};
+//!
+//! fn main() -> Result<()> {
+//!     // The escape codes are dropped by default if the standard output is not a terminal
+//!     set_plain_output(false);
+//!
+//!     let mut buffer = Vec::new();
+//!     queue!(buffer, cursor::MoveTo(1, 2), Print("text"))?;
+//!
+//!     # #[cfg(unix)]
+//!     assert_eq!(buffer, b"\x1B[3;2Htext");
+//!     Ok(())
+//! }
+//! ```
+//!
+//! On Windows versions lower than 10 the commands don't have an ANSI representation, they're
+//! executed on the current console with WinAPI calls regardless of the writer.
+//!
+//! The [TestBackend](struct.TestBackend.html) captures the written bytes and offers helpers for
+//! such assertions.
+//!
+//! When the standard output is not a terminal (e.g. `mytool | less`), the escape codes are dropped
+//! and only the printed text is written, see [set_plain_output](fn.set_plain_output.html).
+//!
 //! ## Examples
 //!
 //! Print a rectangle colored with magenta and use both direct execution and lazy execution.
@@ -225,14 +301,43 @@
 //! }
 //!```
 //!
+//! ## Threads
+//!
+//! Crossterm doesn't keep a per-handle context, the commands are written to the writer you pass
+//! and the shared state (e.g. the raw mode or the event reader) is guarded by the crate. The
+//! commands, styles, events and errors are `Send` and `Sync`, so a render thread and an input
+//! thread can work with the same terminal:
+//!
+//! ```no_run
+//! use std::{io::stdout, sync::mpsc, thread};
+//!
+//! use crossterm::{event, execute, style::Print, Result};
+//!
+//! fn main() -> Result<()> {
+//!     let (sender, receiver) = mpsc::channel();
+//!
+//!     thread::spawn(move || -> Result<()> {
+//!         loop {
+//!             sender.send(event::read()?).ok();
+//!         }
+//!     });
+//!
+//!     for event in receiver {
+//!         execute!(stdout(), Print(format!("{:?}\r\n", event)))?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
 //! [write]: https://doc.rust-lang.org/std/io/trait.Write.html
 //! [stdout]: https://doc.rust-lang.org/std/io/fn.stdout.html
 //! [stderr]: https://doc.rust-lang.org/std/io/fn.stderr.html
 //! [flush]: https://doc.rust-lang.org/std/io/trait.Write.html#tymethod.flush
 
 pub use crate::{
-    command::{Command, ExecutableCommand, QueueableCommand},
+    command::{is_plain_output, set_plain_output, Command, ExecutableCommand, QueueableCommand},
     error::{ErrorKind, Result},
+    test_backend::TestBackend,
 };
 
 /// A module to work with the terminal cursor
@@ -253,3 +358,35 @@ pub mod ansi_support;
 mod command;
 mod error;
 pub(crate) mod macros;
+mod test_backend;
+
+#[cfg(test)]
+mod tests {
+    use crate::{cursor, event, style, terminal, ErrorKind};
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_public_types_are_send_and_sync() {
+        assert_send_sync::<ErrorKind>();
+
+        assert_send_sync::<cursor::MoveTo>();
+        assert_send_sync::<cursor::SetCursorStyle>();
+
+        assert_send_sync::<event::Event>();
+        assert_send_sync::<event::KeyCombination>();
+        assert_send_sync::<event::MouseTracker>();
+        assert_send_sync::<event::Recording>();
+        assert_send_sync::<event::Recorder>();
+        #[cfg(feature = "event-stream")]
+        assert_send_sync::<event::EventStream>();
+
+        assert_send_sync::<style::ContentStyle>();
+        assert_send_sync::<style::StyledContent<String>>();
+        assert_send_sync::<style::PrintStyledContent<String>>();
+        assert_send_sync::<style::Theme>();
+
+        assert_send_sync::<terminal::Capabilities>();
+        assert_send_sync::<terminal::SetTitle<String>>();
+    }
+}
@@ -105,23 +105,73 @@ macro_rules! queue {
 ///
 /// * In the case of UNIX and Windows 10, ANSI codes are written to the given 'writer'.
 /// * In case of Windows versions lower than 10, a direct WinAPI call will be made.
-///     The reason for this is that Windows versions lower than 10 do not support ANSI codes,
-///     and can therefore not be written to the given `writer`.
-///     Therefore, there is no difference between [execute](macro.execute.html)
-///     and [queue](macro.queue.html) for those old Windows versions.
+///   The reason for this is that Windows versions lower than 10 do not support ANSI codes,
+///   and can therefore not be written to the given `writer`.
+///   Therefore, there is no difference between [execute](macro.execute.html)
+///   and [queue](macro.queue.html) for those old Windows versions.
 #[macro_export]
 macro_rules! execute {
     ($writer:expr $(, $command:expr)* $(,)? ) => {{
         use ::std::io::Write;
 
-        // Queue each command, then flush
-        $crate::queue!($writer $(, $command)*)
-            .and_then(|()| {
-                ::std::io::Write::flush($writer.by_ref()).map_err($crate::ErrorKind::IoError)
-            })
+        // Evaluate the writer once, the commands must be flushed on the writer they were
+        // queued on.
+        match $writer.by_ref() {
+            writer => {
+                // Queue each command, then flush
+                $crate::queue!(writer $(, $command)*)
+                    .and_then(|()| {
+                        ::std::io::Write::flush(writer).map_err($crate::ErrorKind::IoError)
+                    })
+            }
+        }
     }}
 }
 
+/// Creates a [StyledContent](style/struct.StyledContent.html) from `format!` arguments.
+///
+/// The format string and its arguments can be followed by a `;` and a list of style specifiers:
+///
+/// - `fg = Color` sets the foreground color.
+/// - `bg = Color` sets the background color.
+/// - `attr = Attribute` adds an attribute, can be used multiple times.
+///
+/// # Examples
+///
+/// ```rust
+/// use crossterm::styled;
+/// use crossterm::style::{Attribute, Color, Styler};
+///
+/// let (key, value) = ("name", "crossterm");
+///
+/// println!("{}", styled!("{}: {}", key.bold(), value; fg = Color::Green));
+/// println!("{}", styled!("plain {}", value));
+/// println!(
+///     "{}",
+///     styled!("{}", value; fg = Color::White, bg = Color::Blue, attr = Attribute::Italic)
+/// );
+/// ```
+#[macro_export]
+macro_rules! styled {
+    (@style $styled:expr, fg = $color:expr) => {
+        $styled.with($color)
+    };
+    (@style $styled:expr, bg = $color:expr) => {
+        $styled.on($color)
+    };
+    (@style $styled:expr, attr = $attribute:expr) => {
+        $styled.attribute($attribute)
+    };
+    ($fmt:literal $(, $arg:expr)* $(,)? ; $($key:ident = $value:expr),* $(,)?) => {{
+        let styled = $crate::style::style(format!($fmt $(, $arg)*));
+        $(let styled = $crate::styled!(@style styled, $key = $value);)*
+        styled
+    }};
+    ($fmt:literal $(, $arg:expr)* $(,)?) => {
+        $crate::style::style(format!($fmt $(, $arg)*))
+    };
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! impl_display {
@@ -153,7 +203,7 @@ mod tests {
 
     // Helper for execute tests to confirm flush
     #[derive(Default, Debug, Clone)]
-    pub(self) struct FakeWrite {
+    struct FakeWrite {
         buffer: String,
         flushed: bool,
     }
@@ -235,6 +285,49 @@ mod tests {
             assert_eq!(&result.buffer, "cmdcmd");
             assert!(result.flushed);
         }
+
+        #[test]
+        fn test_execute_evaluates_writer_once() {
+            let mut writers = [FakeWrite::default(), FakeWrite::default()];
+            let mut evaluations = 0;
+
+            execute!(
+                {
+                    evaluations += 1;
+                    &mut writers[0]
+                },
+                FakeCommand
+            )
+            .unwrap();
+
+            assert_eq!(evaluations, 1);
+            assert_eq!(&writers[0].buffer, "cmd");
+            assert!(writers[0].flushed);
+            assert!(!writers[1].flushed);
+        }
+
+        #[test]
+        fn test_styled_without_style() {
+            let styled = styled!("{} + {} = {}", 1, 1, 1 + 1);
+            assert_eq!(styled.content(), "1 + 1 = 2");
+            assert_eq!(styled.to_string(), "1 + 1 = 2");
+        }
+
+        #[test]
+        fn test_styled_with_style() {
+            use crate::style::{Attribute, Color};
+
+            let styled = styled!(
+                "{}: {}", "key", "value";
+                fg = Color::Green, bg = Color::Black, attr = Attribute::Bold, attr = Attribute::Italic,
+            );
+
+            assert_eq!(styled.content(), "key: value");
+            assert_eq!(styled.style().foreground_color, Some(Color::Green));
+            assert_eq!(styled.style().background_color, Some(Color::Black));
+            assert!(styled.style().attributes.has(Attribute::Bold));
+            assert!(styled.style().attributes.has(Attribute::Italic));
+        }
     }
 
     #[cfg(windows)]
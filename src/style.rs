@@ -15,6 +15,11 @@
 //! * [Color](enum.Color.html#platform-specific-notes)
 //! * [Attribute](enum.Attribute.html#platform-specific-notes)
 //!
+//! ## Disabling Colors
+//!
+//! The `NO_COLOR`, `CLICOLOR` and `CLICOLOR_FORCE` environment variables are honored by default.
+//! Use [`set_color_choice`](fn.set_color_choice.html) to override them.
+//!
 //! ## Examples
 //!
 //! A few examples of how to use the style module.
@@ -113,24 +118,31 @@
 use std::{
     env,
     fmt::{self, Display},
+    sync::atomic::{AtomicU8, Ordering},
 };
 
 #[cfg(windows)]
 use crate::Result;
-use crate::{csi, impl_display, Command};
+use parking_lot::Mutex;
+
+use crate::{command::is_plain_output, csi, impl_display, Command};
+
+pub use sys::{default_background_color, default_foreground_color};
 
 pub use self::{
     attributes::Attributes,
     content_style::ContentStyle,
+    parse::{parse_ansi, strip_ansi},
     styled_content::StyledContent,
     traits::{Colorize, Styler},
-    types::{Attribute, Color, Colored, Colors},
+    types::{Attribute, Color, ColorChoice, ColorSupport, Colored, Colors, ParseColorError, Theme},
 };
 
 #[macro_use]
 mod macros;
 mod attributes;
 mod content_style;
+mod parse;
 mod styled_content;
 mod sys;
 mod traits;
@@ -157,6 +169,54 @@ pub fn style<D: Display>(val: D) -> StyledContent<D> {
     ContentStyle::new().apply(val)
 }
 
+/// Colors the text with a foreground color ramp from the first to the second color.
+///
+/// Every character gets its own color, interpolated with [`Color::lerp`](enum.Color.html#method.lerp).
+/// Consecutive characters with the same color are combined into a single fragment, so that the
+/// fragments can be printed with as few escape codes as possible. The text is returned unstyled
+/// if one of the colors is `Color::Reset`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{queue, Result};
+/// use crossterm::style::{gradient, Color, PrintStyledContent};
+///
+/// fn main() -> Result<()> {
+///     let mut stdout = stdout();
+///
+///     let from = Color::Rgb { r: 255, g: 0, b: 128 };
+///     let to = Color::Rgb { r: 0, g: 128, b: 255 };
+///
+///     for fragment in gradient("Loading...", from, to) {
+///         queue!(stdout, PrintStyledContent(fragment))?;
+///     }
+///
+///     stdout.flush()?;
+///     Ok(())
+/// }
+/// ```
+pub fn gradient(text: &str, from: Color, to: Color) -> Vec<StyledContent<String>> {
+    let count = text.chars().count();
+    let colors = match from.lerp_steps(to, count) {
+        Some(colors) => colors,
+        None => return vec![style(text.to_string())],
+    };
+
+    let mut fragments: Vec<StyledContent<String>> = Vec::new();
+    for (c, color) in text.chars().zip(colors) {
+        match fragments.last_mut() {
+            Some(fragment) if fragment.style().foreground_color == Some(color) => {
+                fragment.content_mut().push(c)
+            }
+            _ => fragments.push(style(c.to_string()).with(color)),
+        }
+    }
+    fragments
+}
+
 impl_colorize!(String);
 impl_colorize!(char);
 
@@ -179,11 +239,246 @@ impl<'a> Styler<&'a str> for &'a str {
 ///
 /// # Notes
 ///
-/// This does not always provide a good result.
+/// This does not always provide a good result. Terminals supporting 24-bit colors are
+/// reported as 256, use [`color_support`](fn.color_support.html) to detect them.
 pub fn available_color_count() -> u16 {
-    env::var("TERM")
-        .map(|x| if x.contains("256color") { 256 } else { 8 })
-        .unwrap_or(8)
+    match color_support() {
+        ColorSupport::Ansi16 => 8,
+        ColorSupport::Ansi256 | ColorSupport::TrueColor => 256,
+    }
+}
+
+/// Returns the color depth supported by the terminal.
+///
+/// The detection is done in the following order:
+///
+/// * `COLORTERM` set to `truecolor` or `24bit` means `TrueColor`.
+/// * UNIX: `TERM` ending with `-direct` means `TrueColor` and `TERM` containing `256color` means
+///   `Ansi256`, otherwise the terminfo `colors` capability is consulted (via `tput`).
+/// * Windows: consoles supporting ANSI escape codes (Windows 10) are `TrueColor`, the legacy
+///   console is `Ansi16`.
+///
+/// Falls back to `Ansi16` when nothing could be detected. The detection is done once, the result
+/// is reused by subsequent calls. Use [`set_color_support`](fn.set_color_support.html) to override
+/// it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::style::{color_support, Color, ColorSupport, SetForegroundColor};
+///
+/// let color = if color_support() == ColorSupport::TrueColor {
+///     Color::Rgb { r: 255, g: 136, b: 0 }
+/// } else {
+///     Color::Yellow
+/// };
+///
+/// println!("{}orange?", SetForegroundColor(color));
+/// ```
+pub fn color_support() -> ColorSupport {
+    match COLOR_SUPPORT.load(Ordering::Relaxed) {
+        0 => ColorSupport::Ansi16,
+        1 => ColorSupport::Ansi256,
+        2 => ColorSupport::TrueColor,
+        _ => {
+            let support = detect_color_support();
+            COLOR_SUPPORT.store(support as u8, Ordering::Relaxed);
+            support
+        }
+    }
+}
+
+/// Overrides the detected color depth of the terminal, e.g. if the user configured it.
+///
+/// See [`color_support`](fn.color_support.html) for more info.
+pub fn set_color_support(support: ColorSupport) {
+    COLOR_SUPPORT.store(support as u8, Ordering::Relaxed);
+}
+
+fn detect_color_support() -> ColorSupport {
+    if let Ok(colorterm) = env::var("COLORTERM") {
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorSupport::TrueColor;
+        }
+    }
+
+    sys::color_support()
+}
+
+// The detected `ColorSupport` (`u8::MAX` = not detected yet).
+static COLOR_SUPPORT: AtomicU8 = AtomicU8::new(u8::MAX);
+// Whether the terminal supports the underline color & styles (`u8::MAX` = not detected yet).
+static UNDERLINE_STYLES_SUPPORT: AtomicU8 = AtomicU8::new(u8::MAX);
+// Whether the terminal supports hyperlinks (`u8::MAX` = not detected yet).
+static HYPERLINKS_SUPPORT: AtomicU8 = AtomicU8::new(u8::MAX);
+// The `ColorChoice` set by the user (`COLOR_CHOICE_*`).
+static COLOR_CHOICE: AtomicU8 = AtomicU8::new(COLOR_CHOICE_AUTO);
+// Whether colors are enabled by the environment, resolved on first use (`u8::MAX` = unknown).
+static ENV_COLORS_ENABLED: AtomicU8 = AtomicU8::new(u8::MAX);
+
+const COLOR_CHOICE_AUTO: u8 = 0;
+const COLOR_CHOICE_ALWAYS: u8 = 1;
+const COLOR_CHOICE_NEVER: u8 = 2;
+
+/// Sets whether the color commands should emit colors.
+///
+/// By default (`ColorChoice::Auto`) the `NO_COLOR`, `CLICOLOR` and `CLICOLOR_FORCE` conventions
+/// are honored:
+///
+/// * `CLICOLOR_FORCE` set to anything else than `0` forces colors.
+/// * `NO_COLOR` set to any non-empty value disables colors.
+/// * `CLICOLOR` set to `0` disables colors.
+///
+/// If the standard output is not a terminal, all escape codes (colors included) are dropped unless
+/// `CLICOLOR_FORCE` or `ColorChoice::Always` force them, see
+/// [`set_plain_output`](../fn.set_plain_output.html).
+///
+/// When colors are disabled, [`SetForegroundColor`](struct.SetForegroundColor.html),
+/// [`SetBackgroundColor`](struct.SetBackgroundColor.html) and
+/// [`SetColors`](struct.SetColors.html) (and therefore styled content) do nothing. Attributes are
+/// not affected.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::style::{set_color_choice, ColorChoice};
+///
+/// // e.g. for a `--color=never` command line flag
+/// set_color_choice(ColorChoice::Never);
+/// ```
+pub fn set_color_choice(choice: ColorChoice) {
+    let value = match choice {
+        ColorChoice::Auto => COLOR_CHOICE_AUTO,
+        ColorChoice::Always => COLOR_CHOICE_ALWAYS,
+        ColorChoice::Never => COLOR_CHOICE_NEVER,
+    };
+    COLOR_CHOICE.store(value, Ordering::Relaxed);
+}
+
+/// Returns the current [`ColorChoice`](enum.ColorChoice.html).
+pub fn color_choice() -> ColorChoice {
+    match COLOR_CHOICE.load(Ordering::Relaxed) {
+        COLOR_CHOICE_ALWAYS => ColorChoice::Always,
+        COLOR_CHOICE_NEVER => ColorChoice::Never,
+        _ => ColorChoice::Auto,
+    }
+}
+
+/// Returns whether the color commands should emit colors.
+pub(crate) fn colors_enabled() -> bool {
+    match color_choice() {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => match ENV_COLORS_ENABLED.load(Ordering::Relaxed) {
+            u8::MAX => {
+                let var = |name| env::var(name).ok();
+                let enabled = ColorChoice::resolve_env(
+                    var("NO_COLOR").as_deref(),
+                    var("CLICOLOR").as_deref(),
+                    var("CLICOLOR_FORCE").as_deref(),
+                );
+                ENV_COLORS_ENABLED.store(enabled as u8, Ordering::Relaxed);
+                enabled
+            }
+            enabled => enabled != 0,
+        },
+    }
+}
+
+/// Returns whether the terminal supports underline colors and the extended underline styles.
+///
+/// See [`SetUnderlineColor`](struct.SetUnderlineColor.html) and the `DoubleUnderlined`,
+/// `Undercurled`, `Underdotted` and `Underdashed` [attributes](enum.Attribute.html).
+///
+/// The known terminals (kitty, foot, Alacritty, WezTerm, VTE based, Windows Terminal, ...) and the
+/// terminfo `Smulx` capability are checked. The detection is done once, the result is reused by
+/// subsequent calls.
+pub fn supports_underline_styles() -> bool {
+    match UNDERLINE_STYLES_SUPPORT.load(Ordering::Relaxed) {
+        u8::MAX => {
+            let supported = sys::supports_underline_styles();
+            UNDERLINE_STYLES_SUPPORT.store(supported as u8, Ordering::Relaxed);
+            supported
+        }
+        supported => supported != 0,
+    }
+}
+
+/// Returns whether the terminal supports hyperlinks.
+///
+/// See [`PrintHyperlink`](struct.PrintHyperlink.html).
+///
+/// The known terminals (kitty, foot, Alacritty, WezTerm, iTerm2, VTE based, Windows Terminal, ...)
+/// are checked, setting the `FORCE_HYPERLINK` environment variable to anything else than `0`
+/// forces the support. The detection is done once, the result is reused by subsequent calls.
+pub fn supports_hyperlinks() -> bool {
+    match HYPERLINKS_SUPPORT.load(Ordering::Relaxed) {
+        u8::MAX => {
+            let supported = match env::var("FORCE_HYPERLINK") {
+                Ok(force) if !force.is_empty() => force != "0",
+                _ => sys::supports_hyperlinks(),
+            };
+            HYPERLINKS_SUPPORT.store(supported as u8, Ordering::Relaxed);
+            supported
+        }
+        supported => supported != 0,
+    }
+}
+
+// The process-wide theme set with `set_theme` (`None` = the standard theme).
+static THEME: Mutex<Option<Theme>> = parking_lot::const_mutex(None);
+
+/// Sets the process-wide [`Theme`](struct.Theme.html).
+///
+/// Until a theme is set, [`Theme::standard`](struct.Theme.html#method.standard) is used.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::style::{set_theme, style, theme_color, Color, Theme};
+///
+/// set_theme(Theme::standard().with("error", Color::Magenta));
+///
+/// let error_color = theme_color("error").unwrap_or(Color::Reset);
+/// println!("{}", style("error: file not found").with(error_color));
+/// ```
+pub fn set_theme(theme: Theme) {
+    *THEME.lock() = Some(theme);
+}
+
+/// Returns a copy of the process-wide [`Theme`](struct.Theme.html).
+pub fn theme() -> Theme {
+    THEME.lock().clone().unwrap_or_else(Theme::standard)
+}
+
+/// Returns the color of the given name in the process-wide [`Theme`](struct.Theme.html).
+pub fn theme_color(name: &str) -> Option<Color> {
+    match &*THEME.lock() {
+        Some(theme) => theme.get(name),
+        None => Theme::standard().get(name),
+    }
+}
+
+/// The SGR parameter of a color, downgraded to the colors supported by the terminal.
+///
+/// Terminals supporting only 16 colors get the 4-bit codes of the base colors (e.g. `91`), as
+/// they may not understand the 256 colors codes (e.g. `38;5;9`).
+struct ColorParam(Colored);
+
+impl Display for ColorParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let support = color_support();
+        let colored = match self.0 {
+            Colored::ForegroundColor(color) => Colored::ForegroundColor(color.downgrade(support)),
+            Colored::BackgroundColor(color) => Colored::BackgroundColor(color.downgrade(support)),
+            Colored::UnderlineColor(color) => Colored::UnderlineColor(color.downgrade(support)),
+        };
+
+        match colored.ansi16_code() {
+            Some(code) if support == ColorSupport::Ansi16 => write!(f, "{}", code),
+            _ => write!(f, "{}", colored),
+        }
+    }
 }
 
 /// A command that sets the the foreground color.
@@ -195,19 +490,31 @@ pub fn available_color_count() -> u16 {
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * Colors the terminal can't display are downgraded to the closest supported color, see
+///   [`color_support`](fn.color_support.html) and [`Color::downgrade`](enum.Color.html#method.downgrade).
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SetForegroundColor(pub Color);
 
 impl Command for SetForegroundColor {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        write!(f, csi!("{}m"), Colored::ForegroundColor(self.0))
+        if !colors_enabled() {
+            return Ok(());
+        }
+        write!(f, csi!("{}m"), ColorParam(Colored::ForegroundColor(self.0)))
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        if !colors_enabled() {
+            return Ok(());
+        }
         sys::windows::set_foreground_color(self.0)
     }
+
+    fn update_state(&self) {
+        set_applied_style(None);
+    }
 }
 
 /// A command that sets the the background color.
@@ -219,19 +526,63 @@ impl Command for SetForegroundColor {
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * Colors the terminal can't display are downgraded to the closest supported color, see
+///   [`color_support`](fn.color_support.html) and [`Color::downgrade`](enum.Color.html#method.downgrade).
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SetBackgroundColor(pub Color);
 
 impl Command for SetBackgroundColor {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        write!(f, csi!("{}m"), Colored::BackgroundColor(self.0))
+        if !colors_enabled() {
+            return Ok(());
+        }
+        write!(f, csi!("{}m"), ColorParam(Colored::BackgroundColor(self.0)))
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        if !colors_enabled() {
+            return Ok(());
+        }
         sys::windows::set_background_color(self.0)
     }
+
+    fn update_state(&self) {
+        set_applied_style(None);
+    }
+}
+
+/// A command that sets the the underline color.
+///
+/// See [`Color`](enum.Color.html) for more info.
+///
+/// # Notes
+///
+/// * This does nothing if the terminal doesn't support underline colors, see
+///   [`supports_underline_styles`](fn.supports_underline_styles.html).
+/// * The underline color is not supported by the WinAPI.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetUnderlineColor(pub Color);
+
+impl Command for SetUnderlineColor {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if !colors_enabled() || !supports_underline_styles() {
+            return Ok(());
+        }
+        write!(f, csi!("{}m"), ColorParam(Colored::UnderlineColor(self.0)))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        // the underline color can't be changed with the WinAPI.
+        Ok(())
+    }
+
+    fn update_state(&self) {
+        set_applied_style(None);
+    }
 }
 
 /// A command that optionally sets the foreground and/or background color.
@@ -270,15 +621,19 @@ impl Command for SetColors {
     }
 
     #[cfg(windows)]
-    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+    fn execute_winapi(&self, mut writer: impl FnMut() -> Result<()>) -> Result<()> {
         if let Some(color) = self.0.foreground {
-            sys::windows::set_foreground_color(color)?;
+            SetForegroundColor(color).execute_winapi(&mut writer)?;
         }
         if let Some(color) = self.0.background {
-            sys::windows::set_background_color(color)?;
+            SetBackgroundColor(color).execute_winapi(&mut writer)?;
         }
         Ok(())
     }
+
+    fn update_state(&self) {
+        set_applied_style(None);
+    }
 }
 
 /// A command that sets an attribute.
@@ -293,13 +648,19 @@ pub struct SetAttribute(pub Attribute);
 
 impl Command for SetAttribute {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        write!(f, csi!("{}m"), self.0.sgr())
+        if self.0.is_underline_style() && !supports_underline_styles() {
+            return write!(f, csi!("{}m"), Attribute::Underlined.sgr());
+        }
+        write!(f, csi!("{}m"), self.0.sgr_param())
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
-        // attributes are not supported by WinAPI.
-        Ok(())
+        sys::windows::set_attribute(self.0)
+    }
+
+    fn update_state(&self) {
+        set_applied_style(None);
     }
 }
 
@@ -325,8 +686,255 @@ impl Command for SetAttributes {
 
     #[cfg(windows)]
     fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
-        // attributes are not supported by WinAPI.
+        for attr in Attribute::iterator() {
+            if self.0.has(attr) {
+                sys::windows::set_attribute(attr)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn update_state(&self) {
+        set_applied_style(None);
+    }
+}
+
+/// A command that sets the colors and attributes of a style at once.
+///
+/// Unlike executing [`SetForegroundColor`](struct.SetForegroundColor.html),
+/// [`SetBackgroundColor`](struct.SetBackgroundColor.html) and
+/// [`SetAttributes`](struct.SetAttributes.html) one after another, this writes a single combined
+/// escape sequence (or sets a single attribute word with the WinAPI).
+///
+/// See [`ContentStyle`](struct.ContentStyle.html) for more info.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{execute, Result};
+/// use crossterm::style::{Attribute, Color, ContentStyle, Print, ResetColor, SetStyle};
+///
+/// fn main() -> Result<()> {
+///     let style = ContentStyle::new()
+///         .foreground(Color::Yellow)
+///         .background(Color::Blue)
+///         .attribute(Attribute::Bold)
+///         .attribute(Attribute::Italic);
+///
+///     execute!(stdout(), SetStyle(style), Print("styled"), ResetColor)
+/// }
+/// ```
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetStyle(pub ContentStyle);
+
+impl Command for SetStyle {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write_sgr(
+            f,
+            &[self.0.attributes],
+            self.0.background_color,
+            self.0.foreground_color,
+        )
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        if colors_enabled() {
+            sys::windows::set_style(&self.0)
+        } else {
+            sys::windows::set_style(&ContentStyle {
+                foreground_color: None,
+                background_color: None,
+                ..self.0
+            })
+        }
+    }
+
+    fn update_state(&self) {
+        set_applied_style(None);
+    }
+}
+
+// The style applied by the last style command, `None` if it's unknown (e.g. after
+// `SetAttribute`), see `UpdateStyle`.
+static APPLIED_STYLE: Mutex<Option<ContentStyle>> = parking_lot::const_mutex(None);
+
+fn set_applied_style(style: Option<ContentStyle>) {
+    *APPLIED_STYLE.lock() = style;
+}
+
+/// A command that changes the applied style to the given style, writing only the escape codes
+/// needed for the difference.
+///
+/// Crossterm keeps track of the last style applied by this command. Nothing is written if the
+/// given style is equal to it, only the foreground color code is written if only the foreground
+/// color differs and so on. Removed attributes are turned off without resetting the colors.
+/// Executing this command instead of [`SetStyle`](struct.SetStyle.html) makes redrawing a full
+/// screen a lot cheaper.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{queue, Result};
+/// use crossterm::style::{Color, ContentStyle, Print, UpdateStyle};
+///
+/// fn main() -> Result<()> {
+///     let cells = [
+///         ('a', ContentStyle::new().foreground(Color::Red)),
+///         ('b', ContentStyle::new().foreground(Color::Red)),
+///         ('c', ContentStyle::new().foreground(Color::Blue)),
+///     ];
+///
+///     let mut stdout = stdout();
+///
+///     for (c, style) in cells.iter() {
+///         // Writes nothing for `b` and only the new foreground color for `c`.
+///         queue!(stdout, UpdateStyle(*style), Print(c))?;
+///     }
+///
+///     stdout.flush()?;
+///     Ok(())
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * The applied style is global. The other style commands (e.g.
+///   [`SetForegroundColor`](struct.SetForegroundColor.html)) make it unknown, the next
+///   `UpdateStyle` resets the style and applies the given one as a whole then. Styles written
+///   without commands (e.g. printing a [`StyledContent`](struct.StyledContent.html) with
+///   `print!`) are not tracked.
+/// * The WinAPI applies the given style as a whole (if it differs).
+/// * Commands must be executed/queued for execution otherwise they do nothing. Formatting the
+///   command (e.g. with `to_string`) doesn't change the applied style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpdateStyle(pub ContentStyle);
+
+impl Command for UpdateStyle {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let applied_style = *APPLIED_STYLE.lock();
+        write_style_update(f, applied_style, self.0)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, writer: impl FnMut() -> Result<()>) -> Result<()> {
+        if *APPLIED_STYLE.lock() == Some(self.0) {
+            return Ok(());
+        }
+        SetStyle(reset_and(self.0)).execute_winapi(writer)
+    }
+
+    fn update_state(&self) {
+        set_applied_style(Some(self.0));
+    }
+}
+
+/// Writes the escape codes which change the style from `from` (`None` = unknown) to `to`.
+fn write_style_update(
+    f: &mut impl fmt::Write,
+    from: Option<ContentStyle>,
+    to: ContentStyle,
+) -> fmt::Result {
+    let from = match from {
+        Some(from) => from,
+        None => return SetStyle(reset_and(to)).write_ansi(f),
+    };
+
+    if from == to {
+        return Ok(());
+    }
+    if to.attributes.has(Attribute::Reset) {
+        return SetStyle(to).write_ansi(f);
+    }
+
+    // Turn off the removed attributes, this may turn off other attributes as well (e.g.
+    // `NormalIntensity` turns off `Bold` and `Dim`)
+    let mut attributes_off = Attributes::default();
+    for attr in Attribute::iterator() {
+        if from.attributes.has(attr) && !to.attributes.has(attr) {
+            if let Some(off) = attr.off() {
+                attributes_off.set(off);
+            }
+        }
+    }
+
+    // Set the new attributes and the ones which were turned off as a side effect
+    let mut attributes_on = Attributes::default();
+    for attr in Attribute::iterator() {
+        let turned_off = matches!(attr.off(), Some(off) if attributes_off.has(off));
+        if to.attributes.has(attr) && (!from.attributes.has(attr) || turned_off) {
+            attributes_on.set(attr);
+        }
+    }
+
+    let color_diff = |from: Option<Color>, to: Option<Color>| {
+        if from == to {
+            None
+        } else {
+            Some(to.unwrap_or(Color::Reset))
+        }
+    };
+
+    write_sgr(
+        f,
+        &[attributes_off, attributes_on],
+        color_diff(from.background_color, to.background_color),
+        color_diff(from.foreground_color, to.foreground_color),
+    )
+}
+
+/// Writes the attributes (in the order of the given groups) and colors as a single SGR sequence.
+///
+/// Nothing is written if there's nothing to set.
+fn write_sgr(
+    f: &mut impl fmt::Write,
+    attribute_groups: &[Attributes],
+    background_color: Option<Color>,
+    foreground_color: Option<Color>,
+) -> fmt::Result {
+    let mut separator = "";
+    let mut write_param = |f: &mut dyn fmt::Write, param: &dyn Display| {
+        if separator.is_empty() {
+            f.write_str(csi!())?;
+        }
+        write!(f, "{}{}", separator, param)?;
+        separator = ";";
+        Ok(())
+    };
+
+    for attributes in attribute_groups {
+        for attr in Attribute::iterator() {
+            if attributes.has(attr) {
+                if attr.is_underline_style() && !supports_underline_styles() {
+                    write_param(f, &Attribute::Underlined.sgr())?;
+                } else {
+                    write_param(f, &attr.sgr_param())?;
+                }
+            }
+        }
+    }
+
+    if colors_enabled() {
+        if let Some(bg) = background_color {
+            write_param(f, &ColorParam(Colored::BackgroundColor(bg)))?;
+        }
+        if let Some(fg) = foreground_color {
+            write_param(f, &ColorParam(Colored::ForegroundColor(fg)))?;
+        }
+    }
+
+    if separator.is_empty() {
         Ok(())
+    } else {
+        f.write_str("m")
     }
 }
 
@@ -345,10 +953,18 @@ impl<D: Display> Command for PrintStyledContent<D> {
         write!(f, "{}", self.0)
     }
 
+    fn write_plain(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "{}", self.0.content())
+    }
+
     #[cfg(windows)]
     fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
         Ok(())
     }
+
+    fn update_state(&self) {
+        set_applied_style(None);
+    }
 }
 
 /// A command that resets the colors back to default.
@@ -368,6 +984,192 @@ impl Command for ResetColor {
     fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
         sys::windows::reset()
     }
+
+    fn update_state(&self) {
+        set_applied_style(Some(ContentStyle::default()));
+    }
+}
+
+/// A command that redefines a color of the terminal palette.
+///
+/// The first value is the index of the palette entry (`0` - `15` are the base colors, see
+/// [`Color::AnsiValue`](enum.Color.html#variant.AnsiValue) for the others) and the second one the
+/// new color. Setting an entry to `Color::Reset` restores its default color.
+///
+/// Use [`ResetPalette`](struct.ResetPalette.html) to restore the whole palette, e.g. on exit.
+///
+/// # Notes
+///
+/// * This uses `OSC 4` which is not supported by all terminals.
+/// * Changing the palette is not supported by the WinAPI.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetPaletteColor(pub u8, pub Color);
+
+impl Command for SetPaletteColor {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        match self.1.to_rgb() {
+            Some((r, g, b)) => write!(
+                f,
+                "\x1B]4;{};rgb:{:02x}/{:02x}/{:02x}\x1B\\",
+                self.0, r, g, b
+            ),
+            None => write!(f, "\x1B]104;{}\x1B\\", self.0),
+        }
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        // the palette can't be changed with the WinAPI.
+        Ok(())
+    }
+}
+
+/// A command that restores the default colors of the whole terminal palette.
+///
+/// See [`SetPaletteColor`](struct.SetPaletteColor.html) for more info.
+///
+/// # Notes
+///
+/// * This uses `OSC 104` which is not supported by all terminals.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetPalette;
+
+impl Command for ResetPalette {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1B]104\x1B\\")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        // the palette can't be changed with the WinAPI.
+        Ok(())
+    }
+}
+
+// The styles pushed with `PushStyle`, the last one is the currently applied style.
+static STYLE_STACK: Mutex<Vec<ContentStyle>> = parking_lot::const_mutex(Vec::new());
+
+/// A command that remembers the current style and applies the given style on top of it.
+///
+/// The colors of the given style replace the current ones, the attributes are added to the current
+/// ones. Use [`PopStyle`](struct.PopStyle.html) to restore the previous style. This allows nested
+/// components to temporarily change the style without clobbering the style of the caller.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{execute, Result};
+/// use crossterm::style::{Attribute, Color, ContentStyle, PopStyle, Print, PushStyle};
+///
+/// fn main() -> Result<()> {
+///     execute!(
+///         stdout(),
+///         PushStyle(ContentStyle::new().foreground(Color::Green)),
+///         Print("green "),
+///         PushStyle(ContentStyle::new().attribute(Attribute::Bold)),
+///         Print("bold green "),
+///         PopStyle,
+///         Print("green again"),
+///         PopStyle,
+///     )
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * The style stack is global and it only knows about the styles pushed with this command.
+/// * Commands must be executed/queued for execution otherwise they do nothing. Formatting the
+///   command (e.g. with `to_string`) doesn't push the style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PushStyle(pub ContentStyle);
+
+impl PushStyle {
+    /// Returns the style which is applied by this command.
+    fn style(&self) -> ContentStyle {
+        match STYLE_STACK.lock().last() {
+            Some(current) => ContentStyle {
+                foreground_color: self.0.foreground_color.or(current.foreground_color),
+                background_color: self.0.background_color.or(current.background_color),
+                attributes: current.attributes | self.0.attributes,
+            },
+            None => self.0,
+        }
+    }
+}
+
+impl Command for PushStyle {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        SetStyle(reset_and(self.style())).write_ansi(f)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, writer: impl FnMut() -> Result<()>) -> Result<()> {
+        SetStyle(reset_and(self.style())).execute_winapi(writer)
+    }
+
+    fn update_state(&self) {
+        let style = self.style();
+        STYLE_STACK.lock().push(style);
+        set_applied_style(Some(style));
+    }
+}
+
+/// A command that restores the style which was applied before the last
+/// [`PushStyle`](struct.PushStyle.html).
+///
+/// The colors and attributes are reset if there's no previous style.
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PopStyle;
+
+impl PopStyle {
+    /// Returns the style which is applied by this command.
+    fn style(&self) -> ContentStyle {
+        let stack = STYLE_STACK.lock();
+        match stack.len() {
+            0 | 1 => ContentStyle::default(),
+            len => stack[len - 2],
+        }
+    }
+}
+
+impl Command for PopStyle {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        SetStyle(reset_and(self.style())).write_ansi(f)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, writer: impl FnMut() -> Result<()>) -> Result<()> {
+        SetStyle(reset_and(self.style())).execute_winapi(writer)
+    }
+
+    fn update_state(&self) {
+        set_applied_style(Some(self.style()));
+        STYLE_STACK.lock().pop();
+    }
+}
+
+/// Forgets the styles pushed with `PushStyle` and the applied style (the default one after the
+/// terminal was reset).
+pub(crate) fn reset_style_state() {
+    STYLE_STACK.lock().clear();
+    set_applied_style(Some(ContentStyle::default()));
+}
+
+/// Returns the given style with a leading `Attribute::Reset`, so that applying it replaces the
+/// current colors and attributes.
+fn reset_and(style: ContentStyle) -> ContentStyle {
+    ContentStyle {
+        attributes: Attributes::from(Attribute::Reset) | style.attributes,
+        ..style
+    }
 }
 
 /// A command that prints the given displayable type.
@@ -381,12 +1183,76 @@ impl<T: Display> Command for Print<T> {
         write!(f, "{}", self.0)
     }
 
+    fn write_plain(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        self.write_ansi(f)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, mut writer: impl FnMut() -> Result<()>) -> Result<()> {
+        writer()
+    }
+}
+
+/// A command that prints a hyperlink.
+///
+/// The first value is the URI the link points to and the second one the text to print. Terminals
+/// supporting hyperlinks render the text as a clickable link, others just print the text.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{execute, Result};
+/// use crossterm::style::PrintHyperlink;
+///
+/// fn main() -> Result<()> {
+///     execute!(
+///         stdout(),
+///         PrintHyperlink("https://github.com/crossterm-rs/crossterm", "crossterm")
+///     )
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * This uses `OSC 8`, see [`supports_hyperlinks`](fn.supports_hyperlinks.html).
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrintHyperlink<U: Display, T: Display>(pub U, pub T);
+
+impl<U: Display, T: Display> PrintHyperlink<U, T> {
+    /// Writes the link if `supported`, only the text otherwise.
+    fn write_link(&self, f: &mut impl fmt::Write, supported: bool) -> fmt::Result {
+        if supported {
+            write!(f, "\x1B]8;;{}\x1B\\{}\x1B]8;;\x1B\\", self.0, self.1)
+        } else {
+            write!(f, "{}", self.1)
+        }
+    }
+}
+
+impl<U: Display, T: Display> Command for PrintHyperlink<U, T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        self.write_link(f, supports_hyperlinks())
+    }
+
+    fn write_plain(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        self.write_link(f, false)
+    }
+
     #[cfg(windows)]
     fn execute_winapi(&self, mut writer: impl FnMut() -> Result<()>) -> Result<()> {
         writer()
     }
 }
 
+impl<U: Display, T: Display> Display for PrintHyperlink<U, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_link(f, !is_plain_output() && supports_hyperlinks())
+    }
+}
+
 impl<T: Display> Display for Print<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
@@ -396,13 +1262,257 @@ impl<T: Display> Display for Print<T> {
 impl_display!(for SetForegroundColor);
 impl_display!(for SetBackgroundColor);
 impl_display!(for SetColors);
+impl_display!(for SetUnderlineColor);
 impl_display!(for SetAttribute);
+impl_display!(for SetStyle);
+impl_display!(for UpdateStyle);
 impl_display!(for PrintStyledContent<String>);
 impl_display!(for PrintStyledContent<&'static str>);
 impl_display!(for ResetColor);
+impl_display!(for PushStyle);
+impl_display!(for PopStyle);
+impl_display!(for SetPaletteColor);
+impl_display!(for ResetPalette);
 
 /// Utility function for ANSI parsing in Color and Colored.
 /// Gets the next element of `iter` and tries to parse it as a u8.
 fn parse_next_u8<'a>(iter: &mut impl Iterator<Item = &'a str>) -> Option<u8> {
-    iter.next().and_then(|s| u8::from_str_radix(s, 10).ok())
+    iter.next().and_then(|s| s.parse::<u8>().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        gradient, set_color_choice, set_color_support, set_theme, theme, theme_color,
+        write_style_update, Attribute, Color, ColorChoice, ColorSupport, Colorize, ContentStyle,
+        PopStyle, PrintHyperlink, PushStyle, ResetPalette, SetAttribute, SetPaletteColor, SetStyle,
+        Styler, Theme, UpdateStyle,
+    };
+    use crate::queue;
+
+    #[test]
+    fn test_set_palette_color() {
+        assert_eq!(
+            SetPaletteColor(
+                1,
+                Color::Rgb {
+                    r: 255,
+                    g: 0,
+                    b: 10
+                }
+            )
+            .to_string(),
+            "\x1B]4;1;rgb:ff/00/0a\x1B\\"
+        );
+        assert_eq!(
+            SetPaletteColor(200, Color::White).to_string(),
+            "\x1B]4;200;rgb:ff/ff/ff\x1B\\"
+        );
+        assert_eq!(
+            SetPaletteColor(3, Color::Reset).to_string(),
+            "\x1B]104;3\x1B\\"
+        );
+    }
+
+    #[test]
+    fn test_reset_palette() {
+        assert_eq!(ResetPalette.to_string(), "\x1B]104\x1B\\");
+    }
+
+    #[test]
+    fn test_print_hyperlink() {
+        let link = PrintHyperlink("https://example.com", "example");
+
+        let mut supported = String::new();
+        link.write_link(&mut supported, true).unwrap();
+        assert_eq!(
+            supported,
+            "\x1B]8;;https://example.com\x1B\\example\x1B]8;;\x1B\\"
+        );
+
+        let mut unsupported = String::new();
+        link.write_link(&mut unsupported, false).unwrap();
+        assert_eq!(unsupported, "example");
+    }
+
+    #[test]
+    fn test_set_style() {
+        // Don't depend on `NO_COLOR` & co. or the color support of the test environment.
+        set_color_choice(ColorChoice::Always);
+        set_color_support(ColorSupport::Ansi256);
+
+        assert_eq!(SetStyle(ContentStyle::new()).to_string(), "");
+        assert_eq!(
+            SetStyle(
+                ContentStyle::new()
+                    .foreground(Color::Yellow)
+                    .background(Color::Blue)
+                    .attribute(Attribute::Bold)
+                    .attribute(Attribute::Italic)
+            )
+            .to_string(),
+            "\x1B[1;3;48;5;12;38;5;11m"
+        );
+    }
+
+    #[test]
+    fn test_push_and_pop_style() {
+        // Don't depend on `NO_COLOR` & co. or the color support of the test environment.
+        set_color_choice(ColorChoice::Always);
+        set_color_support(ColorSupport::Ansi256);
+
+        let push_red = PushStyle(ContentStyle::new().foreground(Color::Red));
+        // Formatting doesn't push the style
+        assert_eq!(push_red.to_string(), "\x1B[0;38;5;9m");
+        assert_eq!(push_red.to_string(), "\x1B[0;38;5;9m");
+
+        let mut ansi = Vec::new();
+        queue!(
+            ansi,
+            push_red,
+            PushStyle(
+                ContentStyle::new()
+                    .background(Color::Blue)
+                    .attribute(Attribute::Bold)
+            ),
+            PushStyle(ContentStyle::new().foreground(Color::Green)),
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(ansi).unwrap(),
+            "\x1B[0;38;5;9m\x1B[0;1;48;5;12;38;5;9m\x1B[0;1;48;5;12;38;5;10m"
+        );
+
+        assert_eq!(PopStyle.to_string(), "\x1B[0;1;48;5;12;38;5;9m");
+
+        let mut ansi = Vec::new();
+        // popping an empty stack just resets
+        queue!(ansi, PopStyle, PopStyle, PopStyle, PopStyle).unwrap();
+        assert_eq!(
+            String::from_utf8(ansi).unwrap(),
+            "\x1B[0;1;48;5;12;38;5;9m\x1B[0;38;5;9m\x1B[0m\x1B[0m"
+        );
+
+        // The commands track the applied style for `UpdateStyle`, it's tested here as the style
+        // stack and the applied style are global
+        let red = ContentStyle::new().foreground(Color::Red);
+        let mut ansi = Vec::new();
+        queue!(
+            ansi,
+            UpdateStyle(red),
+            UpdateStyle(red),
+            PushStyle(ContentStyle::new().attribute(Attribute::Bold)),
+            UpdateStyle(red),
+            SetAttribute(Attribute::Italic),
+            UpdateStyle(red),
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(ansi).unwrap(),
+            "\x1B[38;5;9m\x1B[0;1m\x1B[22;38;5;9m\x1B[3m\x1B[0;38;5;9m"
+        );
+        // Formatting doesn't change the applied style
+        assert_eq!(UpdateStyle(ContentStyle::new()).to_string(), "\x1B[39m");
+        assert_eq!(UpdateStyle(ContentStyle::new()).to_string(), "\x1B[39m");
+        let mut ansi = Vec::new();
+        queue!(ansi, PopStyle).unwrap();
+    }
+
+    #[test]
+    fn test_colorize_and_styler_chaining() {
+        let styled = "error".red().bold();
+        assert_eq!(styled.style().foreground_color, Some(Color::Red));
+        assert!(styled.style().attributes.has(Attribute::Bold));
+        assert_eq!(*styled.content(), "error");
+
+        let styled = String::from("warning")
+            .underlined()
+            .on_dark_yellow()
+            .black();
+        assert_eq!(styled.style().foreground_color, Some(Color::Black));
+        assert_eq!(styled.style().background_color, Some(Color::DarkYellow));
+        assert!(styled.style().attributes.has(Attribute::Underlined));
+
+        let styled = 'x'.italic().green();
+        assert_eq!(styled.style().foreground_color, Some(Color::Green));
+        assert!(styled.style().attributes.has(Attribute::Italic));
+    }
+
+    #[test]
+    fn test_update_style() {
+        set_color_choice(ColorChoice::Always);
+        set_color_support(ColorSupport::Ansi256);
+
+        let update = |from: Option<ContentStyle>, to: ContentStyle| {
+            let mut ansi = String::new();
+            write_style_update(&mut ansi, from, to).unwrap();
+            ansi
+        };
+
+        let red = ContentStyle::new().foreground(Color::Red);
+        let red_bold = red.attribute(Attribute::Bold);
+        let red_bold_dim = red_bold.attribute(Attribute::Dim);
+
+        assert_eq!(update(Some(red), red), "");
+        assert_eq!(
+            update(Some(red), ContentStyle::new().foreground(Color::Blue)),
+            "\x1B[38;5;12m"
+        );
+        assert_eq!(
+            update(Some(red), ContentStyle::new().background(Color::Blue)),
+            "\x1B[48;5;12;39m"
+        );
+        assert_eq!(update(Some(red), red_bold), "\x1B[1m");
+        assert_eq!(update(Some(red_bold), red), "\x1B[22m");
+        // `NormalIntensity` turns off `Dim` as well, so it's applied again
+        assert_eq!(
+            update(Some(red_bold_dim), red.attribute(Attribute::Dim)),
+            "\x1B[22;2m"
+        );
+        assert_eq!(
+            update(Some(red_bold), red_bold.attribute(Attribute::Reset)),
+            "\x1B[0;1;38;5;9m"
+        );
+        // The applied style is unknown
+        assert_eq!(update(None, red_bold), "\x1B[0;1;38;5;9m");
+    }
+
+    #[test]
+    fn test_gradient() {
+        let from = Color::Rgb { r: 0, g: 0, b: 0 };
+        let to = Color::Rgb { r: 0, g: 0, b: 2 };
+
+        let fragments = gradient("abcde", from, to);
+        let contents: Vec<_> = fragments.iter().map(|f| f.content().as_str()).collect();
+        let colors: Vec<_> = fragments
+            .iter()
+            .map(|f| f.style().foreground_color)
+            .collect();
+
+        // 0, 0.5, 1, 1.5, 2 are rounded to 0, 1, 1, 2, 2
+        assert_eq!(contents, ["a", "bc", "de"]);
+        assert_eq!(
+            colors,
+            [Some(from), Some(Color::Rgb { r: 0, g: 0, b: 1 }), Some(to)]
+        );
+
+        assert!(gradient("", from, to).is_empty());
+
+        let fragments = gradient("reset", from, Color::Reset);
+        assert_eq!(fragments.len(), 1);
+        assert_eq!(fragments[0].content(), "reset");
+        assert_eq!(fragments[0].style(), &ContentStyle::new());
+    }
+
+    #[test]
+    fn test_theme() {
+        assert_eq!(theme_color("error"), Theme::standard().get("error"));
+
+        set_theme(Theme::new().with("error", Color::Magenta));
+        assert_eq!(theme_color("error"), Some(Color::Magenta));
+        assert_eq!(theme_color("warning"), None);
+        assert_eq!(theme(), Theme::new().with("error", Color::Magenta));
+
+        set_theme(Theme::standard());
+    }
 }
@@ -1,8 +1,12 @@
 use std::ops::{BitAnd, BitOr, BitXor};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::style::Attribute;
 
 /// a bitset for all possible attributes
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct Attributes(u32);
 
@@ -2,9 +2,13 @@
 
 use std::fmt::Display;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::style::{Attribute, Attributes, Color, StyledContent};
 
 /// The style that can be put on content.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
 pub struct ContentStyle {
     /// The foreground color.
@@ -0,0 +1,304 @@
+//! This module contains the logic to parse and strip styled text (text with ANSI escape codes).
+
+use crate::style::{Attribute, Color, ContentStyle};
+
+/// Parses text containing ANSI escape codes into styled fragments.
+///
+/// The SGR sequences (`ESC [ ... m`) are interpreted and every fragment contains the text with
+/// the style that applies to it. Other escape sequences (cursor movement, OSC, ...) are removed.
+/// This makes it possible to re-render, filter or convert output captured from another program.
+///
+/// # Examples
+///
+/// ```
+/// use crossterm::style::{parse_ansi, Attribute, Color, ContentStyle};
+///
+/// let fragments = parse_ansi("\x1B[1;31mError:\x1B[0m file not found");
+///
+/// assert_eq!(
+///     fragments,
+///     vec![
+///         (
+///             ContentStyle::new().foreground(Color::DarkRed).attribute(Attribute::Bold),
+///             "Error:".to_string()
+///         ),
+///         (ContentStyle::new(), " file not found".to_string()),
+///     ]
+/// );
+/// ```
+///
+/// # Notes
+///
+/// * Attributes which are turned off (e.g. `22`, `24`) are removed from the fragment style.
+/// * The underline color (`58`) is not part of the `ContentStyle` and therefore ignored.
+pub fn parse_ansi(text: &str) -> Vec<(ContentStyle, String)> {
+    let mut fragments: Vec<(ContentStyle, String)> = Vec::new();
+    let mut style = ContentStyle::new();
+
+    scan(text, |token| match token {
+        Token::Char(c) => match fragments.last_mut() {
+            Some((last_style, content)) if *last_style == style => content.push(c),
+            _ => fragments.push((style, c.to_string())),
+        },
+        Token::Sgr(params) => apply_sgr(&mut style, params),
+    });
+
+    fragments
+}
+
+/// Removes all the ANSI escape codes (CSI, OSC and other escape sequences) from the text.
+///
+/// This is useful to measure the width of styled text or to log an unstyled copy of it.
+///
+/// # Examples
+///
+/// ```
+/// use crossterm::style::{strip_ansi, Colorize, Styler};
+///
+/// let styled = format!("{} {}", "error:".red().bold(), "file not found");
+///
+/// assert_eq!(strip_ansi(&styled), "error: file not found");
+/// assert_eq!(strip_ansi("\x1B]0;title\x07\x1B[2Jtext"), "text");
+/// ```
+pub fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    scan(text, |token| {
+        if let Token::Char(c) = token {
+            stripped.push(c);
+        }
+    });
+    stripped
+}
+
+/// A part of a text containing ANSI escape codes.
+enum Token<'a> {
+    /// A character which is not part of an escape code.
+    Char(char),
+    /// The parameters of a SGR sequence (`ESC [ <params> m`).
+    Sgr(&'a str),
+}
+
+/// Scans the text, calling `on_token` for every character and SGR sequence. Other escape codes
+/// are skipped.
+fn scan(text: &str, mut on_token: impl FnMut(Token<'_>)) {
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1B' {
+            on_token(Token::Char(c));
+            continue;
+        }
+
+        match chars.next() {
+            // CSI: ESC [ <parameters> <final byte>
+            Some('[') => {
+                let mut params = String::new();
+                for c in chars.by_ref() {
+                    if ('\x40'..='\x7E').contains(&c) {
+                        if c == 'm' {
+                            on_token(Token::Sgr(&params));
+                        }
+                        break;
+                    }
+                    params.push(c);
+                }
+            }
+            // OSC: ESC ] <string> (BEL | ESC \)
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1B' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // Any other escape sequence is a single character
+            _ => {}
+        }
+    }
+}
+
+/// Applies the parameters of a SGR sequence (`ESC [ <params> m`) to the style.
+fn apply_sgr(style: &mut ContentStyle, params: &str) {
+    let mut params = params.split(';');
+
+    while let Some(param) = params.next() {
+        match param {
+            "" | "0" => *style = ContentStyle::new(),
+            "38" => style.foreground_color = parse_extended_color(&mut params),
+            "48" => style.background_color = parse_extended_color(&mut params),
+            "58" => {
+                parse_extended_color(&mut params);
+            }
+            "39" => style.foreground_color = None,
+            "49" => style.background_color = None,
+            _ => {
+                if let Ok(code) = param.parse::<u8>() {
+                    match code {
+                        30..=37 => style.foreground_color = Some(Color::from_ansi_value(code - 30)),
+                        90..=97 => {
+                            style.foreground_color = Some(Color::from_ansi_value(code - 90 + 8))
+                        }
+                        40..=47 => style.background_color = Some(Color::from_ansi_value(code - 40)),
+                        100..=107 => {
+                            style.background_color = Some(Color::from_ansi_value(code - 100 + 8))
+                        }
+                        _ => apply_attribute(style, param),
+                    }
+                } else {
+                    apply_attribute(style, param);
+                }
+            }
+        }
+    }
+}
+
+/// Parses the `5;<n>` or `2;<r>;<g>;<b>` part of an extended color.
+fn parse_extended_color<'a>(params: &mut impl Iterator<Item = &'a str>) -> Option<Color> {
+    let mut next_u8 = || params.next().and_then(|p| p.parse::<u8>().ok());
+
+    match next_u8()? {
+        5 => next_u8().map(Color::from_ansi_value),
+        2 => Some(Color::Rgb {
+            r: next_u8()?,
+            g: next_u8()?,
+            b: next_u8()?,
+        }),
+        _ => None,
+    }
+}
+
+fn apply_attribute(style: &mut ContentStyle, param: &str) {
+    let attribute = match Attribute::iterator().find(|attribute| attribute.sgr_param() == param) {
+        Some(attribute) => attribute,
+        None => return,
+    };
+
+    // Attributes turning off other attributes remove them
+    let removed: &[Attribute] = match attribute {
+        Attribute::NoBold => &[Attribute::Bold],
+        Attribute::NormalIntensity => &[Attribute::Bold, Attribute::Dim],
+        Attribute::NoItalic => &[Attribute::Italic, Attribute::Fraktur],
+        Attribute::NoUnderline => &[
+            Attribute::Underlined,
+            Attribute::DoubleUnderlined,
+            Attribute::Undercurled,
+            Attribute::Underdotted,
+            Attribute::Underdashed,
+        ],
+        Attribute::NoBlink => &[Attribute::SlowBlink, Attribute::RapidBlink],
+        Attribute::NoReverse => &[Attribute::Reverse],
+        Attribute::NoHidden => &[Attribute::Hidden],
+        Attribute::NotCrossedOut => &[Attribute::CrossedOut],
+        Attribute::NotFramedOrEncircled => &[Attribute::Framed, Attribute::Encircled],
+        Attribute::NotOverLined => &[Attribute::OverLined],
+        _ => {
+            style.attributes.set(attribute);
+            return;
+        }
+    };
+
+    for attribute in removed {
+        style.attributes.unset(*attribute);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_ansi, strip_ansi};
+    use crate::style::{Attribute, Color, ContentStyle};
+
+    #[test]
+    fn test_parse_plain_text() {
+        assert_eq!(
+            parse_ansi("plain text"),
+            vec![(ContentStyle::new(), "plain text".to_string())]
+        );
+        assert_eq!(parse_ansi(""), vec![]);
+    }
+
+    #[test]
+    fn test_parse_colors() {
+        assert_eq!(
+            parse_ansi("\x1B[38;5;9mred\x1B[48;2;1;2;3mon rgb\x1B[39;49m\x1B[94;101mblue"),
+            vec![
+                (
+                    ContentStyle::new().foreground(Color::Red),
+                    "red".to_string()
+                ),
+                (
+                    ContentStyle::new()
+                        .foreground(Color::Red)
+                        .background(Color::Rgb { r: 1, g: 2, b: 3 }),
+                    "on rgb".to_string()
+                ),
+                (
+                    ContentStyle::new()
+                        .foreground(Color::Blue)
+                        .background(Color::Red),
+                    "blue".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_attributes() {
+        assert_eq!(
+            parse_ansi("\x1B[1m\x1B[4:3mbold curly\x1B[22mcurly\x1B[24mplain"),
+            vec![
+                (
+                    ContentStyle::new()
+                        .attribute(Attribute::Bold)
+                        .attribute(Attribute::Undercurled),
+                    "bold curly".to_string()
+                ),
+                (
+                    ContentStyle::new().attribute(Attribute::Undercurled),
+                    "curly".to_string()
+                ),
+                (ContentStyle::new(), "plain".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_reset() {
+        assert_eq!(
+            parse_ansi("\x1B[1;32mgreen\x1B[mreset"),
+            vec![
+                (
+                    ContentStyle::new()
+                        .foreground(Color::DarkGreen)
+                        .attribute(Attribute::Bold),
+                    "green".to_string()
+                ),
+                (ContentStyle::new(), "reset".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_skips_other_sequences() {
+        assert_eq!(
+            parse_ansi("a\x1B[2Jb\x1B]0;title\x07c\x1B]8;;https://example.com\x1B\\d\x1B7e"),
+            vec![(ContentStyle::new(), "abcde".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(strip_ansi("plain"), "plain");
+        assert_eq!(
+            strip_ansi(
+                "\x1B[1;38;2;1;2;3mbold\x1B[0m \x1B[5Amoved\x1B]8;;uri\x1B\\link\x1B]8;;\x1B\\"
+            ),
+            "bold movedlink"
+        );
+        // An unterminated sequence is removed until the end of the text
+        assert_eq!(strip_ansi("text\x1B[1"), "text");
+    }
+}
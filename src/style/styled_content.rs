@@ -8,8 +8,8 @@ use std::{
 use crate::{
     command::execute_fmt,
     style::{
-        Attribute, Color, Colorize, ContentStyle, ResetColor, SetAttributes, SetBackgroundColor,
-        SetForegroundColor, Styler,
+        Attribute, Attributes, Color, Colorize, ContentStyle, SetAttributes, SetBackgroundColor,
+        SetForegroundColor, SetStyle, Styler,
     },
 };
 
@@ -77,6 +77,12 @@ impl<D: Display> StyledContent<D> {
         &self.content
     }
 
+    /// Returns a mutable reference to the content.
+    #[inline]
+    pub fn content_mut(&mut self) -> &mut D {
+        &mut self.content
+    }
+
     /// Returns the style.
     #[inline]
     pub fn style(&self) -> &ContentStyle {
@@ -93,40 +99,37 @@ impl<D: Display> StyledContent<D> {
 
 impl<D: Display> Display for StyledContent<D> {
     fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
-        let mut reset_background = false;
-        let mut reset_foreground = false;
-        let mut reset = false;
-
-        if let Some(bg) = self.style.background_color {
-            execute_fmt(f, SetBackgroundColor(bg)).map_err(|_| fmt::Error)?;
-            reset_background = true;
-        }
-        if let Some(fg) = self.style.foreground_color {
-            execute_fmt(f, SetForegroundColor(fg)).map_err(|_| fmt::Error)?;
-            reset_foreground = true;
-        }
+        let reset_background = self.style.background_color.is_some();
+        let reset_foreground = self.style.foreground_color.is_some();
+        let reset = !self.style.attributes.is_empty();
 
-        if !self.style.attributes.is_empty() {
-            execute_fmt(f, SetAttributes(self.style.attributes)).map_err(|_| fmt::Error)?;
-            reset = true;
+        if reset_background || reset_foreground || reset {
+            // Colors and attributes are applied with a single escape sequence
+            execute_fmt(f, SetStyle(self.style)).map_err(|_| fmt::Error)?;
         }
 
         self.content.fmt(f)?;
 
         if reset {
-            // NOTE: This will reset colors even though self has no colors, hence produce unexpected
-            // resets.
-            // TODO: reset the set attributes only.
-            execute_fmt(f, ResetColor).map_err(|_| fmt::Error)?;
-        } else {
-            // NOTE: Since the above bug, we do not need to reset colors when we reset attributes.
-            if reset_background {
-                execute_fmt(f, SetBackgroundColor(Color::Reset)).map_err(|_| fmt::Error)?;
-            }
-            if reset_foreground {
-                execute_fmt(f, SetForegroundColor(Color::Reset)).map_err(|_| fmt::Error)?;
+            // Only turn off the attributes which were set, so the surrounding colors and
+            // attributes are kept.
+            let attributes_off = Attribute::iterator()
+                .filter(|attribute| self.style.attributes.has(*attribute))
+                .filter_map(Attribute::off)
+                .fold(Attributes::default(), |attributes, attribute| {
+                    attributes | attribute
+                });
+
+            if !attributes_off.is_empty() {
+                execute_fmt(f, SetAttributes(attributes_off)).map_err(|_| fmt::Error)?;
             }
         }
+        if reset_background {
+            execute_fmt(f, SetBackgroundColor(Color::Reset)).map_err(|_| fmt::Error)?;
+        }
+        if reset_foreground {
+            execute_fmt(f, SetForegroundColor(Color::Reset)).map_err(|_| fmt::Error)?;
+        }
 
         Ok(())
     }
@@ -143,6 +146,7 @@ impl<D: Display> Styler<D> for StyledContent<D> {
 #[cfg(test)]
 mod tests {
     use super::{Attribute, Color, ContentStyle};
+    use crate::style::{set_color_choice, set_color_support, style, ColorChoice, ColorSupport};
 
     #[test]
     fn test_set_fg_bg_add_attr() {
@@ -163,4 +167,46 @@ mod tests {
         assert!(styled_content.style.attributes.has(Attribute::Bold));
         assert!(styled_content.style.attributes.has(Attribute::NoItalic));
     }
+
+    #[test]
+    fn test_display_unstyled_content() {
+        assert_eq!(style("hello").to_string(), "hello");
+    }
+
+    #[test]
+    fn test_display_colored_content() {
+        // Don't depend on `NO_COLOR` & co. or the color support of the test environment.
+        set_color_choice(ColorChoice::Always);
+        set_color_support(ColorSupport::Ansi256);
+
+        let styled = style("hello").with(Color::Red).on(Color::Black);
+
+        assert_eq!(
+            styled.to_string(),
+            "\x1B[48;5;0;38;5;9mhello\x1B[49m\x1B[39m"
+        );
+    }
+
+    #[test]
+    fn test_display_content_with_attribute() {
+        let styled = style(42).attribute(Attribute::Bold);
+
+        assert_eq!(styled.to_string(), "\x1B[1m42\x1B[22m");
+    }
+
+    #[test]
+    fn test_display_content_with_attributes_and_color() {
+        set_color_choice(ColorChoice::Always);
+        set_color_support(ColorSupport::Ansi256);
+
+        let styled = style("hi")
+            .with(Color::Red)
+            .attribute(Attribute::Bold)
+            .attribute(Attribute::Underlined);
+
+        assert_eq!(
+            styled.to_string(),
+            "\x1B[1;4;38;5;9mhi\x1B[22m\x1B[24m\x1B[39m"
+        );
+    }
 }
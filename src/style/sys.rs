@@ -1,2 +1,16 @@
+//! This module provides platform related functions.
+
+#[cfg(unix)]
+pub(crate) use self::unix::{color_support, supports_hyperlinks, supports_underline_styles};
+#[cfg(unix)]
+pub use self::unix::{default_background_color, default_foreground_color};
+#[cfg(windows)]
+pub(crate) use self::windows::{color_support, supports_hyperlinks, supports_underline_styles};
+#[cfg(windows)]
+pub use self::windows::{default_background_color, default_foreground_color};
+
 #[cfg(windows)]
 pub(crate) mod windows;
+
+#[cfg(unix)]
+pub(crate) mod unix;
@@ -0,0 +1,138 @@
+use std::{io::Error, time::Duration};
+
+use crate::{
+    event::{filter::DefaultColorFilter, poll_internal, read_internal, InternalEvent},
+    terminal::{
+        disable_raw_mode, enable_raw_mode,
+        sys::{is_raw_mode_enabled, query_control_string, tput},
+    },
+    Result,
+};
+
+use super::super::{Color, ColorSupport, Colored};
+
+pub(crate) fn color_support() -> ColorSupport {
+    if let Ok(term) = std::env::var("TERM") {
+        if term.ends_with("-direct") {
+            return ColorSupport::TrueColor;
+        }
+        if term.contains("256color") {
+            return ColorSupport::Ansi256;
+        }
+    }
+
+    tput_colors()
+        .map(ColorSupport::from_color_count)
+        .unwrap_or(ColorSupport::Ansi16)
+}
+
+pub(crate) fn supports_underline_styles() -> bool {
+    let var = |name| std::env::var(name).unwrap_or_default();
+
+    let term = var("TERM");
+    if ["kitty", "foot", "alacritty", "wezterm", "contour"]
+        .iter()
+        .any(|name| term.contains(name))
+    {
+        return true;
+    }
+
+    if var("TERM_PROGRAM") == "WezTerm" {
+        return true;
+    }
+
+    // VTE based terminals (GNOME Terminal, ...) support them since 0.51.2
+    if matches!(var("VTE_VERSION").parse::<u32>(), Ok(version) if version >= 5102) {
+        return true;
+    }
+
+    // `Smulx` is the terminfo extension for the styled underlines
+    tput("Smulx").is_some()
+}
+
+pub(crate) fn supports_hyperlinks() -> bool {
+    let var = |name| std::env::var(name).unwrap_or_default();
+
+    let term = var("TERM");
+    if ["kitty", "foot", "alacritty", "wezterm", "contour"]
+        .iter()
+        .any(|name| term.contains(name))
+    {
+        return true;
+    }
+
+    if ["WezTerm", "iTerm.app", "vscode"].contains(&var("TERM_PROGRAM").as_str()) {
+        return true;
+    }
+
+    // VTE based terminals (GNOME Terminal, ...) support them since 0.50
+    matches!(var("VTE_VERSION").parse::<u32>(), Ok(version) if version >= 5000)
+}
+
+/// Returns the terminfo `colors` capability as determined by tput.
+fn tput_colors() -> Option<u32> {
+    tput("colors")?.trim().parse().ok()
+}
+
+/// Returns the default foreground color of the terminal.
+///
+/// The terminal is queried with `OSC 10` and the reported color is returned as
+/// [`Color::Rgb`](enum.Color.html#variant.Rgb).
+///
+/// # Notes
+///
+/// * An error is returned if the terminal does not answer within 2 seconds (the query is not
+///   supported by all terminals).
+/// * On Windows, the original console color is returned.
+pub fn default_foreground_color() -> Result<Color> {
+    query_default_color(10)
+}
+
+/// Returns the default background color of the terminal.
+///
+/// The terminal is queried with `OSC 11` and the reported color is returned as
+/// [`Color::Rgb`](enum.Color.html#variant.Rgb).
+///
+/// # Notes
+///
+/// * An error is returned if the terminal does not answer within 2 seconds (the query is not
+///   supported by all terminals).
+/// * On Windows, the original console color is returned.
+pub fn default_background_color() -> Result<Color> {
+    query_default_color(11)
+}
+
+fn query_default_color(code: u8) -> Result<Color> {
+    if is_raw_mode_enabled() {
+        read_default_color_raw(code)
+    } else {
+        enable_raw_mode()?;
+        let color = read_default_color_raw(code);
+        disable_raw_mode()?;
+        color
+    }
+}
+
+fn read_default_color_raw(code: u8) -> Result<Color> {
+    // Use `ESC ] Ps ; ? ESC \` to retrieve the default color.
+    query_control_string(format!("\x1B]{};?\x1B\\", code).as_bytes(), || loop {
+        match poll_internal(Some(Duration::from_millis(2000)), &DefaultColorFilter) {
+            Ok(true) => match read_internal(&DefaultColorFilter) {
+                Ok(InternalEvent::DefaultColor(Colored::ForegroundColor(color))) if code == 10 => {
+                    return Ok(color);
+                }
+                Ok(InternalEvent::DefaultColor(Colored::BackgroundColor(color))) if code == 11 => {
+                    return Ok(color);
+                }
+                _ => {}
+            },
+            Ok(false) => {
+                return Err(Error::other(
+                    "The default color could not be read within a normal duration",
+                )
+                .into());
+            }
+            Err(_) => {}
+        }
+    })
+}
@@ -6,7 +6,7 @@ use winapi::um::wincon;
 
 use crate::Result;
 
-use super::super::{Color, Colored};
+use super::super::{Attribute, Color, ColorSupport, Colored, ContentStyle};
 
 const FG_GREEN: u16 = wincon::FOREGROUND_GREEN;
 const FG_RED: u16 = wincon::FOREGROUND_RED;
@@ -18,6 +18,9 @@ const BG_RED: u16 = wincon::BACKGROUND_RED;
 const BG_BLUE: u16 = wincon::BACKGROUND_BLUE;
 const BG_INTENSITY: u16 = wincon::BACKGROUND_INTENSITY;
 
+const UNDERSCORE: u16 = wincon::COMMON_LVB_UNDERSCORE;
+const REVERSE_VIDEO: u16 = wincon::COMMON_LVB_REVERSE_VIDEO;
+
 pub(crate) fn set_foreground_color(fg_color: Color) -> Result<()> {
     init_console_color()?;
 
@@ -31,12 +34,12 @@ pub(crate) fn set_foreground_color(fg_color: Color) -> Result<()> {
     let mut color: u16;
     let attrs = csbi.attributes();
     let bg_color = attrs & 0x0070;
-    color = color_value | bg_color;
+    color = color_value | bg_color | (attrs & (UNDERSCORE | REVERSE_VIDEO));
 
     // background intensity is a separate value in attrs,
     // wee need to check if this was applied to the current bg color.
-    if (attrs & wincon::BACKGROUND_INTENSITY as u16) != 0 {
-        color |= wincon::BACKGROUND_INTENSITY as u16;
+    if (attrs & wincon::BACKGROUND_INTENSITY) != 0 {
+        color |= wincon::BACKGROUND_INTENSITY;
     }
 
     Console::from(screen_buffer.handle().clone()).set_text_attribute(color)?;
@@ -56,18 +59,92 @@ pub(crate) fn set_background_color(bg_color: Color) -> Result<()> {
     let mut color: u16;
     let attrs = csbi.attributes();
     let fg_color = attrs & 0x0007;
-    color = fg_color | color_value;
+    color = fg_color | color_value | (attrs & (UNDERSCORE | REVERSE_VIDEO));
 
     // Foreground intensity is a separate value in attrs,
     // So we need to check if this was applied to the current fg color.
-    if (attrs & wincon::FOREGROUND_INTENSITY as u16) != 0 {
-        color |= wincon::FOREGROUND_INTENSITY as u16;
+    if (attrs & wincon::FOREGROUND_INTENSITY) != 0 {
+        color |= wincon::FOREGROUND_INTENSITY;
     }
 
     Console::from(screen_buffer.handle().clone()).set_text_attribute(color)?;
     Ok(())
 }
 
+pub(crate) fn set_attribute(attribute: Attribute) -> Result<()> {
+    init_console_color()?;
+
+    let screen_buffer = ScreenBuffer::current()?;
+    let attrs = screen_buffer.info()?.attributes();
+
+    let new_attrs = apply_attribute(attrs, attribute);
+
+    if new_attrs != attrs {
+        Console::from(screen_buffer.handle().clone()).set_text_attribute(new_attrs)?;
+    }
+    Ok(())
+}
+
+/// Sets the colors and attributes of the given style with a single attribute word.
+pub(crate) fn set_style(style: &ContentStyle) -> Result<()> {
+    init_console_color()?;
+
+    let screen_buffer = ScreenBuffer::current()?;
+    let attrs = apply_style(screen_buffer.info()?.attributes(), style);
+
+    Console::from(screen_buffer.handle().clone()).set_text_attribute(attrs)?;
+    Ok(())
+}
+
+/// Returns the console attributes with the given style applied.
+fn apply_style(mut attrs: u16, style: &ContentStyle) -> u16 {
+    // `Reset` replaces everything, the colors and the other attributes are applied on top of it
+    if style.attributes.has(Attribute::Reset) {
+        attrs = apply_attribute(attrs, Attribute::Reset);
+    }
+    if let Some(fg_color) = style.foreground_color {
+        attrs = (attrs & !0x000F) | (u16::from(Colored::ForegroundColor(fg_color)) & 0x000F);
+    }
+    if let Some(bg_color) = style.background_color {
+        attrs = (attrs & !0x00F0) | (u16::from(Colored::BackgroundColor(bg_color)) & 0x00F0);
+    }
+    // The attributes are applied after the colors, so that bold isn't cleared by the foreground
+    // color. Turning bold off must not darken a bright foreground color though.
+    let keeps_intensity = |attribute| {
+        style.foreground_color.is_some()
+            && matches!(attribute, Attribute::NoBold | Attribute::NormalIntensity)
+    };
+    for attribute in Attribute::iterator() {
+        if attribute != Attribute::Reset
+            && style.attributes.has(attribute)
+            && !keeps_intensity(attribute)
+        {
+            attrs = apply_attribute(attrs, attribute);
+        }
+    }
+    attrs
+}
+
+/// Returns the console attributes with the given attribute applied.
+fn apply_attribute(attrs: u16, attribute: Attribute) -> u16 {
+    match attribute {
+        Attribute::Reset => original_console_color(),
+        // The WinAPI has no bold text, the closest thing is a bright foreground color.
+        Attribute::Bold => attrs | FG_INTENSITY,
+        Attribute::NoBold | Attribute::NormalIntensity => attrs & !FG_INTENSITY,
+        Attribute::Underlined
+        | Attribute::DoubleUnderlined
+        | Attribute::Undercurled
+        | Attribute::Underdotted
+        | Attribute::Underdashed => attrs | UNDERSCORE,
+        Attribute::NoUnderline => attrs & !UNDERSCORE,
+        Attribute::Reverse => attrs | REVERSE_VIDEO,
+        Attribute::NoReverse => attrs & !REVERSE_VIDEO,
+        // Other attributes are not supported by the WinAPI.
+        _ => attrs,
+    }
+}
+
 pub(crate) fn reset() -> Result<()> {
     if let Ok(original_color) = u16::try_from(ORIGINAL_CONSOLE_COLOR.load(Ordering::Relaxed)) {
         Console::from(Handle::new(HandleType::CurrentOutputHandle)?)
@@ -77,6 +154,77 @@ pub(crate) fn reset() -> Result<()> {
     Ok(())
 }
 
+/// Returns the color support of the console.
+pub(crate) fn color_support() -> ColorSupport {
+    // Consoles supporting virtual terminal sequences (Windows 10) do support 24-bit colors,
+    // the legacy WinAPI console is limited to the 16 base colors.
+    if crate::ansi_support::supports_ansi() {
+        ColorSupport::TrueColor
+    } else {
+        ColorSupport::Ansi16
+    }
+}
+
+/// Returns the default foreground color of the console.
+///
+/// The foreground color the console had before crossterm changed it is returned as one of the 16
+/// console colors (e.g. [`Color::DarkBlue`](enum.Color.html#variant.DarkBlue)).
+///
+/// # Notes
+///
+/// * On UNIX, the terminal is queried with `OSC 10` and the reported color is returned as
+///   [`Color::Rgb`](enum.Color.html#variant.Rgb).
+pub fn default_foreground_color() -> Result<Color> {
+    init_console_color()?;
+    Ok(console_color(original_console_color() & 0x000F))
+}
+
+/// Returns the default background color of the console.
+///
+/// The background color the console had before crossterm changed it is returned as one of the 16
+/// console colors (e.g. [`Color::DarkBlue`](enum.Color.html#variant.DarkBlue)).
+///
+/// # Notes
+///
+/// * On UNIX, the terminal is queried with `OSC 11` and the reported color is returned as
+///   [`Color::Rgb`](enum.Color.html#variant.Rgb).
+pub fn default_background_color() -> Result<Color> {
+    init_console_color()?;
+    Ok(console_color((original_console_color() & 0x00F0) >> 4))
+}
+
+/// Returns the `Color` of the given (foreground) console color value.
+fn console_color(value: u16) -> Color {
+    match value {
+        0 => Color::Black,
+        FG_BLUE => Color::DarkBlue,
+        FG_GREEN => Color::DarkGreen,
+        v if v == FG_GREEN | FG_BLUE => Color::DarkCyan,
+        FG_RED => Color::DarkRed,
+        v if v == FG_RED | FG_BLUE => Color::DarkMagenta,
+        v if v == FG_RED | FG_GREEN => Color::DarkYellow,
+        v if v == FG_RED | FG_GREEN | FG_BLUE => Color::Grey,
+        FG_INTENSITY => Color::DarkGrey,
+        v if v == FG_INTENSITY | FG_BLUE => Color::Blue,
+        v if v == FG_INTENSITY | FG_GREEN => Color::Green,
+        v if v == FG_INTENSITY | FG_GREEN | FG_BLUE => Color::Cyan,
+        v if v == FG_INTENSITY | FG_RED => Color::Red,
+        v if v == FG_INTENSITY | FG_RED | FG_BLUE => Color::Magenta,
+        v if v == FG_INTENSITY | FG_RED | FG_GREEN => Color::Yellow,
+        _ => Color::White,
+    }
+}
+
+pub(crate) fn supports_underline_styles() -> bool {
+    // Windows Terminal supports them, the console host does not.
+    std::env::var("WT_SESSION").is_ok()
+}
+
+pub(crate) fn supports_hyperlinks() -> bool {
+    // Windows Terminal supports them, the console host does not.
+    std::env::var("WT_SESSION").is_ok()
+}
+
 /// Initializes the default console color. It will will be skipped if it has already been initialized.
 pub(crate) fn init_console_color() -> Result<()> {
     if ORIGINAL_CONSOLE_COLOR.load(Ordering::Relaxed) == u32::MAX {
@@ -132,9 +280,10 @@ impl From<Colored> for u16 {
                         original_color & !REMOVE_BG_MASK
                     }
 
-                    /* WinAPI will be used for systems that do not support ANSI, those are windows version less then 10. RGB and 255 (AnsiBValue) colors are not supported in that case.*/
-                    Color::Rgb { .. } => 0,
-                    Color::AnsiValue(_val) => 0,
+                    /* WinAPI will be used for systems that do not support ANSI, those are windows version less then 10. RGB and 255 (AnsiValue) colors are approximated by the closest console color in that case.*/
+                    Color::Rgb { .. } | Color::AnsiValue(_) => {
+                        Colored::ForegroundColor(color.downgrade(ColorSupport::Ansi16)).into()
+                    }
                 }
             }
             Colored::BackgroundColor(color) => {
@@ -164,11 +313,14 @@ impl From<Colored> for u16 {
 
                         original_color & !REMOVE_FG_MASK
                     }
-                    /* WinAPI will be used for systems that do not support ANSI, those are windows version less then 10. RGB and 255 (AnsiBValue) colors are not supported in that case.*/
-                    Color::Rgb { .. } => 0,
-                    Color::AnsiValue(_val) => 0,
+                    /* WinAPI will be used for systems that do not support ANSI, those are windows version less then 10. RGB and 255 (AnsiValue) colors are approximated by the closest console color in that case.*/
+                    Color::Rgb { .. } | Color::AnsiValue(_) => {
+                        Colored::BackgroundColor(color.downgrade(ColorSupport::Ansi16)).into()
+                    }
                 }
             }
+            // the underline color can't be changed with the WinAPI.
+            Colored::UnderlineColor(_) => 0,
         }
     }
 }
@@ -180,9 +332,20 @@ mod tests {
     use crate::style::sys::windows::set_foreground_color;
 
     use super::{
-        Color, Colored, BG_INTENSITY, BG_RED, FG_INTENSITY, FG_RED, ORIGINAL_CONSOLE_COLOR,
+        apply_style, Attribute, Color, Colored, ContentStyle, BG_BLUE, BG_INTENSITY, BG_RED,
+        FG_INTENSITY, FG_RED, ORIGINAL_CONSOLE_COLOR,
     };
 
+    #[test]
+    fn test_apply_style_keeps_bold_with_foreground_color() {
+        let style = ContentStyle::new()
+            .foreground(Color::DarkRed)
+            .background(Color::DarkBlue)
+            .attribute(Attribute::Bold);
+
+        assert_eq!(apply_style(0, &style), FG_RED | FG_INTENSITY | BG_BLUE);
+    }
+
     #[test]
     fn test_parse_fg_color() {
         let colored = Colored::ForegroundColor(Color::Red);
@@ -195,6 +358,34 @@ mod tests {
         assert_eq!(Into::<u16>::into(colored), BG_INTENSITY | BG_RED);
     }
 
+    #[test]
+    fn test_parse_rgb_color_to_closest_console_color() {
+        let colored = Colored::ForegroundColor(Color::Rgb {
+            r: 250,
+            g: 10,
+            b: 5,
+        });
+        assert_eq!(Into::<u16>::into(colored), FG_INTENSITY | FG_RED);
+
+        let colored = Colored::BackgroundColor(Color::Rgb { r: 120, g: 0, b: 0 });
+        assert_eq!(Into::<u16>::into(colored), BG_RED);
+    }
+
+    #[test]
+    fn test_parse_ansi_value_to_closest_console_color() {
+        // 9 is the bright red of the base colors
+        let colored = Colored::ForegroundColor(Color::AnsiValue(9));
+        assert_eq!(Into::<u16>::into(colored), FG_INTENSITY | FG_RED);
+
+        // 196 is the pure red of the color cube
+        let colored = Colored::ForegroundColor(Color::AnsiValue(196));
+        assert_eq!(Into::<u16>::into(colored), FG_INTENSITY | FG_RED);
+
+        // 88 is a dark red of the color cube
+        let colored = Colored::BackgroundColor(Color::AnsiValue(88));
+        assert_eq!(Into::<u16>::into(colored), BG_RED);
+    }
+
     #[test]
     fn test_original_console_color_is_set() {
         assert_eq!(ORIGINAL_CONSOLE_COLOR.load(Ordering::Relaxed), u32::MAX);
@@ -1,6 +1,18 @@
-pub use self::{attribute::Attribute, color::Color, colored::Colored, colors::Colors};
+pub use self::{
+    attribute::Attribute,
+    color::{Color, ParseColorError},
+    color_choice::ColorChoice,
+    color_support::ColorSupport,
+    colored::Colored,
+    colors::Colors,
+    theme::Theme,
+};
 
 mod attribute;
 mod color;
+mod color_choice;
+mod color_names;
+mod color_support;
 mod colored;
 mod colors;
+mod theme;
@@ -20,6 +20,8 @@ macro_rules! Attribute {
         /// # Platform-specific Notes
         ///
         /// * Only UNIX and Windows 10 terminals do support text attributes.
+        /// * Windows versions without ANSI support emulate `Reset`, `Bold` (bright foreground),
+        ///   `Underlined` and `Reverse` (and the matching `No*` attributes) only.
         /// * Keep in mind that not all terminals support all attributes.
         /// * Crossterm implements almost all attributes listed in the
         ///   [SGR parameters](https://en.wikipedia.org/wiki/ANSI_escape_code#SGR_parameters).
@@ -41,6 +43,10 @@ macro_rules! Attribute {
         /// | `Framed` | ? | ? | Not widely supported. |
         /// | `Encircled` | ? | ? | This should turn on the encircled attribute. |
         /// | `OverLined` | ? | ? | This should draw a line at the top of the text. |
+        /// | `DoubleUnderlined` | ? | ? | Plain underline if not supported, see `supports_underline_styles`. |
+        /// | `Undercurled` | ? | ? | Plain underline if not supported, see `supports_underline_styles`. |
+        /// | `Underdotted` | ? | ? | Plain underline if not supported, see `supports_underline_styles`. |
+        /// | `Underdashed` | ? | ? | Plain underline if not supported, see `supports_underline_styles`. |
         ///
         /// # Examples
         ///
@@ -67,6 +73,7 @@ macro_rules! Attribute {
         /// ```
         #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
         #[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
+        #[non_exhaustive]
         pub enum Attribute {
             $(
                 $(#[$inner $($args)*])*
@@ -88,7 +95,6 @@ macro_rules! Attribute {
     }
 }
 
-#[non_exhaustive]
 Attribute! {
     /// Resets all the attributes.
     Reset = 0,
@@ -100,6 +106,8 @@ Attribute! {
     Italic = 3,
     /// Underlines the text.
     Underlined = 4,
+
+
     /// Makes the text blinking (< 150 per minute).
     SlowBlink = 5,
     /// Makes the text blinking (>= 150 per minute).
@@ -140,6 +148,17 @@ Attribute! {
     NotFramedOrEncircled = 54,
     /// Turns off the `OverLined` attribute.
     NotOverLined = 55,
+
+    // Other types of underlining. They're placed last to keep the indexes (and the `Attributes`
+    // bits) of the other attributes, the style is written with `Attribute::sgr_param`.
+    /// Double underlines the text.
+    DoubleUnderlined = 4,
+    /// Undercurls the text.
+    Undercurled = 4,
+    /// Underdots the text.
+    Underdotted = 4,
+    /// Underdashes the text.
+    Underdashed = 4,
 }
 
 impl Display for Attribute {
@@ -162,8 +181,127 @@ impl Attribute {
     }
     /// Returns the SGR attribute value.
     ///
+    /// The underline styles (e.g. `Undercurled`) return the `Underlined` value, `4`. Use
+    /// [`sgr_param`](#method.sgr_param) to get the parameter which selects the style.
+    ///
     /// See https://en.wikipedia.org/wiki/ANSI_escape_code#SGR_parameters
     pub fn sgr(self) -> i16 {
         SGR[self as usize]
     }
+
+    /// Returns the SGR parameter written to the terminal, which is `4:n` for the underline
+    /// styles (e.g. `4:3` for `Undercurled`) and the [`sgr`](#method.sgr) value otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crossterm::style::Attribute;
+    ///
+    /// assert_eq!(Attribute::Bold.sgr_param(), "1");
+    /// assert_eq!(Attribute::Undercurled.sgr_param(), "4:3");
+    /// ```
+    pub fn sgr_param(self) -> String {
+        match self.underline_style() {
+            Some(style) => format!("{}:{}", self.sgr(), style),
+            None => self.sgr().to_string(),
+        }
+    }
+
+    /// Returns the attribute which turns this attribute off, without resetting the colors or
+    /// other attributes.
+    ///
+    /// Returns `None` for `Reset` and for the attributes which do turn off other attributes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crossterm::style::Attribute;
+    ///
+    /// assert_eq!(Attribute::Bold.off(), Some(Attribute::NormalIntensity));
+    /// assert_eq!(Attribute::Undercurled.off(), Some(Attribute::NoUnderline));
+    /// assert_eq!(Attribute::NoUnderline.off(), None);
+    /// ```
+    pub fn off(self) -> Option<Attribute> {
+        let attribute = match self {
+            // `NoBold` (21) is interpreted as double underline by a lot of terminals.
+            Attribute::Bold | Attribute::Dim => Attribute::NormalIntensity,
+            Attribute::Italic | Attribute::Fraktur => Attribute::NoItalic,
+            Attribute::Underlined
+            | Attribute::DoubleUnderlined
+            | Attribute::Undercurled
+            | Attribute::Underdotted
+            | Attribute::Underdashed => Attribute::NoUnderline,
+            Attribute::SlowBlink | Attribute::RapidBlink => Attribute::NoBlink,
+            Attribute::Reverse => Attribute::NoReverse,
+            Attribute::Hidden => Attribute::NoHidden,
+            Attribute::CrossedOut => Attribute::NotCrossedOut,
+            Attribute::Framed | Attribute::Encircled => Attribute::NotFramedOrEncircled,
+            Attribute::OverLined => Attribute::NotOverLined,
+            _ => return None,
+        };
+        Some(attribute)
+    }
+
+    /// Returns whether the attribute is one of the extended underline styles (double, curly,
+    /// dotted, dashed).
+    pub(crate) fn is_underline_style(self) -> bool {
+        self.underline_style().is_some()
+    }
+
+    // The `n` of the `4:n` SGR parameter of the extended underline styles.
+    fn underline_style(self) -> Option<u8> {
+        match self {
+            Attribute::DoubleUnderlined => Some(2),
+            Attribute::Undercurled => Some(3),
+            Attribute::Underdotted => Some(4),
+            Attribute::Underdashed => Some(5),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Attribute;
+
+    #[test]
+    fn test_sgr() {
+        assert_eq!(Attribute::Bold.sgr(), 1);
+        assert_eq!(Attribute::Underlined.sgr(), 4);
+        assert_eq!(Attribute::Undercurled.sgr(), 4);
+        assert_eq!(Attribute::NotOverLined.sgr(), 55);
+    }
+
+    #[test]
+    fn test_sgr_param() {
+        assert_eq!(Attribute::Bold.sgr_param(), "1");
+        assert_eq!(Attribute::Underlined.sgr_param(), "4");
+        assert_eq!(Attribute::DoubleUnderlined.sgr_param(), "4:2");
+        assert_eq!(Attribute::Undercurled.sgr_param(), "4:3");
+        assert_eq!(Attribute::Underdotted.sgr_param(), "4:4");
+        assert_eq!(Attribute::Underdashed.sgr_param(), "4:5");
+        assert_eq!(Attribute::NotOverLined.sgr_param(), "55");
+    }
+
+    #[test]
+    fn test_underline_styles_keep_indexes() {
+        // The variants added later must not shift the `Attributes` bits of the existing ones
+        assert_eq!(Attribute::NotOverLined as usize, 23);
+        assert_eq!(Attribute::DoubleUnderlined as usize, 24);
+        assert_eq!(Attribute::Underdashed.bytes(), 1 << 28);
+    }
+
+    #[test]
+    fn test_off() {
+        assert_eq!(Attribute::Bold.off(), Some(Attribute::NormalIntensity));
+        assert_eq!(Attribute::Dim.off(), Some(Attribute::NormalIntensity));
+        assert_eq!(Attribute::Underdashed.off(), Some(Attribute::NoUnderline));
+        assert_eq!(Attribute::RapidBlink.off(), Some(Attribute::NoBlink));
+        assert_eq!(
+            Attribute::Encircled.off(),
+            Some(Attribute::NotFramedOrEncircled)
+        );
+        assert_eq!(Attribute::Reset.off(), None);
+        assert_eq!(Attribute::NoItalic.off(), None);
+    }
 }
@@ -1,9 +1,17 @@
-use std::{convert::AsRef, convert::TryFrom, result::Result, str::FromStr};
+use std::{
+    convert::AsRef,
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+    result::Result,
+    str::FromStr,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::style::parse_next_u8;
+use crate::style::{parse_next_u8, ColorSupport};
+
+use super::color_names::x11_color;
 
 /// Represents a color.
 ///
@@ -80,13 +88,17 @@ pub enum Color {
 
     /// An RGB color. See [RGB color model](https://en.wikipedia.org/wiki/RGB_color_model) for more info.
     ///
-    /// Most UNIX terminals and Windows 10 supported only.
+    /// Most UNIX terminals and Windows 10 supported only. Terminals with a smaller palette
+    /// (including Windows versions without ANSI support) display the closest supported color
+    /// instead, see [`Color::downgrade`](enum.Color.html#method.downgrade).
     /// See [Platform-specific notes](enum.Color.html#platform-specific-notes) for more info.
     Rgb { r: u8, g: u8, b: u8 },
 
     /// An ANSI color. See [256 colors - cheat sheet](https://jonasjacek.github.io/colors/) for more info.
     ///
-    /// Most UNIX terminals and Windows 10 supported only.
+    /// Most UNIX terminals and Windows 10 supported only. Terminals with a smaller palette
+    /// (including Windows versions without ANSI support) display the closest supported color
+    /// instead, see [`Color::downgrade`](enum.Color.html#method.downgrade).
     /// See [Platform-specific notes](enum.Color.html#platform-specific-notes) for more info.
     AnsiValue(u8),
 }
@@ -119,32 +131,7 @@ impl Color {
     pub(crate) fn parse_ansi_iter<'a>(values: &mut impl Iterator<Item = &'a str>) -> Option<Self> {
         let color = match parse_next_u8(values)? {
             // 8 bit colors: `5;<n>`
-            5 => {
-                let n = parse_next_u8(values)?;
-
-                use Color::*;
-                [
-                    Black,       // 0
-                    DarkRed,     // 1
-                    DarkGreen,   // 2
-                    DarkYellow,  // 3
-                    DarkBlue,    // 4
-                    DarkMagenta, // 5
-                    DarkCyan,    // 6
-                    Grey,        // 7
-                    DarkGrey,    // 8
-                    Red,         // 9
-                    Green,       // 10
-                    Yellow,      // 11
-                    Blue,        // 12
-                    Magenta,     // 13
-                    Cyan,        // 14
-                    White,       // 15
-                ]
-                .get(n as usize)
-                .copied()
-                .unwrap_or(Color::AnsiValue(n))
-            }
+            5 => Color::from_ansi_value(parse_next_u8(values)?),
 
             // 24 bit colors: `2;<r>;<g>;<b>`
             2 => Color::Rgb {
@@ -163,11 +150,467 @@ impl Color {
     }
 }
 
+/// The base colors in the order of the first 16 ANSI color values.
+const ANSI_BASE_COLORS: [Color; 16] = [
+    Color::Black,       // 0
+    Color::DarkRed,     // 1
+    Color::DarkGreen,   // 2
+    Color::DarkYellow,  // 3
+    Color::DarkBlue,    // 4
+    Color::DarkMagenta, // 5
+    Color::DarkCyan,    // 6
+    Color::Grey,        // 7
+    Color::DarkGrey,    // 8
+    Color::Red,         // 9
+    Color::Green,       // 10
+    Color::Yellow,      // 11
+    Color::Blue,        // 12
+    Color::Magenta,     // 13
+    Color::Cyan,        // 14
+    Color::White,       // 15
+];
+
+/// The (approximate) RGB values of the base colors, see `ANSI_BASE_COLORS` for the order.
+///
+/// Terminals let users configure these, the values of the Windows console are used here.
+const ANSI_BASE_COLORS_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (128, 0, 0),
+    (0, 128, 0),
+    (128, 128, 0),
+    (0, 0, 128),
+    (128, 0, 128),
+    (0, 128, 128),
+    (192, 192, 192),
+    (128, 128, 128),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (0, 0, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// The levels of the xterm 6x6x6 color cube (ANSI values 16 - 231).
+const COLOR_CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn rgb_distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> i32 {
+    let dr = i32::from(r1) - i32::from(r2);
+    let dg = i32::from(g1) - i32::from(g2);
+    let db = i32::from(b1) - i32::from(b2);
+    dr * dr + dg * dg + db * db
+}
+
+/// Returns the RGB value of the given ANSI (256 colors) value.
+fn ansi_value_to_rgb(value: u8) -> (u8, u8, u8) {
+    match value {
+        0..=15 => ANSI_BASE_COLORS_RGB[value as usize],
+        16..=231 => {
+            let value = value - 16;
+            (
+                COLOR_CUBE_LEVELS[(value / 36) as usize],
+                COLOR_CUBE_LEVELS[((value / 6) % 6) as usize],
+                COLOR_CUBE_LEVELS[(value % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + (value - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+/// Returns the base color which is the closest to the given RGB value.
+fn closest_base_color(rgb: (u8, u8, u8)) -> Color {
+    let index = (0..16)
+        .min_by_key(|&i| rgb_distance(ANSI_BASE_COLORS_RGB[i], rgb))
+        .unwrap_or(0);
+    ANSI_BASE_COLORS[index]
+}
+
+/// Returns the ANSI value (color cube or grayscale ramp) which is the closest to the given RGB
+/// value.
+fn closest_ansi_value(rgb: (u8, u8, u8)) -> u8 {
+    let cube_index = |c: u8| {
+        (0..6)
+            .min_by_key(|&i| (i32::from(COLOR_CUBE_LEVELS[i]) - i32::from(c)).abs())
+            .unwrap_or(0) as u8
+    };
+    let (r, g, b) = (cube_index(rgb.0), cube_index(rgb.1), cube_index(rgb.2));
+    let cube_value = 16 + 36 * r + 6 * g + b;
+
+    let average = (u16::from(rgb.0) + u16::from(rgb.1) + u16::from(rgb.2)) / 3;
+    let gray_value = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+
+    if rgb_distance(ansi_value_to_rgb(gray_value), rgb)
+        < rgb_distance(ansi_value_to_rgb(cube_value), rgb)
+    {
+        gray_value
+    } else {
+        cube_value
+    }
+}
+
+impl Color {
+    /// Converts the color to the closest color a terminal with the given
+    /// [`ColorSupport`](enum.ColorSupport.html) is able to display.
+    ///
+    /// `Rgb` colors are converted to `AnsiValue` colors for `Ansi256` and `Rgb` and `AnsiValue`
+    /// colors are converted to the 16 base colors for `Ansi16`. Other colors are returned as they
+    /// are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crossterm::style::{Color, ColorSupport};
+    ///
+    /// let orange = Color::Rgb { r: 255, g: 135, b: 0 };
+    ///
+    /// assert_eq!(orange.downgrade(ColorSupport::TrueColor), orange);
+    /// assert_eq!(orange.downgrade(ColorSupport::Ansi256), Color::AnsiValue(208));
+    /// assert_eq!(orange.downgrade(ColorSupport::Ansi16), Color::Yellow);
+    /// ```
+    pub fn downgrade(self, support: ColorSupport) -> Color {
+        match (self, support) {
+            (Color::Rgb { r, g, b }, ColorSupport::Ansi256) => {
+                Color::AnsiValue(closest_ansi_value((r, g, b)))
+            }
+            (Color::Rgb { r, g, b }, ColorSupport::Ansi16) => closest_base_color((r, g, b)),
+            (Color::AnsiValue(value), ColorSupport::Ansi16) => {
+                match ANSI_BASE_COLORS.get(value as usize) {
+                    Some(color) => *color,
+                    None => closest_base_color(ansi_value_to_rgb(value)),
+                }
+            }
+            _ => self,
+        }
+    }
+}
+
+impl Color {
+    /// Returns the color of the given ANSI (256 colors) value, the first 16 values are the base
+    /// colors.
+    pub(crate) fn from_ansi_value(value: u8) -> Color {
+        ANSI_BASE_COLORS
+            .get(value as usize)
+            .copied()
+            .unwrap_or(Color::AnsiValue(value))
+    }
+
+    /// Returns the ANSI value (`0` - `15`) of a base color.
+    pub(crate) fn base_index(self) -> Option<u8> {
+        ANSI_BASE_COLORS
+            .iter()
+            .position(|base_color| *base_color == self)
+            .map(|index| index as u8)
+    }
+
+    /// Returns the (approximate) RGB value of the color or `None` for `Color::Reset`.
+    pub(crate) fn to_rgb(self) -> Option<(u8, u8, u8)> {
+        match self {
+            Color::Reset => None,
+            Color::Rgb { r, g, b } => Some((r, g, b)),
+            Color::AnsiValue(value) => Some(ansi_value_to_rgb(value)),
+            color => color
+                .base_index()
+                .map(|index| ANSI_BASE_COLORS_RGB[index as usize]),
+        }
+    }
+}
+
+impl Color {
+    /// Creates a `Color::Rgb` from HSL (hue, saturation, lightness) values.
+    ///
+    /// The hue is given in degrees (wrapped to `0..360`), saturation and lightness are in the range
+    /// `0.0..=1.0` and clamped otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crossterm::style::Color;
+    ///
+    /// assert_eq!(Color::from_hsl(0.0, 1.0, 0.5), Color::Rgb { r: 255, g: 0, b: 0 });
+    /// assert_eq!(Color::from_hsl(120.0, 1.0, 0.25), Color::Rgb { r: 0, g: 128, b: 0 });
+    /// ```
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Color {
+        let s = s.clamp(0.0, 1.0);
+        let l = l.clamp(0.0, 1.0);
+
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        from_hue_chroma(h, chroma, l - chroma / 2.0)
+    }
+
+    /// Creates a `Color::Rgb` from HSV (hue, saturation, value) values.
+    ///
+    /// The hue is given in degrees (wrapped to `0..360`), saturation and value are in the range
+    /// `0.0..=1.0` and clamped otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crossterm::style::Color;
+    ///
+    /// assert_eq!(Color::from_hsv(240.0, 1.0, 1.0), Color::Rgb { r: 0, g: 0, b: 255 });
+    /// assert_eq!(Color::from_hsv(0.0, 0.0, 0.5), Color::Rgb { r: 128, g: 128, b: 128 });
+    /// ```
+    pub fn from_hsv(h: f32, s: f32, v: f32) -> Color {
+        let s = s.clamp(0.0, 1.0);
+        let v = v.clamp(0.0, 1.0);
+
+        let chroma = v * s;
+        from_hue_chroma(h, chroma, v - chroma)
+    }
+
+    /// Returns the HSL (hue in degrees, saturation, lightness) values of the color or `None` for
+    /// `Color::Reset`.
+    ///
+    /// Base and ANSI colors are converted using their approximate RGB value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crossterm::style::Color;
+    ///
+    /// assert_eq!(Color::Rgb { r: 255, g: 0, b: 0 }.to_hsl(), Some((0.0, 1.0, 0.5)));
+    /// assert_eq!(Color::Reset.to_hsl(), None);
+    /// ```
+    pub fn to_hsl(self) -> Option<(f32, f32, f32)> {
+        let (hue, max, min) = hue_max_min(self.to_rgb()?);
+
+        let l = (max + min) / 2.0;
+        let s = if max == min {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * l - 1.0).abs())
+        };
+        Some((hue, s, l))
+    }
+
+    /// Returns the HSV (hue in degrees, saturation, value) values of the color or `None` for
+    /// `Color::Reset`.
+    ///
+    /// Base and ANSI colors are converted using their approximate RGB value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crossterm::style::Color;
+    ///
+    /// assert_eq!(Color::Rgb { r: 0, g: 0, b: 255 }.to_hsv(), Some((240.0, 1.0, 1.0)));
+    /// assert_eq!(Color::Reset.to_hsv(), None);
+    /// ```
+    pub fn to_hsv(self) -> Option<(f32, f32, f32)> {
+        let (hue, max, min) = hue_max_min(self.to_rgb()?);
+
+        let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+        Some((hue, s, max))
+    }
+}
+
+impl Color {
+    /// Linearly interpolates between this and the given color.
+    ///
+    /// `t` is clamped to `0.0..=1.0`, where `0.0` returns this color and `1.0` the given color (both
+    /// as `Color::Rgb`). Base and ANSI colors are interpolated using their approximate RGB value.
+    /// Returns `None` if one of the colors is `Color::Reset`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crossterm::style::Color;
+    ///
+    /// let black = Color::Rgb { r: 0, g: 0, b: 0 };
+    /// let white = Color::Rgb { r: 255, g: 255, b: 255 };
+    ///
+    /// assert_eq!(black.lerp(white, 0.5), Some(Color::Rgb { r: 128, g: 128, b: 128 }));
+    /// assert_eq!(black.lerp(Color::Reset, 0.5), None);
+    /// ```
+    pub fn lerp(self, to: Color, t: f32) -> Option<Color> {
+        let (r1, g1, b1) = self.to_rgb()?;
+        let (r2, g2, b2) = to.to_rgb()?;
+        let t = t.clamp(0.0, 1.0);
+
+        let component =
+            |c1: u8, c2: u8| (f32::from(c1) + (f32::from(c2) - f32::from(c1)) * t).round() as u8;
+        Some(Color::Rgb {
+            r: component(r1, r2),
+            g: component(g1, g2),
+            b: component(b1, b2),
+        })
+    }
+
+    /// Returns `steps` colors evenly interpolated from this to the given color, including both
+    /// colors, e.g. for fades and loading animations.
+    ///
+    /// See [`Color::lerp`](enum.Color.html#method.lerp) for more info. Returns `None` if one of
+    /// the colors is `Color::Reset`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crossterm::style::Color;
+    ///
+    /// let black = Color::Rgb { r: 0, g: 0, b: 0 };
+    /// let red = Color::Rgb { r: 255, g: 0, b: 0 };
+    ///
+    /// assert_eq!(
+    ///     black.lerp_steps(red, 3),
+    ///     Some(vec![black, Color::Rgb { r: 128, g: 0, b: 0 }, red])
+    /// );
+    /// ```
+    pub fn lerp_steps(self, to: Color, steps: usize) -> Option<Vec<Color>> {
+        let last = steps.saturating_sub(1).max(1) as f32;
+        (0..steps)
+            .map(|step| self.lerp(to, step as f32 / last))
+            .collect()
+    }
+}
+
+impl Color {
+    /// Returns the foreground color (black or white) which is the most readable on this
+    /// background color.
+    ///
+    /// The color with the highest contrast ratio to the (approximate) RGB value of the background
+    /// is chosen, based on its [relative luminance](https://www.w3.org/TR/WCAG20/#relativeluminancedef).
+    /// Returns `Color::Reset` for `Color::Reset`, as the color of the terminal background is unknown.
+    ///
+    /// See also [`Colors::contrasting`](struct.Colors.html#method.contrasting).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use crossterm::style::Color;
+    ///
+    /// assert_eq!(Color::contrast_fg(Color::Yellow), Color::Black);
+    /// assert_eq!(Color::DarkBlue.contrast_fg(), Color::White);
+    /// ```
+    pub fn contrast_fg(self) -> Color {
+        let luminance = match self.to_rgb() {
+            Some(rgb) => relative_luminance(rgb),
+            None => return Color::Reset,
+        };
+
+        // The contrast ratios with black and white are (L + 0.05) / 0.05 and 1.05 / (L + 0.05)
+        if (luminance + 0.05) * (luminance + 0.05) > 1.05 * 0.05 {
+            Color::Black
+        } else {
+            Color::White
+        }
+    }
+}
+
+/// Returns the relative luminance (`0.0` - `1.0`) of the sRGB value.
+fn relative_luminance((r, g, b): (u8, u8, u8)) -> f32 {
+    let linear = |c: u8| {
+        let c = f32::from(c) / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// Creates a `Color::Rgb` from the hue (in degrees), the chroma and the value which is added to
+/// all the components (all in the range `0.0..=1.0`).
+fn from_hue_chroma(h: f32, chroma: f32, m: f32) -> Color {
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+
+    let (r, g, b) = match h as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    let component = |c: f32| ((c + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    Color::Rgb {
+        r: component(r),
+        g: component(g),
+        b: component(b),
+    }
+}
+
+/// Returns the hue (in degrees), the maximum and minimum component (in the range `0.0..=1.0`) of
+/// the RGB value.
+fn hue_max_min((r, g, b): (u8, u8, u8)) -> (f32, f32, f32) {
+    let (r, g, b) = (
+        f32::from(r) / 255.0,
+        f32::from(g) / 255.0,
+        f32::from(b) / 255.0,
+    );
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+
+    (hue, max, min)
+}
+
+/// An error which is returned if a string can't be converted to a [`Color`](enum.Color.html).
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// use crossterm::style::Color;
+///
+/// let error = Color::try_from("#12345").unwrap_err();
+///
+/// assert_eq!(error.input(), "#12345");
+/// assert_eq!(error.to_string(), "invalid hex color `#12345`");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseColorError {
+    input: String,
+    hex: bool,
+}
+
+impl ParseColorError {
+    /// Returns the string which couldn't be converted.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.hex {
+            write!(f, "invalid hex color `{}`", self.input)
+        } else {
+            write!(f, "unknown color name `{}`", self.input)
+        }
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
 impl TryFrom<&str> for Color {
-    type Error = ();
+    type Error = ParseColorError;
 
     /// Try to create a `Color` from the string representation. This returns an error if the string does not match.
+    ///
+    /// * The base color names (e.g. `red`, `dark_red`) are converted to the base colors.
+    /// * RGB colors can be written in hex as `#ff8800`, `0xff8800` or `#f80`.
+    /// * The X11 color names (e.g. `dodgerblue`, `Dark Orange`, `gray50`) are converted to RGB
+    ///   colors.
     fn try_from(src: &str) -> Result<Self, Self::Error> {
+        let input = src;
         let src = src.to_lowercase();
 
         match src.as_ref() {
@@ -187,11 +630,45 @@ impl TryFrom<&str> for Color {
             "dark_cyan" => Ok(Color::DarkCyan),
             "white" => Ok(Color::White),
             "grey" => Ok(Color::Grey),
-            _ => Err(()),
+            _ => match src.strip_prefix('#').or_else(|| src.strip_prefix("0x")) {
+                Some(hex) => parse_hex(hex).ok_or_else(|| ParseColorError {
+                    input: input.to_string(),
+                    hex: true,
+                }),
+                None => x11_color(&src)
+                    .map(|(r, g, b)| Color::Rgb { r, g, b })
+                    .ok_or_else(|| ParseColorError {
+                        input: input.to_string(),
+                        hex: false,
+                    }),
+            },
         }
     }
 }
 
+/// Parses the `rrggbb` (or the short `rgb`) hex representation of an RGB color.
+fn parse_hex(hex: &str) -> Option<Color> {
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let (r, g, b) = match hex.len() {
+        6 => (
+            u8::from_str_radix(&hex[0..2], 16).ok()?,
+            u8::from_str_radix(&hex[2..4], 16).ok()?,
+            u8::from_str_radix(&hex[4..6], 16).ok()?,
+        ),
+        3 => (
+            u8::from_str_radix(&hex[0..1], 16).ok()? * 0x11,
+            u8::from_str_radix(&hex[1..2], 16).ok()? * 0x11,
+            u8::from_str_radix(&hex[2..3], 16).ok()? * 0x11,
+        ),
+        _ => return None,
+    };
+
+    Some(Color::Rgb { r, g, b })
+}
+
 impl FromStr for Color {
     type Err = ();
 
@@ -216,7 +693,137 @@ impl From<(u8, u8, u8)> for Color {
 
 #[cfg(test)]
 mod tests {
-    use super::Color;
+    use std::convert::TryFrom;
+
+    use super::{Color, ColorSupport};
+
+    #[test]
+    fn test_hsl_hsv_round_trip() {
+        let colors = [
+            Color::Rgb {
+                r: 255,
+                g: 135,
+                b: 0,
+            },
+            Color::Rgb {
+                r: 12,
+                g: 200,
+                b: 99,
+            },
+            Color::Rgb {
+                r: 40,
+                g: 40,
+                b: 40,
+            },
+            Color::Rgb {
+                r: 130,
+                g: 20,
+                b: 240,
+            },
+        ];
+
+        for color in colors.iter().copied() {
+            let (h, s, l) = color.to_hsl().unwrap();
+            assert_eq!(Color::from_hsl(h, s, l), color);
+
+            let (h, s, v) = color.to_hsv().unwrap();
+            assert_eq!(Color::from_hsv(h, s, v), color);
+        }
+    }
+
+    #[test]
+    fn test_lerp() {
+        let from = Color::Rgb {
+            r: 10,
+            g: 200,
+            b: 0,
+        };
+        let to = Color::Rgb {
+            r: 20,
+            g: 100,
+            b: 255,
+        };
+
+        assert_eq!(from.lerp(to, 0.0), Some(from));
+        assert_eq!(from.lerp(to, 1.0), Some(to));
+        assert_eq!(from.lerp(to, 2.0), Some(to));
+        assert_eq!(
+            from.lerp(to, 0.5),
+            Some(Color::Rgb {
+                r: 15,
+                g: 150,
+                b: 128
+            })
+        );
+        assert_eq!(
+            Color::Black.lerp(Color::White, 1.0),
+            Some(Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 255
+            })
+        );
+        assert_eq!(Color::Reset.lerp(to, 0.5), None);
+
+        assert_eq!(from.lerp_steps(to, 0), Some(vec![]));
+        assert_eq!(from.lerp_steps(to, 1), Some(vec![from]));
+        assert_eq!(from.lerp_steps(to, 5).map(|colors| colors.len()), Some(5));
+    }
+
+    #[test]
+    fn test_contrast_fg() {
+        let rgb = |r, g, b| Color::Rgb { r, g, b };
+
+        assert_eq!(rgb(255, 255, 255).contrast_fg(), Color::Black);
+        assert_eq!(rgb(0, 0, 0).contrast_fg(), Color::White);
+        assert_eq!(rgb(0, 255, 0).contrast_fg(), Color::Black);
+        assert_eq!(rgb(200, 0, 0).contrast_fg(), Color::White);
+        assert_eq!(Color::Grey.contrast_fg(), Color::Black);
+        assert_eq!(Color::DarkRed.contrast_fg(), Color::White);
+        assert_eq!(Color::AnsiValue(231).contrast_fg(), Color::Black);
+        assert_eq!(Color::Reset.contrast_fg(), Color::Reset);
+    }
+
+    #[test]
+    fn test_from_hsl_hsv() {
+        assert_eq!(
+            Color::from_hsl(60.0, 1.0, 0.5),
+            Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 0
+            }
+        );
+        assert_eq!(
+            Color::from_hsl(-60.0, 1.0, 0.5),
+            Color::Rgb {
+                r: 255,
+                g: 0,
+                b: 255
+            }
+        );
+        assert_eq!(
+            Color::from_hsl(0.0, 0.0, 1.0),
+            Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 255
+            }
+        );
+        assert_eq!(
+            Color::from_hsv(180.0, 1.0, 1.0),
+            Color::Rgb {
+                r: 0,
+                g: 255,
+                b: 255
+            }
+        );
+        assert_eq!(
+            Color::from_hsv(360.0, 2.0, 1.0),
+            Color::Rgb { r: 255, g: 0, b: 0 }
+        );
+        assert_eq!(Color::Black.to_hsv(), Some((0.0, 0.0, 0.0)));
+    }
 
     #[test]
     fn test_known_color_conversion() {
@@ -243,6 +850,121 @@ mod tests {
         assert_eq!("foo".parse(), Ok(Color::White));
     }
 
+    #[test]
+    fn test_hex_color_conversion() {
+        let orange = Color::Rgb {
+            r: 255,
+            g: 136,
+            b: 0,
+        };
+
+        assert_eq!("#ff8800".parse(), Ok(orange));
+        assert_eq!("0xff8800".parse(), Ok(orange));
+        assert_eq!("#FF8800".parse(), Ok(orange));
+        assert_eq!("#f80".parse(), Ok(orange));
+        assert!(Color::try_from("#ff880").is_err());
+        assert!(Color::try_from("#gg8800").is_err());
+        assert!(Color::try_from("ff8800").is_err());
+        assert!(Color::try_from("#+f8800").is_err());
+    }
+
+    #[test]
+    fn test_x11_color_conversion() {
+        assert_eq!(
+            Color::try_from("dodgerblue"),
+            Ok(Color::Rgb {
+                r: 30,
+                g: 144,
+                b: 255
+            })
+        );
+        assert_eq!(
+            Color::try_from("DarkOrange"),
+            Ok(Color::Rgb {
+                r: 255,
+                g: 140,
+                b: 0
+            })
+        );
+        // The base color names take precedence
+        assert_eq!(Color::try_from("red"), Ok(Color::Red));
+        assert_eq!(Color::try_from("dark_red"), Ok(Color::DarkRed));
+    }
+
+    #[test]
+    fn test_parse_color_error() {
+        let error = Color::try_from("Not A Color").unwrap_err();
+        assert_eq!(error.input(), "Not A Color");
+        assert_eq!(error.to_string(), "unknown color name `Not A Color`");
+
+        let error = Color::try_from("#ff").unwrap_err();
+        assert_eq!(error.to_string(), "invalid hex color `#ff`");
+    }
+
+    #[test]
+    fn test_downgrade_rgb_color() {
+        let rgb = |r, g, b| Color::Rgb { r, g, b };
+
+        assert_eq!(
+            rgb(1, 2, 3).downgrade(ColorSupport::TrueColor),
+            rgb(1, 2, 3)
+        );
+
+        assert_eq!(
+            rgb(255, 0, 0).downgrade(ColorSupport::Ansi256),
+            Color::AnsiValue(196)
+        );
+        assert_eq!(
+            rgb(0, 0, 0).downgrade(ColorSupport::Ansi256),
+            Color::AnsiValue(16)
+        );
+        assert_eq!(
+            rgb(128, 128, 128).downgrade(ColorSupport::Ansi256),
+            Color::AnsiValue(244)
+        );
+
+        assert_eq!(rgb(250, 10, 5).downgrade(ColorSupport::Ansi16), Color::Red);
+        assert_eq!(
+            rgb(120, 0, 0).downgrade(ColorSupport::Ansi16),
+            Color::DarkRed
+        );
+        assert_eq!(
+            rgb(200, 200, 200).downgrade(ColorSupport::Ansi16),
+            Color::Grey
+        );
+    }
+
+    #[test]
+    fn test_downgrade_ansi_value_color() {
+        assert_eq!(
+            Color::AnsiValue(88).downgrade(ColorSupport::Ansi256),
+            Color::AnsiValue(88)
+        );
+
+        assert_eq!(
+            Color::AnsiValue(9).downgrade(ColorSupport::Ansi16),
+            Color::Red
+        );
+        assert_eq!(
+            Color::AnsiValue(196).downgrade(ColorSupport::Ansi16),
+            Color::Red
+        );
+        assert_eq!(
+            Color::AnsiValue(88).downgrade(ColorSupport::Ansi16),
+            Color::DarkRed
+        );
+        assert_eq!(
+            Color::AnsiValue(255).downgrade(ColorSupport::Ansi16),
+            Color::White
+        );
+    }
+
+    #[test]
+    fn test_downgrade_base_color() {
+        assert_eq!(Color::Blue.downgrade(ColorSupport::Ansi16), Color::Blue);
+        assert_eq!(Color::Reset.downgrade(ColorSupport::Ansi16), Color::Reset);
+    }
+
     #[test]
     fn test_know_rgb_color_conversion() {
         assert_eq!(Color::from((0, 0, 0)), Color::Rgb { r: 0, g: 0, b: 0 });
@@ -0,0 +1,64 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Represents whether colors should be emitted.
+///
+/// See [`set_color_choice`](fn.set_color_choice.html) for more info.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColorChoice {
+    /// Emit colors unless the environment asks otherwise (`NO_COLOR`, `CLICOLOR`,
+    /// `CLICOLOR_FORCE`). This is the default.
+    #[default]
+    Auto,
+    /// Always emit colors.
+    Always,
+    /// Never emit colors.
+    Never,
+}
+
+impl ColorChoice {
+    /// Resolves `Auto` from the values of the `NO_COLOR`, `CLICOLOR` and `CLICOLOR_FORCE`
+    /// environment variables.
+    ///
+    /// * `CLICOLOR_FORCE` set to anything else than `0` forces colors.
+    /// * `NO_COLOR` set to any non-empty value disables colors.
+    /// * `CLICOLOR` set to `0` disables colors.
+    pub(crate) fn resolve_env(
+        no_color: Option<&str>,
+        clicolor: Option<&str>,
+        clicolor_force: Option<&str>,
+    ) -> bool {
+        if matches!(clicolor_force, Some(v) if !v.is_empty() && v != "0") {
+            return true;
+        }
+        if matches!(no_color, Some(v) if !v.is_empty()) {
+            return false;
+        }
+        clicolor != Some("0")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColorChoice;
+
+    #[test]
+    fn test_resolve_env_defaults_to_colors() {
+        assert!(ColorChoice::resolve_env(None, None, None));
+        assert!(ColorChoice::resolve_env(None, Some("1"), None));
+    }
+
+    #[test]
+    fn test_resolve_env_disables_colors() {
+        assert!(!ColorChoice::resolve_env(Some("1"), None, None));
+        assert!(!ColorChoice::resolve_env(None, Some("0"), None));
+        assert!(ColorChoice::resolve_env(Some(""), None, None));
+    }
+
+    #[test]
+    fn test_resolve_env_forces_colors() {
+        assert!(ColorChoice::resolve_env(Some("1"), Some("0"), Some("1")));
+        assert!(!ColorChoice::resolve_env(Some("1"), None, Some("0")));
+    }
+}
@@ -0,0 +1,55 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Represents the color depth supported by the terminal.
+///
+/// The variants are ordered by the amount of colors, a terminal supporting `TrueColor` does also
+/// support `Ansi256` and `Ansi16` colors.
+///
+/// See [`color_support`](fn.color_support.html) for how it is detected.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
+pub enum ColorSupport {
+    /// The 16 base colors.
+    Ansi16,
+    /// The 256 colors of the [`Color::AnsiValue`](enum.Color.html#variant.AnsiValue) palette.
+    Ansi256,
+    /// 24-bit [`Color::Rgb`](enum.Color.html#variant.Rgb) colors.
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Creates a `ColorSupport` from the amount of colors reported by the terminal (e.g. the
+    /// terminfo `colors` capability).
+    #[cfg(unix)]
+    pub(crate) fn from_color_count(count: u32) -> ColorSupport {
+        match count {
+            0..=255 => ColorSupport::Ansi16,
+            256..=16_777_215 => ColorSupport::Ansi256,
+            _ => ColorSupport::TrueColor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColorSupport;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_color_support_from_color_count() {
+        assert_eq!(ColorSupport::from_color_count(8), ColorSupport::Ansi16);
+        assert_eq!(ColorSupport::from_color_count(16), ColorSupport::Ansi16);
+        assert_eq!(ColorSupport::from_color_count(256), ColorSupport::Ansi256);
+        assert_eq!(
+            ColorSupport::from_color_count(16_777_216),
+            ColorSupport::TrueColor
+        );
+    }
+
+    #[test]
+    fn test_color_support_ordering() {
+        assert!(ColorSupport::Ansi16 < ColorSupport::Ansi256);
+        assert!(ColorSupport::Ansi256 < ColorSupport::TrueColor);
+    }
+}
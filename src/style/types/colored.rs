@@ -16,6 +16,11 @@ pub enum Colored {
     ForegroundColor(Color),
     /// A background color.
     BackgroundColor(Color),
+    /// An underline color.
+    ///
+    /// Not supported by all terminals, see
+    /// [`supports_underline_styles`](fn.supports_underline_styles.html).
+    UnderlineColor(Color),
 }
 
 impl Colored {
@@ -47,8 +52,11 @@ impl Colored {
             38 => return Color::parse_ansi_iter(values).map(ForegroundColor),
             48 => return Color::parse_ansi_iter(values).map(BackgroundColor),
 
+            58 => return Color::parse_ansi_iter(values).map(Colored::UnderlineColor),
+
             39 => ForegroundColor(Color::Reset),
             49 => BackgroundColor(Color::Reset),
+            59 => Colored::UnderlineColor(Color::Reset),
 
             _ => return None,
         };
@@ -59,6 +67,22 @@ impl Colored {
 
         Some(output)
     }
+
+    /// Returns the 4-bit SGR code (`30` - `37` and `90` - `97` for the foreground, `40` - `47` and
+    /// `100` - `107` for the background) of a base color, as understood by terminals which don't
+    /// support the 256 colors codes.
+    pub(crate) fn ansi16_code(self) -> Option<u8> {
+        let (color, normal, bright) = match self {
+            Colored::ForegroundColor(color) => (color, 30, 90),
+            Colored::BackgroundColor(color) => (color, 40, 100),
+            Colored::UnderlineColor(_) => return None,
+        };
+
+        match color.base_index()? {
+            index @ 0..=7 => Some(normal + index),
+            index => Some(bright + index - 8),
+        }
+    }
 }
 
 impl fmt::Display for Colored {
@@ -82,6 +106,14 @@ impl fmt::Display for Colored {
                     color = new_color;
                 }
             }
+            Colored::UnderlineColor(new_color) => {
+                if new_color == Color::Reset {
+                    return f.write_str("59");
+                } else {
+                    f.write_str("58;")?;
+                    color = new_color;
+                }
+            }
         }
 
         match color {
@@ -136,6 +168,18 @@ mod tests {
         assert_eq!(colored.to_string(), "49");
     }
 
+    #[test]
+    fn test_format_underline_color() {
+        let colored = Colored::UnderlineColor(Color::Red);
+        assert_eq!(colored.to_string(), "58;5;9");
+    }
+
+    #[test]
+    fn test_format_reset_underline_color() {
+        let colored = Colored::UnderlineColor(Color::Reset);
+        assert_eq!(colored.to_string(), "59");
+    }
+
     #[test]
     fn test_format_fg_rgb_color() {
         let colored = Colored::BackgroundColor(Color::Rgb { r: 1, g: 2, b: 3 });
@@ -158,6 +202,11 @@ mod tests {
         test_parse_ansi(Colored::ForegroundColor)
     }
 
+    #[test]
+    fn test_parse_ansi_underline() {
+        test_parse_ansi(Colored::UnderlineColor)
+    }
+
     /// Used for test_parse_ansi_fg and test_parse_ansi_bg
     fn test_parse_ansi(bg_or_fg: impl Fn(Color) -> Colored) {
         /// Formats a re-parses `color` to check the result.
@@ -254,4 +303,38 @@ mod tests {
         test("48;2;0;2;25;");
         test("48;2;0;2;25;3");
     }
+
+    #[test]
+    fn test_ansi16_code() {
+        assert_eq!(
+            Colored::ForegroundColor(Color::Black).ansi16_code(),
+            Some(30)
+        );
+        assert_eq!(
+            Colored::ForegroundColor(Color::Grey).ansi16_code(),
+            Some(37)
+        );
+        assert_eq!(
+            Colored::ForegroundColor(Color::DarkGrey).ansi16_code(),
+            Some(90)
+        );
+        assert_eq!(
+            Colored::ForegroundColor(Color::White).ansi16_code(),
+            Some(97)
+        );
+        assert_eq!(
+            Colored::BackgroundColor(Color::DarkRed).ansi16_code(),
+            Some(41)
+        );
+        assert_eq!(
+            Colored::BackgroundColor(Color::Red).ansi16_code(),
+            Some(101)
+        );
+        assert_eq!(Colored::BackgroundColor(Color::Reset).ansi16_code(), None);
+        assert_eq!(
+            Colored::ForegroundColor(Color::AnsiValue(9)).ansi16_code(),
+            None
+        );
+        assert_eq!(Colored::UnderlineColor(Color::Red).ansi16_code(), None);
+    }
 }
@@ -1,3 +1,6 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::style::{Color, Colored};
 
 /// Represents, optionally, a foreground and/or a background color.
@@ -26,6 +29,7 @@ use crate::style::{Color, Colored};
 /// ```
 ///
 /// See [Color](enum.Color.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Colors {
     pub foreground: Option<Color>,
@@ -50,6 +54,24 @@ impl Colors {
             background: Some(background),
         }
     }
+
+    /// Returns the given background color with the most readable foreground color, see
+    /// [`Color::contrast_fg`](enum.Color.html#method.contrast_fg).
+    ///
+    /// ```no_run
+    /// use std::io::{stdout, Write};
+    ///
+    /// use crossterm::{execute, Result};
+    /// use crossterm::style::{Color, Colors, Print, ResetColor, SetColors};
+    ///
+    /// fn main() -> Result<()> {
+    ///     let background = Color::Rgb { r: 250, g: 220, b: 40 };
+    ///     execute!(stdout(), SetColors(Colors::contrasting(background)), Print("readable"), ResetColor)
+    /// }
+    /// ```
+    pub fn contrasting(background: Color) -> Colors {
+        Colors::new(background.contrast_fg(), background)
+    }
 }
 
 impl From<Colored> for Colors {
@@ -63,6 +85,10 @@ impl From<Colored> for Colors {
                 foreground: None,
                 background: Some(color),
             },
+            Colored::UnderlineColor(_) => Colors {
+                foreground: None,
+                background: None,
+            },
         }
     }
 }
@@ -227,4 +253,16 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_colors_contrasting() {
+        assert_eq!(
+            Colors::contrasting(Color::White),
+            Colors::new(Color::Black, Color::White)
+        );
+        assert_eq!(
+            Colors::contrasting(Color::DarkBlue),
+            Colors::new(Color::White, Color::DarkBlue)
+        );
+    }
 }
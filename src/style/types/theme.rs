@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::color::Color;
+
+/// Maps semantic color names (e.g. `"error"`, `"warning"`, `"accent"`) to colors.
+///
+/// A process-wide theme can be set with [`set_theme`](fn.set_theme.html), which allows to restyle
+/// an application (or the output of a library) by changing the theme only.
+///
+/// # Examples
+///
+/// ```
+/// use crossterm::style::{Color, Theme};
+///
+/// let theme = Theme::standard()
+///     .with("accent", Color::Magenta)
+///     .with("link", Color::Rgb { r: 0, g: 120, b: 255 });
+///
+/// assert_eq!(theme.get("accent"), Some(Color::Magenta));
+/// assert_eq!(theme.get("error"), Some(Color::Red));
+/// assert_eq!(theme.get("unknown"), None);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Theme {
+    colors: BTreeMap<String, Color>,
+}
+
+impl Theme {
+    /// Creates a theme without any colors.
+    pub fn new() -> Theme {
+        Theme::default()
+    }
+
+    /// Creates the theme which is used if no other theme is set.
+    ///
+    /// | Name | Color |
+    /// | :-- | :-- |
+    /// | `error` | `Red` |
+    /// | `warning` | `Yellow` |
+    /// | `success` | `Green` |
+    /// | `info` | `Cyan` |
+    /// | `accent` | `Blue` |
+    /// | `muted` | `DarkGrey` |
+    pub fn standard() -> Theme {
+        Theme::new()
+            .with("error", Color::Red)
+            .with("warning", Color::Yellow)
+            .with("success", Color::Green)
+            .with("info", Color::Cyan)
+            .with("accent", Color::Blue)
+            .with("muted", Color::DarkGrey)
+    }
+
+    /// Sets the color of the given name.
+    pub fn with(mut self, name: impl Into<String>, color: Color) -> Theme {
+        self.set(name, color);
+        self
+    }
+
+    /// Sets the color of the given name, returns the previous color if there was one.
+    pub fn set(&mut self, name: impl Into<String>, color: Color) -> Option<Color> {
+        self.colors.insert(name.into(), color)
+    }
+
+    /// Removes the color of the given name, returns the color if there was one.
+    pub fn remove(&mut self, name: &str) -> Option<Color> {
+        self.colors.remove(name)
+    }
+
+    /// Returns the color of the given name.
+    pub fn get(&self, name: &str) -> Option<Color> {
+        self.colors.get(name).copied()
+    }
+
+    /// Iterates over the names and colors of the theme (sorted by name).
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Color)> {
+        self.colors
+            .iter()
+            .map(|(name, color)| (name.as_str(), *color))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Color, Theme};
+
+    #[test]
+    fn test_set_and_remove() {
+        let mut theme = Theme::new();
+        assert_eq!(theme.get("error"), None);
+
+        assert_eq!(theme.set("error", Color::Red), None);
+        assert_eq!(theme.set("error", Color::DarkRed), Some(Color::Red));
+        assert_eq!(theme.get("error"), Some(Color::DarkRed));
+
+        assert_eq!(theme.remove("error"), Some(Color::DarkRed));
+        assert_eq!(theme, Theme::new());
+    }
+
+    #[test]
+    fn test_iter() {
+        let theme = Theme::new().with("b", Color::Blue).with("a", Color::Green);
+
+        assert_eq!(
+            theme.iter().collect::<Vec<_>>(),
+            [("a", Color::Green), ("b", Color::Blue)]
+        );
+    }
+}
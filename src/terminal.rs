@@ -40,6 +40,9 @@
 //! When it is launched from bash, a whole new buffer is used to modify a file.
 //! Then, when the modification is finished, it closes again and continues on the main screen.
 //!
+//! The [AlternateScreen](./struct.AlternateScreen.html) guard enters the alternate screen and
+//! leaves it when dropped.
+//!
 //! ### Raw Mode
 //!
 //! By default, the terminal functions in a certain way.
@@ -58,6 +61,10 @@
 //! - Special keys like backspace and CTL+C will not be processed by terminal driver
 //! - New line character will not be processed therefore `println!` can't be used, use `write!` instead
 //!
+//! The raw mode stays enabled until [disable_raw_mode](./fn.disable_raw_mode.html) is called,
+//! [enable_raw_mode_scoped](./fn.enable_raw_mode_scoped.html) returns a guard which disables it
+//! when dropped, e.g. on an early return with `?`.
+//!
 //! ## Examples
 //!
 //! ```no_run
@@ -81,7 +88,10 @@
 //!
 //! For manual execution control check out [crossterm::queue](../macro.queue.html).
 
-use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{env, fmt, io, panic, path::Path};
+
+use parking_lot::Mutex;
 
 #[cfg(windows)]
 use crossterm_winapi::{ConsoleMode, Handle, ScreenBuffer};
@@ -92,32 +102,254 @@ use winapi::um::wincon::ENABLE_WRAP_AT_EOL_OUTPUT;
 
 #[doc(no_inline)]
 use crate::Command;
-use crate::{csi, impl_display, Result};
+use crate::{
+    csi, cursor, execute, impl_display,
+    style::{default_background_color, reset_style_state, Color, ResetColor},
+    ExecutableCommand, Result,
+};
 
+pub(crate) mod base64;
 pub(crate) mod sys;
 
 /// Enables raw mode.
 ///
 /// Please have a look at the [raw mode](./#raw-mode) section.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::{
+///     event::{read, Event, KeyCode, KeyEvent},
+///     terminal, Result,
+/// };
+///
+/// fn main() -> Result<()> {
+///     terminal::enable_raw_mode()?;
+///
+///     // Keys are read one by one, without waiting for enter
+///     while read()? != Event::Key(KeyEvent::from(KeyCode::Esc)) {}
+///
+///     terminal::disable_raw_mode()
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * On UNIX, the terminal attributes of `/dev/tty` are changed with termios. On Windows, the
+///   line input, echo input and processed input modes of the console input are disabled.
+/// * The previous mode is saved and restored by [disable_raw_mode](./fn.disable_raw_mode.html).
+///   Enabling the raw mode again while it is enabled does nothing.
 pub fn enable_raw_mode() -> Result<()> {
     sys::enable_raw_mode()
 }
 
 /// Disables raw mode.
 ///
+/// Restores the mode which was active before [enable_raw_mode](./fn.enable_raw_mode.html), it
+/// does nothing if the raw mode is not enabled.
+///
 /// Please have a look at the [raw mode](./#raw-mode) section.
 pub fn disable_raw_mode() -> Result<()> {
     sys::disable_raw_mode()
 }
 
+/// Returns whether the raw mode was enabled with [enable_raw_mode](./fn.enable_raw_mode.html).
+pub fn is_raw_mode_enabled() -> bool {
+    sys::is_raw_mode_enabled()
+}
+
+/// Enables raw mode until the returned guard is dropped.
+///
+/// Please have a look at the [raw mode](./#raw-mode) section.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::{
+///     event::{read, Event, KeyCode, KeyEvent},
+///     terminal, Result,
+/// };
+///
+/// fn main() -> Result<()> {
+///     let _raw_mode = terminal::enable_raw_mode_scoped()?;
+///
+///     // The raw mode is disabled even if `read` fails
+///     while read()? != Event::Key(KeyEvent::from(KeyCode::Esc)) {}
+///     Ok(())
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * If the raw mode was already enabled, dropping the guard keeps it enabled, so the guards
+///   can be nested.
+/// * The errors of [disable_raw_mode](./fn.disable_raw_mode.html) are ignored on drop, call
+///   [RawModeGuard::disable](./struct.RawModeGuard.html#method.disable) to handle them.
+pub fn enable_raw_mode_scoped() -> Result<RawModeGuard> {
+    let was_enabled = is_raw_mode_enabled();
+    enable_raw_mode()?;
+    Ok(RawModeGuard {
+        was_enabled,
+        restored: false,
+    })
+}
+
+/// A guard returned by [enable_raw_mode_scoped](./fn.enable_raw_mode_scoped.html) which restores
+/// the previous mode when dropped.
+#[must_use = "the raw mode is disabled when the guard is dropped"]
+#[derive(Debug)]
+pub struct RawModeGuard {
+    was_enabled: bool,
+    restored: bool,
+}
+
+impl RawModeGuard {
+    /// Restores the previous mode now and returns the error of
+    /// [disable_raw_mode](./fn.disable_raw_mode.html) if it failed.
+    pub fn disable(mut self) -> Result<()> {
+        self.restored = true;
+        if self.was_enabled {
+            return Ok(());
+        }
+        disable_raw_mode()
+    }
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        if !self.restored && !self.was_enabled {
+            let _ = disable_raw_mode();
+        }
+    }
+}
+
+/// Suspends the process like `Ctrl+Z` does in a shell.
+///
+/// The terminal is restored first (the alternate screen is left, the cursor is shown, the colors
+/// are reset and the raw mode is disabled), then the process is stopped. Once the process is
+/// continued (`SIGCONT`, e.g. `fg` in the shell), the raw mode is enabled and the alternate screen
+/// is entered again if they were active before.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::{
+///     event::{read, Event, KeyCode, KeyEvent, KeyModifiers},
+///     terminal, Result,
+/// };
+///
+/// fn main() -> Result<()> {
+///     terminal::enable_raw_mode()?;
+///
+///     loop {
+///         match read()? {
+///             Event::Key(KeyEvent {
+///                 code: KeyCode::Char('z'),
+///                 modifiers: KeyModifiers::CONTROL,
+///                 ..
+///             }) => terminal::suspend()?,
+///             Event::Key(KeyEvent { code: KeyCode::Esc, .. }) => break,
+///             _ => {}
+///         }
+///     }
+///
+///     terminal::disable_raw_mode()
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * In the raw mode, `Ctrl+Z` is read as a key event and does not send `SIGTSTP`, call this
+///   function when the key event is read.
+/// * The content of the alternate screen is not restored, redraw the screen after this
+///   function returns.
+#[cfg(unix)]
+pub fn suspend() -> Result<()> {
+    sys::suspend()
+}
+
+/// Handles the `SIGTSTP` signal (e.g. `kill -TSTP` or `Ctrl+Z` outside of the raw mode) with
+/// [suspend](./fn.suspend.html).
+///
+/// A thread waiting for the signal is started, calling this function more than once does nothing.
+///
+/// # Notes
+///
+/// * The application is not notified when it is continued, redraw the screen when the next event
+///   is read if the alternate screen content matters.
+#[cfg(unix)]
+pub fn enable_suspend_handling() -> Result<()> {
+    sys::enable_suspend_handling()
+}
+
 /// Returns the terminal size `(columns, rows)`.
 ///
-/// The top left cell is represented `(1, 1)`.
+/// The cursor positions go from `(0, 0)` to `(columns - 1, rows - 1)`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{cursor::MoveTo, execute, style::Print, terminal, Result};
+///
+/// fn main() -> Result<()> {
+///     // Center a message
+///     let message = "Hello";
+///     let (columns, rows) = terminal::size()?;
+///     let column = columns.saturating_sub(message.len() as u16) / 2;
+///     execute!(stdout(), MoveTo(column, rows / 2), Print(message))
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * On UNIX, the size is read with the `TIOCGWINSZ` ioctl (from `/dev/tty`, or stdout if it
+///   can't be opened), falling back to `tput cols` & `tput lines`.
+/// * On Windows, the size of the visible window is read with `GetConsoleScreenBufferInfo`.
+/// * The size is never `0x0`, an error is returned if it can't be determined.
 pub fn size() -> Result<(u16, u16)> {
     sys::size()
 }
 
-/// Disables line wrapping.
+/// A command that disables line wrapping.
+///
+/// Text written past the last column doesn't continue on the next row, the cursor stays in the
+/// last column (overwriting it). Full screen applications can draw the last column, including the
+/// bottom right cell, without the terminal scrolling.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{
+///     cursor::MoveTo,
+///     execute,
+///     style::Print,
+///     terminal::{self, DisableLineWrap, EnableLineWrap},
+///     Result,
+/// };
+///
+/// fn main() -> Result<()> {
+///     let (columns, rows) = terminal::size()?;
+///     execute!(
+///         stdout(),
+///         DisableLineWrap,
+///         MoveTo(columns - 1, rows - 1),
+///         Print("#"),
+///         EnableLineWrap
+///     )
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * On UNIX and Windows 10 this uses `DECAWM` (`CSI ? 7 l`), the legacy Windows console unsets
+///   `ENABLE_WRAP_AT_EOL_OUTPUT`.
+/// * Use [EnableLineWrap](./struct.EnableLineWrap.html) to restore the line wrapping, e.g. on
+///   exit.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct DisableLineWrap;
 
@@ -136,7 +368,13 @@ impl Command for DisableLineWrap {
     }
 }
 
-/// Enable line wrapping.
+/// A command that enables line wrapping (the default).
+///
+/// See the [DisableLineWrap](./struct.DisableLineWrap.html) command.
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct EnableLineWrap;
 
@@ -155,12 +393,306 @@ impl Command for EnableLineWrap {
     }
 }
 
+/// Returns the terminal size in pixels `(width, height)`.
+///
+/// This is the size of the text area, e.g. to scale images (sixel, kitty graphics, ...) to the
+/// window. Divide it by the [size](./fn.size.html) to get the size of a cell.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::{terminal, Result};
+///
+/// fn main() -> Result<()> {
+///     let (width, height) = terminal::size_pixels()?;
+///     let (columns, rows) = terminal::size()?;
+///     println!("A cell is {}x{} pixels", width / columns, height / rows);
+///     Ok(())
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * On UNIX, the pixel fields of the `TIOCGWINSZ` ioctl are used. Many terminals leave them
+///   empty, the terminal is queried with `CSI 14 t` then (the raw mode is enabled while waiting
+///   for the answer if it isn't enabled yet). An error is returned if the terminal does not
+///   answer within 2 seconds.
+/// * On Windows, the size is computed from the console font size.
+pub fn size_pixels() -> Result<(u16, u16)> {
+    sys::size_pixels()
+}
+
+/// Returns whether the terminal background is dark, `None` if it is unknown.
+///
+/// Applications can use it to choose a default color scheme which is readable on the background.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::{style::Color, terminal};
+///
+/// let accent = match terminal::background_is_dark() {
+///     Some(false) => Color::DarkBlue,
+///     _ => Color::Cyan,
+/// };
+/// ```
+///
+/// # Notes
+///
+/// * The background color is queried with
+///   [default_background_color](../style/fn.default_background_color.html) and it is dark if
+///   white text has a better contrast on it than black text.
+/// * If the terminal doesn't report the color, the `COLORFGBG` environment variable (set by
+///   rxvt, Konsole, ...) is used.
+/// * Querying the terminal can take up to 2 seconds if it doesn't answer, call it once at
+///   startup and flush the output before.
+pub fn background_is_dark() -> Option<bool> {
+    match default_background_color() {
+        Ok(color) if color.to_rgb().is_some() => Some(color.contrast_fg() == Color::White),
+        _ => env::var("COLORFGBG")
+            .ok()
+            .and_then(|value| colorfgbg_is_dark(&value)),
+    }
+}
+
+/// Returns whether the background of the `COLORFGBG` value (`fg;bg` or `fg;default;bg`) is dark.
+fn colorfgbg_is_dark(value: &str) -> Option<bool> {
+    let background = value.rsplit(';').next()?.parse::<u8>().ok()?;
+    // Black, the dark colors (except light grey) and dark grey
+    Some(matches!(background, 0..=6 | 8))
+}
+
+/// The features supported by the terminal, see [capabilities](./fn.capabilities.html).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Capabilities {
+    /// Whether 24-bit colors are supported (the `RGB` or `Tc` terminfo capability), see
+    /// [`style::color_support`](../style/fn.color_support.html).
+    pub true_color: bool,
+    /// Whether the synchronized output mode (`2026`) is supported, see
+    /// [BeginSynchronizedUpdate](./struct.BeginSynchronizedUpdate.html).
+    pub synchronized_output: bool,
+    /// Whether the SGR mouse mode (`1006`) is supported, which allows mouse coordinates of more
+    /// than 223.
+    pub sgr_mouse: bool,
+    /// Whether the bracketed paste mode (`2004`) is supported.
+    pub bracketed_paste: bool,
+    /// Whether the focus events mode (`1004`) is supported.
+    pub focus_events: bool,
+}
+
+/// Queries the features supported by the terminal.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{queue, terminal, Result};
+///
+/// fn main() -> Result<()> {
+///     let capabilities = terminal::capabilities()?;
+///
+///     let mut stdout = stdout();
+///     if capabilities.synchronized_output {
+///         queue!(stdout, terminal::BeginSynchronizedUpdate)?;
+///     }
+///     // Draw the frame ...
+///     if capabilities.synchronized_output {
+///         queue!(stdout, terminal::EndSynchronizedUpdate)?;
+///     }
+///     stdout.flush()?;
+///     Ok(())
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * On UNIX, the modes are queried with `DECRQM` (`CSI ? Pm $ p`) followed by a primary device
+///   attributes query (`DA1`, `CSI c`). Every terminal answers the latter, the modes which were
+///   not reported before its answer are unsupported. The raw mode is enabled while waiting for
+///   the answers if it isn't enabled yet. An error is returned if the terminal does not answer
+///   within 2 seconds.
+/// * On UNIX, `true_color` is queried with `XTGETTCAP` (`DCS + q Pt ST`) for the `RGB` and `Tc`
+///   terminfo capabilities. The environment is used instead (see
+///   [`style::color_support`](../style/fn.color_support.html)) if the terminal doesn't answer.
+/// * On Windows, the terminal is not queried and only `true_color` is set.
+/// * Flush the output before querying, the answers are read from the terminal input.
+pub fn capabilities() -> Result<Capabilities> {
+    sys::capabilities()
+}
+
+// Whether the alternate screen was entered with `EnterAlternateScreen` (and not left yet).
+static ALTERNATE_SCREEN: AtomicBool = AtomicBool::new(false);
+
+/// Returns whether the alternate screen is active.
+///
+/// This is tracked by the [EnterAlternateScreen](./struct.EnterAlternateScreen.html) and
+/// [LeaveAlternateScreen](./struct.LeaveAlternateScreen.html) commands, the terminal is not
+/// queried. E.g. a panic handler can use it to know if it must leave the alternate screen before
+/// printing the panic message on the main screen.
+pub fn is_alternate_screen_active() -> bool {
+    ALTERNATE_SCREEN.load(Ordering::SeqCst)
+}
+
+/// Installs a panic hook which restores the terminal before the panic message is printed.
+///
+/// The hook leaves the alternate screen, shows the cursor, resets the colors and disables the raw
+/// mode, then the previous panic hook is called. Without it, a panicking application leaves the
+/// shell of the user in the raw mode and the panic message is lost on the alternate screen.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::stdout;
+/// use crossterm::{execute, terminal, Result};
+///
+/// fn main() -> Result<()> {
+///     terminal::install_panic_hook();
+///
+///     terminal::enable_raw_mode()?;
+///     execute!(stdout(), terminal::EnterAlternateScreen)?;
+///
+///     panic!("The terminal is restored before this message is printed");
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * Install it before changing the terminal, calling it more than once restores the terminal
+///   more than once.
+/// * The alternate screen is only left if it was entered with
+///   [EnterAlternateScreen](./struct.EnterAlternateScreen.html), see
+///   [is_alternate_screen_active](./fn.is_alternate_screen_active.html).
+/// * The commands are written to the standard output, errors are ignored.
+pub fn install_panic_hook() {
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        restore();
+        previous_hook(info);
+    }));
+}
+
+pub(crate) fn restore() {
+    let mut stdout = io::stdout();
+    let _ = execute!(stdout, ResetColor, cursor::Show);
+    if is_alternate_screen_active() {
+        let _ = execute!(stdout, LeaveAlternateScreen);
+    }
+    let _ = disable_raw_mode();
+}
+
+/// A command that resets the terminal to a sane state without clearing the screen.
+///
+/// The colors and attributes are reset, the cursor is shown and the scroll region is removed,
+/// e.g. when the application doesn't know in which state a crashed child process left the
+/// terminal. The styles pushed with [PushStyle](../style/struct.PushStyle.html) are forgotten.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{execute, terminal::SoftReset, Result};
+///
+/// fn main() -> Result<()> {
+///     execute!(stdout(), SoftReset)
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * This uses `DECSTR` (`CSI ! p`), which also resets the modes like the insert mode and the
+///   origin mode and the saved cursor position. The screen content and the cursor position are
+///   kept.
+/// * On the legacy Windows console, only the colors are reset and the cursor is shown.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoftReset;
+
+impl Command for SoftReset {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("!p"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, mut writer: impl FnMut() -> Result<()>) -> Result<()> {
+        ResetColor.execute_winapi(&mut writer)?;
+        cursor::Show.execute_winapi(writer)
+    }
+
+    fn update_state(&self) {
+        reset_style_state();
+    }
+}
+
+/// A command that resets the terminal to its initial state.
+///
+/// Unlike [SoftReset](./struct.SoftReset.html), the screen is cleared too. Use it when the
+/// screen was corrupted, e.g. by binary output switching the character set.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{execute, terminal::HardReset, Result};
+///
+/// fn main() -> Result<()> {
+///     execute!(stdout(), HardReset)
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * This uses `RIS` (`ESC c`), which resets everything including the tab stops, the palette
+///   and the character sets, and switches to the main screen.
+/// * On the legacy Windows console, the main screen is shown, the raw mode is disabled, the
+///   output mode (e.g. the line wrapping) and the colors are reset to the ones of the console
+///   when crossterm started, the cursor is shown, the screen buffer is cleared and the cursor is
+///   moved to the top left cell.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HardReset;
+
+impl Command for HardReset {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1Bc")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, mut writer: impl FnMut() -> Result<()>) -> Result<()> {
+        if is_alternate_screen_active() {
+            LeaveAlternateScreen.execute_winapi(&mut writer)?;
+        }
+        // The input mode is restored by leaving the raw mode
+        sys::disable_raw_mode()?;
+        match crate::ansi_support::original_output_mode() {
+            Some(mode) => ConsoleMode::from(Handle::current_out_handle()?).set_mode(mode)?,
+            None => EnableLineWrap.execute_winapi(&mut writer)?,
+        }
+        ResetColor.execute_winapi(&mut writer)?;
+        cursor::Show.execute_winapi(&mut writer)?;
+        Clear(ClearType::All).execute_winapi(&mut writer)?;
+        cursor::MoveTo(0, 0).execute_winapi(writer)
+    }
+
+    fn update_state(&self) {
+        reset_style_state();
+        ALTERNATE_SCREEN.store(false, Ordering::SeqCst);
+    }
+}
+
 /// A command that switches to alternate screen.
 ///
 /// # Notes
 ///
 /// * Commands must be executed/queued for execution otherwise they do nothing.
 /// * Use [LeaveAlternateScreen](./struct.LeaveAlternateScreen.html) command to leave the entered alternate screen.
+/// * Whether the alternate screen is active is tracked when the command is queued, see
+///   [is_alternate_screen_active](./fn.is_alternate_screen_active.html).
+/// * On the legacy Windows console, entering the alternate screen again while it is active does
+///   nothing. The escape code is written again otherwise, most terminals clear the alternate
+///   screen then.
 ///
 /// # Examples
 ///
@@ -187,10 +719,17 @@ impl Command for EnterAlternateScreen {
 
     #[cfg(windows)]
     fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
-        let alternate_screen = ScreenBuffer::create();
-        alternate_screen.show()?;
+        // Entering it again must not stack another screen buffer
+        if !ALTERNATE_SCREEN.load(Ordering::SeqCst) {
+            let alternate_screen = ScreenBuffer::create();
+            alternate_screen.show()?;
+        }
         Ok(())
     }
+
+    fn update_state(&self) {
+        ALTERNATE_SCREEN.store(true, Ordering::SeqCst);
+    }
 }
 
 /// A command that switches back to the main screen.
@@ -229,31 +768,154 @@ impl Command for LeaveAlternateScreen {
         screen_buffer.show()?;
         Ok(())
     }
-}
 
-/// Different ways to clear the terminal buffer.
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
-pub enum ClearType {
-    /// All cells.
-    All,
-    /// All cells from the cursor position downwards.
-    FromCursorDown,
-    /// All cells from the cursor position upwards.
-    FromCursorUp,
-    /// All cells at the cursor row.
-    CurrentLine,
-    /// All cells from the cursor position until the new line.
-    UntilNewLine,
+    fn update_state(&self) {
+        ALTERNATE_SCREEN.store(false, Ordering::SeqCst);
+    }
 }
 
-/// A command that scrolls the terminal screen a given number of rows up.
+// The number of `AlternateScreen` guards which are alive, the first guard enters the alternate
+// screen and the last one leaves it.
+static ALTERNATE_SCREEN_GUARDS: Mutex<usize> = parking_lot::const_mutex(0);
+
+/// A guard which switches to the alternate screen and back to the main screen when dropped.
+///
+/// The guards can be nested, only the first guard enters the alternate screen and it's left when
+/// the last guard is dropped. The guard writes to the wrapped writer, which is accessible through
+/// its `Write` implementation.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{execute, style::Print, terminal::AlternateScreen, Result};
+///
+/// fn main() -> Result<()> {
+///     let mut screen = AlternateScreen::enter(stdout())?;
+///
+///     // Do anything on the alternate screen, the main screen is restored even if this fails
+///     execute!(screen, Print("Hello"))?;
+///
+///     screen.leave()
+/// }
+/// ```
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct ScrollUp(pub u16);
+/// * The errors of [LeaveAlternateScreen](./struct.LeaveAlternateScreen.html) are ignored on drop,
+///   call [leave](#method.leave) to handle them.
+/// * Don't mix the guards with the [EnterAlternateScreen](./struct.EnterAlternateScreen.html) and
+///   [LeaveAlternateScreen](./struct.LeaveAlternateScreen.html) commands, the guards don't know
+///   about a screen which was entered with the command.
+#[must_use = "the main screen is restored when the guard is dropped"]
+#[derive(Debug)]
+pub struct AlternateScreen<W: io::Write> {
+    writer: W,
+    left: bool,
+}
+
+impl<W: io::Write> AlternateScreen<W> {
+    /// Enters the alternate screen, unless another guard is alive.
+    pub fn enter(mut writer: W) -> Result<AlternateScreen<W>> {
+        let mut guards = ALTERNATE_SCREEN_GUARDS.lock();
+        if *guards == 0 {
+            writer.execute(EnterAlternateScreen)?;
+        }
+        *guards += 1;
+
+        Ok(AlternateScreen {
+            writer,
+            left: false,
+        })
+    }
+
+    /// Leaves the alternate screen now if this is the last alive guard.
+    pub fn leave(mut self) -> Result<()> {
+        self.leave_alternate_screen()
+    }
+
+    fn leave_alternate_screen(&mut self) -> Result<()> {
+        if self.left {
+            return Ok(());
+        }
+        self.left = true;
+
+        let mut guards = ALTERNATE_SCREEN_GUARDS.lock();
+        *guards -= 1;
+        if *guards == 0 {
+            self.writer.execute(LeaveAlternateScreen)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: io::Write> io::Write for AlternateScreen<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<W: io::Write> Drop for AlternateScreen<W> {
+    fn drop(&mut self) {
+        let _ = self.leave_alternate_screen();
+    }
+}
+
+/// Different ways to clear the terminal buffer.
+///
+/// Clearing only the part which changed avoids the flickering of clearing the whole screen.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
+pub enum ClearType {
+    /// All cells (`CSI 2 J`).
+    ///
+    /// The WinAPI clears the whole screen buffer and moves the cursor to the top left cell.
+    All,
+    /// All cells from the cursor position downwards, including the cursor cell (`CSI J`).
+    FromCursorDown,
+    /// All cells from the cursor position upwards, including the cursor cell (`CSI 1 J`).
+    FromCursorUp,
+    /// All cells at the cursor row (`CSI 2 K`).
+    CurrentLine,
+    /// All cells from the cursor position until the new line, including the cursor cell
+    /// (`CSI K`).
+    UntilNewLine,
+}
+
+/// A command that scrolls the terminal screen a given number of rows up.
+///
+/// The content moves up, the top rows disappear and blank rows are added at the bottom. Only
+/// the new rows need to be drawn, e.g. when a log viewer shows the next lines.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{cursor::MoveTo, execute, style::Print, terminal, Result};
+///
+/// fn main() -> Result<()> {
+///     let (_, rows) = terminal::size()?;
+///     execute!(
+///         stdout(),
+///         terminal::ScrollUp(1),
+///         MoveTo(0, rows - 1),
+///         Print("next log line")
+///     )
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * The cursor doesn't move.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollUp(pub u16);
 
 impl Command for ScrollUp {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
@@ -271,9 +933,12 @@ impl Command for ScrollUp {
 
 /// A command that scrolls the terminal screen a given number of rows down.
 ///
+/// The content moves down, the bottom rows disappear and blank rows are added at the top.
+///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * The cursor doesn't move.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ScrollDown(pub u16);
 
@@ -291,83 +956,1221 @@ impl Command for ScrollDown {
     }
 }
 
-/// A command that clears the terminal screen buffer.
+/// A command that sets a tab stop at the cursor column.
 ///
-/// See the [`ClearType`](enum.ClearType.html) enum.
+/// The tab character and [MoveToNextTab](../cursor/struct.MoveToNextTab.html) move the cursor to
+/// the tab stops, e.g. to print column-aligned output without space padding.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{
+///     cursor::MoveToColumn,
+///     execute,
+///     style::Print,
+///     terminal::{ClearAllTabStops, SetTabStop},
+///     Result,
+/// };
+///
+/// fn main() -> Result<()> {
+///     execute!(
+///         stdout(),
+///         ClearAllTabStops,
+///         MoveToColumn(20),
+///         SetTabStop,
+///         MoveToColumn(0),
+///         Print("name\tsize\n"),
+///     )
+/// }
+/// ```
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * This uses `HTS` (`ESC H`). Terminals start with a tab stop every 8 columns.
+/// * The tab stops of the legacy Windows console can't be changed, the command does nothing.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Clear(pub ClearType);
+pub struct SetTabStop;
 
-impl Command for Clear {
+impl Command for SetTabStop {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        f.write_str(match self.0 {
-            ClearType::All => csi!("2J"),
-            ClearType::FromCursorDown => csi!("J"),
-            ClearType::FromCursorUp => csi!("1J"),
-            ClearType::CurrentLine => csi!("2K"),
-            ClearType::UntilNewLine => csi!("K"),
-        })
+        f.write_str("\x1BH")
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
-        sys::clear(self.0)
+        Ok(())
     }
 }
 
-/// A command that sets the terminal size `(columns, rows)`.
+/// A command that clears the tab stop at the cursor column.
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * This uses `TBC` (`CSI g`).
+/// * The tab stops of the legacy Windows console can't be changed, the command does nothing.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct SetSize(pub u16, pub u16);
+pub struct ClearTabStop;
 
-impl Command for SetSize {
+impl Command for ClearTabStop {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        write!(f, csi!("8;{};{}t"), self.1, self.0)
+        f.write_str(csi!("g"))
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
-        sys::set_size(self.0, self.1)
+        Ok(())
     }
 }
 
-/// A command that sets the terminal title
+/// A command that clears all tab stops.
 ///
 /// # Notes
 ///
-/// Commands must be executed/queued for execution otherwise they do nothing.
+/// * This uses `TBC` (`CSI 3 g`). The tab character moves the cursor to the last column then.
+/// * The tab stops of the legacy Windows console can't be changed, the command does nothing.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct SetTitle<T>(pub T);
+pub struct ClearAllTabStops;
 
-impl<T: fmt::Display> Command for SetTitle<T> {
+impl Command for ClearAllTabStops {
     fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        write!(f, "\x1B]0;{}\x07", &self.0)
+        f.write_str(csi!("3g"))
     }
 
     #[cfg(windows)]
     fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
-        sys::set_window_title(&self.0)
+        Ok(())
     }
 }
 
-impl_display!(for ScrollUp);
-impl_display!(for ScrollDown);
-impl_display!(for SetSize);
-impl_display!(for Clear);
+/// A command that inserts a given number of blank rows at the cursor row.
+///
+/// The cursor row and the rows below it move down, the bottom rows disappear. Unlike redrawing
+/// everything below the cursor, only the inserted rows need to be drawn, e.g. when an editor
+/// opens a new line.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{cursor::MoveTo, execute, style::Print, terminal::InsertLines, Result};
+///
+/// fn main() -> Result<()> {
+///     execute!(stdout(), MoveTo(0, 5), InsertLines(1), Print("new line"))
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * The rows above the cursor and below the [scroll region](./struct.SetScrollRegion.html) are
+///   left untouched.
+/// * This uses `IL` (`CSI n L`), terminals move the cursor to the first column.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsertLines(pub u16);
 
-#[cfg(test)]
-mod tests {
-    use std::{io::stdout, thread, time};
+impl Command for InsertLines {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.0 != 0 {
+            write!(f, csi!("{}L"), self.0)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::insert_lines(self.0)
+    }
+}
+
+/// A command that deletes a given number of rows at the cursor row.
+///
+/// The rows below move up, blank rows are added at the bottom.
+///
+/// # Notes
+///
+/// * The rows above the cursor and below the [scroll region](./struct.SetScrollRegion.html) are
+///   left untouched.
+/// * This uses `DL` (`CSI n M`), terminals move the cursor to the first column.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeleteLines(pub u16);
+
+impl Command for DeleteLines {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.0 != 0 {
+            write!(f, csi!("{}M"), self.0)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::delete_lines(self.0)
+    }
+}
+
+/// A command that inserts a given number of blank cells at the cursor position.
+///
+/// The cursor cell and the cells after it move right, the cells at the end of the row disappear.
+///
+/// # Notes
+///
+/// * The cursor doesn't move.
+/// * This uses `ICH` (`CSI n @`).
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsertChars(pub u16);
+
+impl Command for InsertChars {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.0 != 0 {
+            write!(f, csi!("{}@"), self.0)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::insert_chars(self.0)
+    }
+}
+
+/// A command that deletes a given number of cells at the cursor position.
+///
+/// The cells after them move left, blank cells are added at the end of the row.
+///
+/// # Notes
+///
+/// * The cursor doesn't move.
+/// * This uses `DCH` (`CSI n P`).
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeleteChars(pub u16);
+
+impl Command for DeleteChars {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        if self.0 != 0 {
+            write!(f, csi!("{}P"), self.0)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::delete_chars(self.0)
+    }
+}
+
+/// A command that restricts the scrolling to the rows from `top` to `bottom` (both included).
+///
+/// The rows outside of the region are left untouched when the text written in the region
+/// scrolls it (or with [ScrollUp](./struct.ScrollUp.html)/[ScrollDown](./struct.ScrollDown.html)),
+/// e.g. for a fixed header and footer around a log.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{
+///     cursor::MoveTo,
+///     execute,
+///     style::Print,
+///     terminal::{self, ResetScrollRegion, SetScrollRegion},
+///     Result,
+/// };
+///
+/// fn main() -> Result<()> {
+///     let (_, rows) = terminal::size()?;
+///     execute!(
+///         stdout(),
+///         MoveTo(0, 0),
+///         Print("header"),
+///         MoveTo(0, rows - 1),
+///         Print("footer"),
+///         // Everything in between scrolls
+///         SetScrollRegion(1, rows - 2),
+///         MoveTo(0, rows - 2),
+///         Print("line 1\nline 2\n"),
+///         ResetScrollRegion
+///     )
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * The top row is `0`.
+/// * This uses `DECSTBM` (`CSI top ; bottom r`), which moves the cursor to the top left cell.
+/// * The legacy Windows console does not support scroll regions, the command does nothing.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetScrollRegion(pub u16, pub u16);
+
+impl Command for SetScrollRegion {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        // The ANSI rows are 1-based
+        write!(
+            f,
+            csi!("{};{}r"),
+            u32::from(self.0) + 1,
+            u32::from(self.1) + 1
+        )
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that resets the scroll region to the whole screen.
+///
+/// See the [SetScrollRegion](./struct.SetScrollRegion.html) command.
+///
+/// # Notes
+///
+/// * This moves the cursor to the top left cell, like `SetScrollRegion`.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResetScrollRegion;
+
+impl Command for ResetScrollRegion {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("r"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that clears the terminal screen buffer.
+///
+/// See the [`ClearType`](enum.ClearType.html) enum.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{
+///     cursor::MoveTo,
+///     queue,
+///     style::Print,
+///     terminal::{Clear, ClearType},
+///     Result,
+/// };
+///
+/// fn main() -> Result<()> {
+///     let mut stdout = stdout();
+///
+///     // Only redraw the status line
+///     queue!(
+///         stdout,
+///         MoveTo(0, 0),
+///         Clear(ClearType::CurrentLine),
+///         Print("3 files changed")
+///     )?;
+///     stdout.flush()?;
+///     Ok(())
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * The cursor doesn't move, except for `ClearType::All` with the WinAPI.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clear(pub ClearType);
 
-    use crate::execute;
+impl Command for Clear {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(match self.0 {
+            ClearType::All => csi!("2J"),
+            ClearType::FromCursorDown => csi!("J"),
+            ClearType::FromCursorUp => csi!("1J"),
+            ClearType::CurrentLine => csi!("2K"),
+            ClearType::UntilNewLine => csi!("K"),
+        })
+    }
 
-    use super::{size, SetSize};
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::clear(self.0)
+    }
+}
+
+/// A command that sets the terminal size `(columns, rows)`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{execute, terminal, Result};
+///
+/// fn main() -> Result<()> {
+///     // Make sure there's room for an 80x24 layout
+///     let (columns, rows) = terminal::size()?;
+///     if columns < 80 || rows < 24 {
+///         execute!(stdout(), terminal::SetSize(columns.max(80), rows.max(24)))?;
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * On UNIX, this uses the xterm window operation `CSI 8 ; rows ; columns t`. Many terminals
+///   ignore it (e.g. tabbed terminals, or xterm with `allowWindowOps` disabled), check
+///   [size](./fn.size.html) afterwards if the size matters.
+/// * On Windows, the console window (and the screen buffer if needed) is resized. An error is
+///   returned if the size is less than 2 or larger than the screen allows.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetSize(pub u16, pub u16);
+
+impl Command for SetSize {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, csi!("8;{};{}t"), self.1, self.0)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::set_size(self.0, self.1)
+    }
+}
+
+/// A command that minimizes (iconifies) the terminal window.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{execute, terminal::{MinimizeWindow, RestoreWindow}, Result};
+///
+/// fn main() -> Result<()> {
+///     execute!(stdout(), MinimizeWindow)?;
+///     // ... wait for the next job
+///     execute!(stdout(), RestoreWindow)
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * This uses the xterm window operations (`XTWINOPS`, `CSI 2 t`), many terminals ignore them
+///   or only allow them when configured to. The WinAPI uses `ShowWindow` on the console window.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinimizeWindow;
+
+impl Command for MinimizeWindow {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("2t"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::minimize_window()
+    }
+}
+
+/// A command that maximizes the terminal window.
+///
+/// # Notes
+///
+/// * This uses the xterm window operations (`XTWINOPS`, `CSI 9 ; 1 t`), many terminals ignore
+///   them or only allow them when configured to. The WinAPI uses `ShowWindow` on the console
+///   window.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaximizeWindow;
+
+impl Command for MaximizeWindow {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("9;1t"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::maximize_window()
+    }
+}
+
+/// A command that restores the terminal window after
+/// [MinimizeWindow](./struct.MinimizeWindow.html) or
+/// [MaximizeWindow](./struct.MaximizeWindow.html).
+///
+/// # Notes
+///
+/// * This uses the xterm window operations (`XTWINOPS`, `CSI 1 t` and `CSI 9 ; 0 t`), many
+///   terminals ignore them or only allow them when configured to. The WinAPI uses `ShowWindow`
+///   on the console window.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestoreWindow;
+
+impl Command for RestoreWindow {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("1t"))?;
+        f.write_str(csi!("9;0t"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::restore_window()
+    }
+}
+
+/// A command that raises the terminal window above the other windows.
+///
+/// # Notes
+///
+/// * This uses the xterm window operations (`XTWINOPS`, `CSI 5 t`), many terminals ignore them
+///   or only allow them when configured to. The WinAPI uses `SetWindowPos` on the console
+///   window.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaiseWindow;
+
+impl Command for RaiseWindow {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("5t"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::raise_window()
+    }
+}
+
+/// A command that moves the terminal window to the given screen position `(x, y)` in pixels.
+///
+/// # Notes
+///
+/// * The position is the top left corner of the window.
+/// * This uses the xterm window operations (`XTWINOPS`, `CSI 3 ; x ; y t`), many terminals
+///   ignore them or only allow them when configured to. The WinAPI uses `SetWindowPos` on the
+///   console window.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveWindow(pub u16, pub u16);
+
+impl Command for MoveWindow {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, csi!("3;{};{}t"), self.0, self.1)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::move_window(self.0, self.1)
+    }
+}
+
+/// A command that sets the terminal title
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{execute, terminal::SetTitle, Result};
+///
+/// fn main() -> Result<()> {
+///     for percent in (0..=100).step_by(25) {
+///         execute!(stdout(), SetTitle(format!("backup - {}%", percent)))?;
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * On UNIX and Windows 10 this uses `OSC 0` (the window and the icon/tab title), the legacy
+///   Windows console uses `SetConsoleTitleW`.
+/// * Control characters are removed from the ANSI title, they would end the escape sequence.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetTitle<T>(pub T);
+
+impl<T: fmt::Display> Command for SetTitle<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1B]0;")?;
+        fmt::Write::write_fmt(&mut WithoutControlChars(f), format_args!("{}", &self.0))?;
+        f.write_str("\x07")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::set_window_title(&self.0)
+    }
+}
+
+/// A command that reports the working directory of the application to the terminal.
+///
+/// Terminals use it to open new tabs or windows in the same directory, e.g. when a shell or a
+/// file manager changes the directory.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::{env, io::{stdout, Write}};
+///
+/// use crossterm::{execute, terminal::SetWorkingDirectory, Result};
+///
+/// fn main() -> Result<()> {
+///     execute!(stdout(), SetWorkingDirectory(env::current_dir()?))
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * This uses `OSC 7` with a `file://` URL of the path on the local host name. Terminals which
+///   don't support it ignore it.
+/// * The path should be absolute.
+/// * The legacy Windows console doesn't support it, the command does nothing.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetWorkingDirectory<T>(pub T);
+
+impl<T: AsRef<Path>> Command for SetWorkingDirectory<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1B]7;file://")?;
+        if let Some(hostname) = sys::hostname() {
+            write_percent_encoded(f, hostname.as_bytes())?;
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            write_percent_encoded(f, self.0.as_ref().as_os_str().as_bytes())?;
+        }
+        #[cfg(windows)]
+        {
+            // `C:\dir` is `file:///C:/dir`
+            let path = self.0.as_ref().to_string_lossy().replace('\\', "/");
+            if !path.starts_with('/') {
+                f.write_char('/')?;
+            }
+            write_percent_encoded(f, path.as_bytes())?;
+        }
+
+        f.write_str("\x1B\\")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Writes the bytes with the URL percent-encoding, except the unreserved characters and `/`.
+fn write_percent_encoded(f: &mut impl fmt::Write, bytes: &[u8]) -> fmt::Result {
+    for &byte in bytes {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            f.write_char(char::from(byte))?;
+        } else {
+            write!(f, "%{:02X}", byte)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the title of the terminal window.
+///
+/// E.g. an application can restore the original title on exit after changing it with
+/// [SetTitle](./struct.SetTitle.html).
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{execute, terminal::{self, SetTitle}, Result};
+///
+/// fn main() -> Result<()> {
+///     let original_title = terminal::title()?;
+///     execute!(stdout(), SetTitle("my app"))?;
+///
+///     // ...
+///
+///     execute!(stdout(), SetTitle(original_title))
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * On UNIX, the terminal is queried with `CSI 21 t` (the raw mode is enabled while waiting for
+///   the answer if it isn't enabled yet). Any program can set the title, many terminals only
+///   answer when configured to or answer with an empty title. An error is returned if there is
+///   no answer within 2 seconds.
+/// * Control characters are removed from the reported title, so printing it can't inject escape
+///   sequences.
+/// * On Windows, `GetConsoleTitleW` is used.
+pub fn title() -> Result<String> {
+    sys::title()
+}
+
+/// A command that tells the terminal to stop rendering until
+/// [EndSynchronizedUpdate](./struct.EndSynchronizedUpdate.html) is received.
+///
+/// The terminal renders the whole frame at once, avoiding the tearing (partly drawn frames) of
+/// large redraws.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{
+///     cursor::MoveTo,
+///     queue,
+///     style::Print,
+///     terminal::{BeginSynchronizedUpdate, Clear, ClearType, EndSynchronizedUpdate},
+///     Result,
+/// };
+///
+/// fn main() -> Result<()> {
+///     let mut stdout = stdout();
+///
+///     queue!(stdout, BeginSynchronizedUpdate, Clear(ClearType::All))?;
+///     for row in 0..20 {
+///         queue!(stdout, MoveTo(0, row), Print(format!("row {}", row)))?;
+///     }
+///     queue!(stdout, EndSynchronizedUpdate)?;
+///     stdout.flush()?;
+///     Ok(())
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * This uses the synchronized output mode (`CSI ? 2026 h`), terminals which don't support it
+///   ignore it and render as usual.
+/// * Terminals end the update after a timeout, a missing `EndSynchronizedUpdate` doesn't freeze
+///   them forever. Don't forget it anyway.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeginSynchronizedUpdate;
+
+impl Command for BeginSynchronizedUpdate {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?2026h"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that tells the terminal to render the update started with
+/// [BeginSynchronizedUpdate](./struct.BeginSynchronizedUpdate.html).
+///
+/// # Notes
+///
+/// Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EndSynchronizedUpdate;
+
+impl Command for EndSynchronizedUpdate {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(csi!("?2026l"))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A command that rings the terminal bell.
+///
+/// Depending on the terminal configuration this plays a sound, flashes the screen or marks the
+/// window/tab as urgent, e.g. when a long running task is done.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{execute, style::Print, terminal::Bell, Result};
+///
+/// fn main() -> Result<()> {
+///     execute!(stdout(), Print("Build finished"), Bell)
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * On UNIX and Windows 10 this writes `BEL` (`\x07`), the legacy Windows console plays the
+///   default sound with `MessageBeep`.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bell;
+
+impl Command for Bell {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x07")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::bell()
+    }
+}
+
+/// A command that copies the given text to the system clipboard.
+///
+/// E.g. a TUI can implement a yank command, even when it runs on a remote host over SSH.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{execute, terminal::CopyToClipboard, Result};
+///
+/// fn main() -> Result<()> {
+///     execute!(stdout(), CopyToClipboard("yanked text"))
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * This uses `OSC 52` with the base64 encoded text. Some terminals ignore it, or only allow it
+///   when configured to.
+/// * The legacy Windows console uses the Windows clipboard instead.
+/// * See [clipboard_contents](./fn.clipboard_contents.html) to read the clipboard.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopyToClipboard<T>(pub T);
+
+impl<T: fmt::Display> Command for CopyToClipboard<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1B]52;c;")?;
+        base64::encode(f, self.0.to_string().as_bytes())?;
+        f.write_str("\x1B\\")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        sys::set_clipboard_contents(&self.0.to_string())
+    }
+}
+
+/// Returns the text of the system clipboard.
+///
+/// # Examples
+///
+/// ```no_run
+/// use crossterm::{terminal, Result};
+///
+/// fn main() -> Result<()> {
+///     println!("pasted: {}", terminal::clipboard_contents()?);
+///     Ok(())
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * On UNIX, the terminal is queried with `OSC 52` (the raw mode is enabled while waiting for
+///   the answer if it isn't enabled yet). Most terminals don't answer unless they are configured
+///   to, as any program could read the clipboard otherwise, an error is returned if there is no
+///   answer within 2 seconds.
+/// * On Windows, the Windows clipboard is read, the text is empty if it doesn't contain text.
+pub fn clipboard_contents() -> Result<String> {
+    sys::clipboard_contents()
+}
+
+/// A command that shows a desktop notification with the given title and body.
+///
+/// E.g. a long-running job can notify the user when it is finished.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::{stdout, Write};
+///
+/// use crossterm::{execute, terminal::Notify, Result};
+///
+/// fn main() -> Result<()> {
+///     // ... build
+///     execute!(stdout(), Notify("build", "finished in 42s"))
+/// }
+/// ```
+///
+/// # Notes
+///
+/// * The sequence depends on the terminal: `OSC 99` for kitty, `OSC 9` (body only) for iTerm2
+///   and `OSC 777` (rxvt-unicode, foot, WezTerm, Ghostty, ...) for the others. Terminals which
+///   don't support them ignore them.
+/// * Control characters are removed, and `;` from the title (it separates the `OSC 777` fields).
+/// * The legacy Windows console doesn't support notifications, the command does nothing.
+/// * Commands must be executed/queued for execution otherwise they do nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Notify<T, B>(pub T, pub B);
+
+impl<T: fmt::Display, B: fmt::Display> Command for Notify<T, B> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let protocol = notification_protocol(
+            env::var("TERM").ok().as_deref(),
+            env::var("TERM_PROGRAM").ok().as_deref(),
+        );
+        write_notification(f, protocol, &self.0, &self.1)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self, _writer: impl FnMut() -> Result<()>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The escape sequences for desktop notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NotificationProtocol {
+    /// `OSC 9 ; body`
+    Osc9,
+    /// `OSC 99 ; metadata ; payload` (kitty)
+    Osc99,
+    /// `OSC 777 ; notify ; title ; body`
+    Osc777,
+}
+
+/// Returns the notification protocol of the terminal given by the `TERM` and `TERM_PROGRAM`
+/// environment variables.
+fn notification_protocol(term: Option<&str>, term_program: Option<&str>) -> NotificationProtocol {
+    if term == Some("xterm-kitty") {
+        NotificationProtocol::Osc99
+    } else if term_program == Some("iTerm.app") {
+        NotificationProtocol::Osc9
+    } else {
+        NotificationProtocol::Osc777
+    }
+}
+
+fn write_notification(
+    f: &mut impl fmt::Write,
+    protocol: NotificationProtocol,
+    title: impl fmt::Display,
+    body: impl fmt::Display,
+) -> fmt::Result {
+    let title = title.to_string().replace(';', "");
+    match protocol {
+        NotificationProtocol::Osc9 => {
+            f.write_str("\x1B]9;")?;
+            fmt::Write::write_fmt(&mut WithoutControlChars(f), format_args!("{}", body))?;
+        }
+        NotificationProtocol::Osc99 => {
+            // The title and the body are sent in two parts of the same notification
+            f.write_str("\x1B]99;i=1:d=0;")?;
+            fmt::Write::write_str(&mut WithoutControlChars(f), &title)?;
+            f.write_str("\x1B\\\x1B]99;i=1:d=1:p=body;")?;
+            fmt::Write::write_fmt(&mut WithoutControlChars(f), format_args!("{}", body))?;
+        }
+        NotificationProtocol::Osc777 => {
+            f.write_str("\x1B]777;notify;")?;
+            fmt::Write::write_str(&mut WithoutControlChars(f), &title)?;
+            f.write_char(';')?;
+            fmt::Write::write_fmt(&mut WithoutControlChars(f), format_args!("{}", body))?;
+        }
+    }
+    f.write_str("\x1B\\")
+}
+
+/// A writer which drops the control characters (e.g. `BEL` or `ESC`) of the written text.
+struct WithoutControlChars<'a, W>(&'a mut W);
+
+impl<W: fmt::Write> fmt::Write for WithoutControlChars<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for part in s.split(char::is_control) {
+            self.0.write_str(part)?;
+        }
+        Ok(())
+    }
+}
+
+impl_display!(for ScrollUp);
+impl_display!(for ScrollDown);
+impl_display!(for SetScrollRegion);
+impl_display!(for ResetScrollRegion);
+impl_display!(for InsertLines);
+impl_display!(for DeleteLines);
+impl_display!(for InsertChars);
+impl_display!(for DeleteChars);
+impl_display!(for SetTabStop);
+impl_display!(for ClearTabStop);
+impl_display!(for ClearAllTabStops);
+impl_display!(for SetSize);
+impl_display!(for MinimizeWindow);
+impl_display!(for MaximizeWindow);
+impl_display!(for RestoreWindow);
+impl_display!(for RaiseWindow);
+impl_display!(for MoveWindow);
+impl_display!(for Clear);
+impl_display!(for Bell);
+impl_display!(for SoftReset);
+impl_display!(for HardReset);
+impl_display!(for DisableLineWrap);
+impl_display!(for EnableLineWrap);
+impl_display!(for BeginSynchronizedUpdate);
+impl_display!(for EndSynchronizedUpdate);
+
+#[cfg(test)]
+mod tests {
+    use std::{io::stdout, sync::atomic::Ordering, thread, time};
+
+    use parking_lot::Mutex;
+
+    use crate::{execute, Command};
+
+    use super::{
+        colorfgbg_is_dark, is_alternate_screen_active, notification_protocol, size,
+        write_notification, BeginSynchronizedUpdate, Bell, Clear, ClearAllTabStops, ClearTabStop,
+        ClearType, CopyToClipboard, DeleteChars, DeleteLines, DisableLineWrap, EnableLineWrap,
+        EndSynchronizedUpdate, EnterAlternateScreen, HardReset, InsertChars, InsertLines,
+        LeaveAlternateScreen, MaximizeWindow, MinimizeWindow, MoveWindow, NotificationProtocol,
+        RaiseWindow, ResetScrollRegion, RestoreWindow, ScrollDown, ScrollUp, SetScrollRegion,
+        SetSize, SetTabStop, SetTitle, SetWorkingDirectory, SoftReset, ALTERNATE_SCREEN,
+    };
+
+    // Serializes the tests which change whether the alternate screen is active.
+    static ALTERNATE_SCREEN_TEST: Mutex<()> = parking_lot::const_mutex(());
+
+    #[test]
+    fn test_scroll_region_ansi() {
+        assert_eq!(SetScrollRegion(1, 22).to_string(), "\x1B[2;23r");
+        assert_eq!(SetScrollRegion(0, 0).to_string(), "\x1B[1;1r");
+        assert_eq!(ResetScrollRegion.to_string(), "\x1B[r");
+    }
+
+    #[test]
+    fn test_line_wrap_ansi() {
+        assert_eq!(DisableLineWrap.to_string(), "\x1B[?7l");
+        assert_eq!(EnableLineWrap.to_string(), "\x1B[?7h");
+    }
+
+    #[test]
+    fn test_synchronized_update_ansi() {
+        assert_eq!(BeginSynchronizedUpdate.to_string(), "\x1B[?2026h");
+        assert_eq!(EndSynchronizedUpdate.to_string(), "\x1B[?2026l");
+    }
+
+    #[test]
+    fn test_bell_ansi() {
+        assert_eq!(Bell.to_string(), "\x07");
+    }
+
+    #[test]
+    fn test_set_title_ansi() {
+        let mut ansi = String::new();
+        SetTitle("my app").write_ansi(&mut ansi).unwrap();
+        assert_eq!(ansi, "\x1B]0;my app\x07");
+
+        ansi.clear();
+        SetTitle("evil\x07\x1B[2Jtitle\n")
+            .write_ansi(&mut ansi)
+            .unwrap();
+        assert_eq!(ansi, "\x1B]0;evil[2Jtitle\x07");
+    }
+
+    #[test]
+    fn test_alternate_screen_ansi() {
+        let _lock = ALTERNATE_SCREEN_TEST.lock();
+
+        let mut ansi = String::new();
+        EnterAlternateScreen.write_ansi(&mut ansi).unwrap();
+        assert_eq!(ansi, "\x1B[?1049h");
+        // Formatting doesn't enter the alternate screen
+        assert!(!is_alternate_screen_active());
+
+        ansi.clear();
+        LeaveAlternateScreen.write_ansi(&mut ansi).unwrap();
+        assert_eq!(ansi, "\x1B[?1049l");
+
+        #[cfg(unix)]
+        {
+            let mut ansi = Vec::new();
+            crate::queue!(ansi, EnterAlternateScreen).unwrap();
+            assert!(is_alternate_screen_active());
+            crate::queue!(ansi, LeaveAlternateScreen).unwrap();
+            assert!(!is_alternate_screen_active());
+            assert_eq!(ansi, b"\x1B[?1049h\x1B[?1049l");
+        }
+
+        // Formatting `RIS` doesn't forget the alternate screen
+        ALTERNATE_SCREEN.store(true, Ordering::SeqCst);
+        HardReset.write_ansi(&mut ansi).unwrap();
+        assert!(is_alternate_screen_active());
+        ALTERNATE_SCREEN.store(false, Ordering::SeqCst);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_alternate_screen_guard() {
+        use super::AlternateScreen;
+
+        let _lock = ALTERNATE_SCREEN_TEST.lock();
+
+        let mut outer = AlternateScreen::enter(Vec::new()).unwrap();
+        assert_eq!(outer.writer, b"\x1B[?1049h");
+        assert!(is_alternate_screen_active());
+
+        let inner = AlternateScreen::enter(Vec::new()).unwrap();
+        assert!(inner.writer.is_empty());
+        drop(inner);
+        assert!(is_alternate_screen_active());
+
+        outer.writer.clear();
+        outer.leave_alternate_screen().unwrap();
+        assert_eq!(outer.writer, b"\x1B[?1049l");
+        assert!(!is_alternate_screen_active());
+    }
+
+    #[test]
+    fn test_scroll_ansi() {
+        assert_eq!(ScrollUp(3).to_string(), "\x1B[3S");
+        assert_eq!(ScrollDown(2).to_string(), "\x1B[2T");
+        // `CSI 0 S` would scroll one row
+        assert_eq!(ScrollUp(0).to_string(), "");
+        assert_eq!(ScrollDown(0).to_string(), "");
+    }
+
+    #[test]
+    fn test_insert_delete_ansi() {
+        assert_eq!(InsertLines(2).to_string(), "\x1B[2L");
+        assert_eq!(DeleteLines(3).to_string(), "\x1B[3M");
+        assert_eq!(InsertChars(4).to_string(), "\x1B[4@");
+        assert_eq!(DeleteChars(5).to_string(), "\x1B[5P");
+        // `CSI 0 L` would insert one row
+        assert_eq!(InsertLines(0).to_string(), "");
+        assert_eq!(DeleteChars(0).to_string(), "");
+    }
+
+    #[test]
+    fn test_soft_reset_ansi() {
+        assert_eq!(SoftReset.to_string(), "\x1B[!p");
+    }
+
+    #[test]
+    fn test_hard_reset_ansi() {
+        assert_eq!(HardReset.to_string(), "\x1Bc");
+    }
+
+    #[test]
+    fn test_tab_stops_ansi() {
+        assert_eq!(SetTabStop.to_string(), "\x1BH");
+        assert_eq!(ClearTabStop.to_string(), "\x1B[g");
+        assert_eq!(ClearAllTabStops.to_string(), "\x1B[3g");
+    }
+
+    #[test]
+    fn test_set_working_directory_ansi() {
+        let hostname = super::sys::hostname().unwrap_or_default();
+        let mut ansi = String::new();
+        SetWorkingDirectory("/home/user/my dir")
+            .write_ansi(&mut ansi)
+            .unwrap();
+        assert_eq!(
+            ansi,
+            format!("\x1B]7;file://{}/home/user/my%20dir\x1B\\", hostname)
+        );
+    }
+
+    #[test]
+    fn test_window_operations_ansi() {
+        assert_eq!(MinimizeWindow.to_string(), "\x1B[2t");
+        assert_eq!(MaximizeWindow.to_string(), "\x1B[9;1t");
+        assert_eq!(RestoreWindow.to_string(), "\x1B[1t\x1B[9;0t");
+        assert_eq!(RaiseWindow.to_string(), "\x1B[5t");
+        assert_eq!(MoveWindow(10, 20).to_string(), "\x1B[3;10;20t");
+    }
+
+    #[test]
+    fn test_colorfgbg_is_dark() {
+        assert_eq!(colorfgbg_is_dark("15;0"), Some(true));
+        assert_eq!(colorfgbg_is_dark("0;default;15"), Some(false));
+        assert_eq!(colorfgbg_is_dark("0;7"), Some(false));
+        assert_eq!(colorfgbg_is_dark("7;8"), Some(true));
+        assert_eq!(colorfgbg_is_dark("15;default"), None);
+        assert_eq!(colorfgbg_is_dark(""), None);
+    }
+
+    #[test]
+    fn test_notification_protocol() {
+        assert_eq!(
+            notification_protocol(Some("xterm-kitty"), None),
+            NotificationProtocol::Osc99
+        );
+        assert_eq!(
+            notification_protocol(Some("xterm-256color"), Some("iTerm.app")),
+            NotificationProtocol::Osc9
+        );
+        assert_eq!(
+            notification_protocol(Some("foot"), None),
+            NotificationProtocol::Osc777
+        );
+    }
+
+    #[test]
+    fn test_write_notification() {
+        let write = |protocol| {
+            let mut ansi = String::new();
+            write_notification(&mut ansi, protocol, "build; done", "took\x07 42s; ok").unwrap();
+            ansi
+        };
+
+        assert_eq!(
+            write(NotificationProtocol::Osc9),
+            "\x1B]9;took 42s; ok\x1B\\"
+        );
+        assert_eq!(
+            write(NotificationProtocol::Osc99),
+            "\x1B]99;i=1:d=0;build done\x1B\\\x1B]99;i=1:d=1:p=body;took 42s; ok\x1B\\"
+        );
+        assert_eq!(
+            write(NotificationProtocol::Osc777),
+            "\x1B]777;notify;build done;took 42s; ok\x1B\\"
+        );
+    }
+
+    #[test]
+    fn test_copy_to_clipboard_ansi() {
+        let mut ansi = String::new();
+        CopyToClipboard("hello").write_ansi(&mut ansi).unwrap();
+        assert_eq!(ansi, "\x1B]52;c;aGVsbG8=\x1B\\");
+    }
+
+    #[test]
+    fn test_clear_ansi() {
+        assert_eq!(Clear(ClearType::All).to_string(), "\x1B[2J");
+        assert_eq!(Clear(ClearType::FromCursorDown).to_string(), "\x1B[J");
+        assert_eq!(Clear(ClearType::FromCursorUp).to_string(), "\x1B[1J");
+        assert_eq!(Clear(ClearType::CurrentLine).to_string(), "\x1B[2K");
+        assert_eq!(Clear(ClearType::UntilNewLine).to_string(), "\x1B[K");
+    }
+
+    #[test]
+    fn test_set_size_ansi() {
+        assert_eq!(SetSize(80, 24).to_string(), "\x1B[8;24;80t");
+    }
+
+    #[test]
+    fn test_size() {
+        // The tests may run without a terminal
+        if let Ok((columns, rows)) = size() {
+            assert!(columns > 0);
+            assert!(rows > 0);
+        }
+    }
 
     // Test is disabled, because it's failing on Travis CI
     #[test]
@@ -0,0 +1,87 @@
+//! The standard base64 encoding (with padding) of the `OSC 52` clipboard sequences.
+
+use std::fmt;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Writes the base64 encoding of the bytes.
+pub(crate) fn encode(f: &mut impl fmt::Write, bytes: &[u8]) -> fmt::Result {
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = u32::from(b[0]) << 16 | u32::from(b[1]) << 8 | u32::from(b[2]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (n >> (18 - 6 * i)) & 0x3F;
+                f.write_char(char::from(ALPHABET[index as usize]))?;
+            } else {
+                f.write_char('=')?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the bytes of the base64 encoded text, `None` if it is not valid base64.
+#[cfg(unix)]
+pub(crate) fn decode(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim_end_matches('=');
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+
+    let mut n = 0u32;
+    let mut bits = 0;
+    for c in text.bytes() {
+        let value = ALPHABET.iter().position(|&a| a == c)?;
+        n = n << 6 | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((n >> bits) as u8);
+        }
+    }
+
+    // A single character left over can't encode a byte
+    if bits >= 6 {
+        return None;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encode;
+
+    #[test]
+    fn test_encode() {
+        let encoded = |bytes: &[u8]| {
+            let mut text = String::new();
+            encode(&mut text, bytes).unwrap();
+            text
+        };
+
+        assert_eq!(encoded(b""), "");
+        assert_eq!(encoded(b"f"), "Zg==");
+        assert_eq!(encoded(b"fo"), "Zm8=");
+        assert_eq!(encoded(b"foo"), "Zm9v");
+        assert_eq!(encoded(b"foob"), "Zm9vYg==");
+        assert_eq!(encoded("é\n".as_bytes()), "w6kK");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_decode() {
+        use super::decode;
+
+        assert_eq!(decode(""), Some(vec![]));
+        assert_eq!(decode("Zg=="), Some(b"f".to_vec()));
+        assert_eq!(decode("Zm8="), Some(b"fo".to_vec()));
+        assert_eq!(decode("Zm9vYg=="), Some(b"foob".to_vec()));
+        assert_eq!(decode("Zm9vYg"), Some(b"foob".to_vec()));
+        assert_eq!(decode("Z"), None);
+        assert_eq!(decode("Zm9v!"), None);
+    }
+}
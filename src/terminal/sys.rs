@@ -1,11 +1,17 @@
 //! This module provides platform related functions.
 
 #[cfg(unix)]
-pub(crate) use self::unix::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled, size};
+pub(crate) use self::unix::{
+    capabilities, clipboard_contents, disable_raw_mode, enable_raw_mode, enable_suspend_handling,
+    hostname, is_raw_mode_enabled, query_control_string, size, size_pixels, suspend, title, tput,
+    write_query,
+};
 #[cfg(windows)]
 pub(crate) use self::windows::{
-    clear, disable_raw_mode, enable_raw_mode, scroll_down, scroll_up, set_size, set_window_title,
-    size,
+    bell, capabilities, clear, clipboard_contents, delete_chars, delete_lines, disable_raw_mode,
+    enable_raw_mode, hostname, insert_chars, insert_lines, is_raw_mode_enabled, maximize_window,
+    minimize_window, move_window, raise_window, restore_window, scroll_down, scroll_up,
+    set_clipboard_contents, set_size, set_window_title, size, size_pixels, title,
 };
 
 #[cfg(windows)]
@@ -1,28 +1,45 @@
 //! UNIX related logic for terminal manipulation.
 
-use std::fs::File;
+use std::fs::{self, File};
+use std::io::Write;
 use std::os::unix::io::{IntoRawFd, RawFd};
-use std::{io, mem, process};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{io, mem, process, thread, time::Duration};
 
 use libc::{
     cfmakeraw, ioctl, tcgetattr, tcsetattr, termios as Termios, winsize, STDOUT_FILENO, TCSANOW,
     TIOCGWINSZ,
 };
 use parking_lot::Mutex;
+use signal_hook::iterator::Signals;
 
 use crate::error::{ErrorKind, Result};
-use crate::event::sys::unix::file_descriptor::{tty_fd, FileDesc};
+use crate::event::{
+    filter::{CapabilitiesFilter, ClipboardFilter, PixelSizeFilter, TitleFilter},
+    poll_internal, read_internal,
+    sys::unix::{
+        file_descriptor::{tty_fd, FileDesc},
+        parse::set_string_answer_pending,
+    },
+    InternalEvent,
+};
+use crate::execute;
+use crate::style::{color_support, ColorSupport};
+use crate::terminal::{self, is_alternate_screen_active, Capabilities, EnterAlternateScreen};
 
 // Some(Termios) -> we're in the raw mode and this is the previous mode
 // None -> we're not in the raw mode
 static TERMINAL_MODE_PRIOR_RAW_MODE: Mutex<Option<Termios>> = parking_lot::const_mutex(None);
 
+// Whether the `SIGTSTP` signal is handled by `suspend`
+static SUSPEND_HANDLING: AtomicBool = AtomicBool::new(false);
+
 pub(crate) fn is_raw_mode_enabled() -> bool {
     TERMINAL_MODE_PRIOR_RAW_MODE.lock().is_some()
 }
 
 #[allow(clippy::useless_conversion)]
-pub(crate) fn size() -> Result<(u16, u16)> {
+fn window_size() -> Result<winsize> {
     // http://rosettacode.org/wiki/Terminal_control/Dimensions#Library:_BSD_libc
     let mut size = winsize {
         ws_row: 0,
@@ -31,7 +48,7 @@ pub(crate) fn size() -> Result<(u16, u16)> {
         ws_ypixel: 0,
     };
 
-    let file = File::open("/dev/tty").map(|file| (FileDesc::new(file.into_raw_fd(), true)));
+    let file = File::open("/dev/tty").map(|file| FileDesc::new(file.into_raw_fd(), true));
     let fd = if let Ok(file) = &file {
         file.raw_fd()
     } else {
@@ -39,10 +56,56 @@ pub(crate) fn size() -> Result<(u16, u16)> {
         STDOUT_FILENO
     };
 
-    if wrap_with_result(unsafe { ioctl(fd, TIOCGWINSZ.into(), &mut size) }).is_ok() {
-        Ok((size.ws_col, size.ws_row))
-    } else {
-        tput_size().ok_or_else(|| std::io::Error::last_os_error().into())
+    wrap_with_result(unsafe { ioctl(fd, TIOCGWINSZ.into(), &mut size) })?;
+    Ok(size)
+}
+
+pub(crate) fn size() -> Result<(u16, u16)> {
+    // Some pseudo terminals (e.g. of CI runners or serial consoles) report a size of 0x0
+    match window_size() {
+        Ok(size) if size.ws_col > 0 && size.ws_row > 0 => Ok((size.ws_col, size.ws_row)),
+        _ => tput_size().ok_or_else(|| {
+            io::Error::other("The terminal size can not be determined (TIOCGWINSZ & tput)").into()
+        }),
+    }
+}
+
+pub(crate) fn size_pixels() -> Result<(u16, u16)> {
+    // Many terminals (and terminal multiplexers) leave the pixel fields at 0
+    match window_size() {
+        Ok(size) if size.ws_xpixel > 0 && size.ws_ypixel > 0 => {
+            Ok((size.ws_xpixel, size.ws_ypixel))
+        }
+        _ if is_raw_mode_enabled() => read_size_pixels_raw(),
+        _ => {
+            enable_raw_mode()?;
+            let size = read_size_pixels_raw();
+            disable_raw_mode()?;
+            size
+        }
+    }
+}
+
+fn read_size_pixels_raw() -> Result<(u16, u16)> {
+    // Use `ESC [ 14 t` to report the text area size in pixels.
+    write_query(b"\x1B[14t")?;
+
+    loop {
+        match poll_internal(Some(Duration::from_millis(2000)), &PixelSizeFilter) {
+            Ok(true) => {
+                if let Ok(InternalEvent::PixelSize(width, height)) = read_internal(&PixelSizeFilter)
+                {
+                    return Ok((width, height));
+                }
+            }
+            Ok(false) => {
+                return Err(io::Error::other(
+                    "The terminal size in pixels could not be read within a normal duration",
+                )
+                .into());
+            }
+            Err(_) => {}
+        }
     }
 }
 
@@ -80,6 +143,252 @@ pub(crate) fn disable_raw_mode() -> Result<()> {
     Ok(())
 }
 
+/// Restores the terminal, stops the process and sets the terminal up again once it's continued.
+pub(crate) fn suspend() -> Result<()> {
+    let raw_mode = is_raw_mode_enabled();
+    let alternate_screen = is_alternate_screen_active();
+    terminal::restore();
+
+    // Stops the process, `raise` returns after the process was continued with `SIGCONT`
+    wrap_with_result(unsafe { libc::raise(libc::SIGSTOP) })?;
+
+    if raw_mode {
+        enable_raw_mode()?;
+    }
+    if alternate_screen {
+        execute!(io::stdout(), EnterAlternateScreen)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn enable_suspend_handling() -> Result<()> {
+    if SUSPEND_HANDLING.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+
+    let signals = match Signals::new([libc::SIGTSTP]) {
+        Ok(signals) => signals,
+        Err(e) => {
+            SUSPEND_HANDLING.store(false, Ordering::SeqCst);
+            return Err(e.into());
+        }
+    };
+
+    thread::spawn(move || {
+        for _ in signals.forever() {
+            let _ = suspend();
+        }
+    });
+    Ok(())
+}
+
+pub(crate) fn clipboard_contents() -> Result<String> {
+    if is_raw_mode_enabled() {
+        read_clipboard_contents_raw()
+    } else {
+        enable_raw_mode()?;
+        let contents = read_clipboard_contents_raw();
+        disable_raw_mode()?;
+        contents
+    }
+}
+
+fn read_clipboard_contents_raw() -> Result<String> {
+    // Use `ESC ] 52 ; c ; ? ESC \` to retrieve the clipboard contents.
+    query_control_string(b"\x1B]52;c;?\x1B\\", || loop {
+        match poll_internal(Some(Duration::from_millis(2000)), &ClipboardFilter) {
+            Ok(true) => {
+                if let Ok(InternalEvent::ClipboardContents(contents)) =
+                    read_internal(&ClipboardFilter)
+                {
+                    return Ok(contents);
+                }
+            }
+            Ok(false) => {
+                return Err(io::Error::other(
+                    "The clipboard contents could not be read within a normal duration",
+                )
+                .into());
+            }
+            Err(_) => {}
+        }
+    })
+}
+
+pub(crate) fn title() -> Result<String> {
+    if is_raw_mode_enabled() {
+        read_title_raw()
+    } else {
+        enable_raw_mode()?;
+        let title = read_title_raw();
+        disable_raw_mode()?;
+        title
+    }
+}
+
+fn read_title_raw() -> Result<String> {
+    // Use `ESC [ 21 t` to report the window title.
+    query_control_string(b"\x1B[21t", || loop {
+        match poll_internal(Some(Duration::from_millis(2000)), &TitleFilter) {
+            Ok(true) => {
+                if let Ok(InternalEvent::Title(title)) = read_internal(&TitleFilter) {
+                    return Ok(title);
+                }
+            }
+            Ok(false) => {
+                return Err(io::Error::other(
+                    "The terminal title could not be read within a normal duration",
+                )
+                .into());
+            }
+            Err(_) => {}
+        }
+    })
+}
+
+pub(crate) fn hostname() -> Option<String> {
+    let mut buffer = [0u8; 256];
+    if unsafe { libc::gethostname(buffer.as_mut_ptr() as *mut libc::c_char, buffer.len()) } != 0 {
+        return None;
+    }
+    let len = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+    String::from_utf8(buffer[..len].to_vec()).ok()
+}
+
+/// Writes a query (e.g. `ESC [ 6 n`) to the terminal.
+///
+/// The query is written to the standard output if it's a terminal, otherwise to `/dev/tty`, so
+/// that the terminal answers even if the output is redirected.
+pub(crate) fn write_query(query: &[u8]) -> Result<()> {
+    if unsafe { libc::isatty(STDOUT_FILENO) == 1 } {
+        let mut stdout = io::stdout();
+        stdout.write_all(query)?;
+        stdout.flush()?;
+    } else {
+        let mut tty = fs::OpenOptions::new().write(true).open("/dev/tty")?;
+        tty.write_all(query)?;
+        tty.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Writes a query which the terminal answers with a control string (e.g. the OSC answer to
+/// `ESC ] 10 ; ? ESC \`) and reads the answer with `read`.
+///
+/// `ESC ]` and `ESC P` are only parsed as the start of an answer while the query is pending.
+pub(crate) fn query_control_string<T>(query: &[u8], read: impl FnOnce() -> Result<T>) -> Result<T> {
+    set_string_answer_pending(true);
+    let result = write_query(query).and_then(|_| read());
+    set_string_answer_pending(false);
+    result
+}
+
+pub(crate) fn capabilities() -> Result<Capabilities> {
+    if is_raw_mode_enabled() {
+        read_capabilities_raw()
+    } else {
+        enable_raw_mode()?;
+        let capabilities = read_capabilities_raw();
+        disable_raw_mode()?;
+        capabilities
+    }
+}
+
+fn read_capabilities_raw() -> Result<Capabilities> {
+    // Query the modes with `ESC [ ? Pm $ p` and the `RGB` and `Tc` (24-bit colors) terminfo
+    // capabilities with `ESC P + q Pt ESC \`, followed by `ESC [ c` which is answered by every
+    // terminal. The terminals answer in order, the reports arrive before the device attributes.
+    let mut query = String::new();
+    for mode in &[2026, 1006, 2004, 1004] {
+        query.push_str(&format!("\x1B[?{}$p", mode));
+    }
+    for name in &["RGB", "Tc"] {
+        query.push_str("\x1BP+q");
+        for byte in name.bytes() {
+            query.push_str(&format!("{:02X}", byte));
+        }
+        query.push_str("\x1B\\");
+    }
+    query.push_str("\x1B[c");
+
+    query_control_string(query.as_bytes(), || {
+        // `None` until the terminal answers one of the terminfo queries
+        let mut true_color = None;
+        let mut capabilities = Capabilities::default();
+
+        read_capabilities_answers(&mut capabilities, &mut true_color)?;
+
+        // Fall back to the environment if the terminal doesn't support the terminfo queries
+        capabilities.true_color =
+            true_color.unwrap_or_else(|| color_support() == ColorSupport::TrueColor);
+        Ok(capabilities)
+    })
+}
+
+fn read_capabilities_answers(
+    capabilities: &mut Capabilities,
+    true_color: &mut Option<bool>,
+) -> Result<()> {
+    loop {
+        match poll_internal(Some(Duration::from_millis(2000)), &CapabilitiesFilter) {
+            Ok(true) => match read_internal(&CapabilitiesFilter) {
+                Ok(InternalEvent::ModeReport(mode, status)) => {
+                    // 1 - set, 2 - reset, 3 - permanently set, 4 - permanently reset
+                    // 0 - not recognized
+                    let supported = matches!(status, 1..=3);
+                    match mode {
+                        2026 => capabilities.synchronized_output = supported,
+                        1006 => capabilities.sgr_mouse = supported,
+                        2004 => capabilities.bracketed_paste = supported,
+                        1004 => capabilities.focus_events = supported,
+                        _ => {}
+                    }
+                }
+                Ok(InternalEvent::TerminfoCapability(name, valid))
+                    if name == "RGB" || name == "Tc" =>
+                {
+                    *true_color = Some(true_color.unwrap_or(false) || valid);
+                }
+                Ok(InternalEvent::PrimaryDeviceAttributes) => return Ok(()),
+                _ => {}
+            },
+            Ok(false) => {
+                return Err(io::Error::other(
+                    "The terminal capabilities could not be read within a normal duration",
+                )
+                .into());
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+// The output of the `tput` calls for the static capabilities (`None` = `tput` failed).
+static TPUT_OUTPUTS: Mutex<Vec<(&str, Option<String>)>> = parking_lot::const_mutex(Vec::new());
+
+/// Returns the output of `tput` for the given static terminfo capability (e.g. `colors`), `None`
+/// if it failed.
+///
+/// `tput` is executed once per capability, the output is reused by subsequent calls. Don't use it
+/// for values which change (e.g. `cols`).
+pub(crate) fn tput(capability: &'static str) -> Option<String> {
+    let mut outputs = TPUT_OUTPUTS.lock();
+
+    if let Some((_, output)) = outputs.iter().find(|(name, _)| *name == capability) {
+        return output.clone();
+    }
+
+    let output = process::Command::new("tput")
+        .arg(capability)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+    outputs.push((capability, output.clone()));
+    output
+}
+
 /// execute tput with the given argument and parse
 /// the output as a u16.
 ///
@@ -1,18 +1,58 @@
 //! WinAPI related logic for terminal manipulation.
 
+use std::convert::TryFrom;
 use std::fmt::{self, Write};
+use std::{io, mem, ptr};
 
-use crossterm_winapi::{Console, ConsoleMode, Coord, Handle, ScreenBuffer, Size};
+use crossterm_winapi::{
+    is_true, Console, ConsoleMode, Coord, Handle, ScreenBuffer, ScreenBufferInfo, Size,
+};
+use parking_lot::Mutex;
 use winapi::{
-    shared::minwindef::DWORD,
-    um::wincon::{SetConsoleTitleW, ENABLE_ECHO_INPUT, ENABLE_LINE_INPUT, ENABLE_PROCESSED_INPUT},
+    ctypes::c_int,
+    shared::{
+        minwindef::{DWORD, FALSE, UINT},
+        windef::HWND,
+    },
+    um::{
+        winbase::{GlobalAlloc, GlobalFree, GlobalLock, GlobalUnlock, GMEM_MOVEABLE},
+        wincon::{
+            GetConsoleTitleW, GetConsoleWindow, GetCurrentConsoleFont, ScrollConsoleScreenBufferW,
+            SetConsoleTitleW, CHAR_INFO, CONSOLE_FONT_INFO, COORD, ENABLE_ECHO_INPUT,
+            ENABLE_LINE_INPUT, ENABLE_PROCESSED_INPUT, SMALL_RECT,
+        },
+        winuser::{
+            CloseClipboard, EmptyClipboard, GetClipboardData, MessageBeep, OpenClipboard,
+            SetClipboardData, SetWindowPos, ShowWindow, CF_UNICODETEXT, HWND_TOP, MB_OK,
+            SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, SW_MAXIMIZE, SW_MINIMIZE, SW_RESTORE,
+        },
+    },
 };
 
-use crate::{cursor, terminal::ClearType, ErrorKind, Result};
+use crate::{
+    cursor,
+    style::{color_support, ColorSupport},
+    terminal::{Capabilities, ClearType},
+    ErrorKind, Result,
+};
 
 const RAW_MODE_MASK: DWORD = ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT | ENABLE_PROCESSED_INPUT;
 
+// Some(mode) -> we're in the raw mode and this is the previous console input mode
+// None -> we're not in the raw mode
+static CONSOLE_MODE_PRIOR_RAW_MODE: Mutex<Option<DWORD>> = parking_lot::const_mutex(None);
+
+pub(crate) fn is_raw_mode_enabled() -> bool {
+    CONSOLE_MODE_PRIOR_RAW_MODE.lock().is_some()
+}
+
 pub(crate) fn enable_raw_mode() -> Result<()> {
+    let mut original_mode = CONSOLE_MODE_PRIOR_RAW_MODE.lock();
+
+    if original_mode.is_some() {
+        return Ok(());
+    }
+
     let console_mode = ConsoleMode::from(Handle::current_in_handle()?);
 
     let dw_mode = console_mode.mode()?;
@@ -21,17 +61,21 @@ pub(crate) fn enable_raw_mode() -> Result<()> {
 
     console_mode.set_mode(new_mode)?;
 
+    // Keep it last - set the original mode only if we were able to switch to the raw mode
+    *original_mode = Some(dw_mode);
+
     Ok(())
 }
 
 pub(crate) fn disable_raw_mode() -> Result<()> {
-    let console_mode = ConsoleMode::from(Handle::current_in_handle()?);
+    let mut original_mode = CONSOLE_MODE_PRIOR_RAW_MODE.lock();
 
-    let dw_mode = console_mode.mode()?;
-
-    let new_mode = dw_mode | RAW_MODE_MASK;
-
-    console_mode.set_mode(new_mode)?;
+    if let Some(dw_mode) = *original_mode {
+        let console_mode = ConsoleMode::from(Handle::current_in_handle()?);
+        console_mode.set_mode(dw_mode)?;
+        // Keep it last - remove the original mode only if we were able to switch back
+        *original_mode = None;
+    }
 
     Ok(())
 }
@@ -45,6 +89,109 @@ pub(crate) fn size() -> Result<(u16, u16)> {
     ))
 }
 
+/// The clipboard, it is closed when dropped.
+struct Clipboard;
+
+impl Clipboard {
+    fn open() -> Result<Clipboard> {
+        if !is_true(unsafe { OpenClipboard(ptr::null_mut()) }) {
+            return Err(io::Error::last_os_error().into());
+        }
+        Ok(Clipboard)
+    }
+}
+
+impl Drop for Clipboard {
+    fn drop(&mut self) {
+        unsafe { CloseClipboard() };
+    }
+}
+
+pub(crate) fn set_clipboard_contents(text: &str) -> Result<()> {
+    let text: Vec<u16> = text.encode_utf16().chain(Some(0)).collect();
+
+    let _clipboard = Clipboard::open()?;
+    unsafe {
+        if !is_true(EmptyClipboard()) {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        // The clipboard owns the memory once `SetClipboardData` succeeded
+        let memory = GlobalAlloc(GMEM_MOVEABLE, text.len() * mem::size_of::<u16>());
+        if memory.is_null() {
+            return Err(io::Error::last_os_error().into());
+        }
+        let target = GlobalLock(memory) as *mut u16;
+        if target.is_null() {
+            let error = io::Error::last_os_error();
+            GlobalFree(memory);
+            return Err(error.into());
+        }
+        ptr::copy_nonoverlapping(text.as_ptr(), target, text.len());
+        GlobalUnlock(memory);
+
+        if SetClipboardData(CF_UNICODETEXT, memory).is_null() {
+            let error = io::Error::last_os_error();
+            GlobalFree(memory);
+            return Err(error.into());
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn clipboard_contents() -> Result<String> {
+    let _clipboard = Clipboard::open()?;
+    unsafe {
+        let memory = GetClipboardData(CF_UNICODETEXT);
+        if memory.is_null() {
+            // There is no text in the clipboard
+            return Ok(String::new());
+        }
+        let source = GlobalLock(memory) as *const u16;
+        if source.is_null() {
+            return Err(io::Error::last_os_error().into());
+        }
+        let mut len = 0;
+        while *source.add(len) != 0 {
+            len += 1;
+        }
+        let text = String::from_utf16_lossy(std::slice::from_raw_parts(source, len));
+        GlobalUnlock(memory);
+        Ok(text)
+    }
+}
+
+pub(crate) fn hostname() -> Option<String> {
+    std::env::var("COMPUTERNAME").ok()
+}
+
+pub(crate) fn size_pixels() -> Result<(u16, u16)> {
+    let screen_buffer = ScreenBuffer::current()?;
+    let (columns, rows) = size()?;
+
+    let mut font_info = CONSOLE_FONT_INFO {
+        nFont: 0,
+        dwFontSize: COORD { X: 0, Y: 0 },
+    };
+    unsafe {
+        if !is_true(GetCurrentConsoleFont(
+            **screen_buffer.handle(),
+            FALSE,
+            &mut font_info,
+        )) {
+            return Err(io::Error::last_os_error().into());
+        }
+    }
+
+    // All console cells have the size of the font
+    let width = u32::from(columns) * u32::try_from(font_info.dwFontSize.X).unwrap_or(0);
+    let height = u32::from(rows) * u32::try_from(font_info.dwFontSize.Y).unwrap_or(0);
+    Ok((
+        u16::try_from(width).unwrap_or(u16::MAX),
+        u16::try_from(height).unwrap_or(u16::MAX),
+    ))
+}
+
 pub(crate) fn clear(clear_type: ClearType) -> Result<()> {
     let screen_buffer = ScreenBuffer::current()?;
     let csbi = screen_buffer.info()?;
@@ -66,33 +213,122 @@ pub(crate) fn clear(clear_type: ClearType) -> Result<()> {
 }
 
 pub(crate) fn scroll_up(row_count: u16) -> Result<()> {
-    let csbi = ScreenBuffer::current()?;
-    let mut window = csbi.info()?.terminal_window();
+    scroll_window_content(-i32::from(row_count))
+}
 
-    // check whether the window is too close to the screen buffer top
-    let count = row_count as i16;
-    if window.top >= count {
-        window.top -= count; // move top down
-        window.bottom -= count; // move bottom down
+pub(crate) fn scroll_down(row_count: u16) -> Result<()> {
+    scroll_window_content(i32::from(row_count))
+}
 
-        Console::output()?.set_console_info(true, window)?;
-    }
-    Ok(())
+/// Moves the content of the visible window the given number of rows down (up if negative) and
+/// fills the freed rows with blanks, like `CSI T` (`CSI S`).
+fn scroll_window_content(row_count: i32) -> Result<()> {
+    let screen_buffer = ScreenBuffer::current()?;
+    let csbi = screen_buffer.info()?;
+    let window = csbi.terminal_window();
+
+    let window_rect = SMALL_RECT {
+        Left: window.left,
+        Top: window.top,
+        Right: window.right,
+        Bottom: window.bottom,
+    };
+    move_rect_content(&screen_buffer, &csbi, window_rect, 0, row_count)
 }
 
-pub(crate) fn scroll_down(row_count: u16) -> Result<()> {
+pub(crate) fn insert_lines(count: u16) -> Result<()> {
+    move_lines_below_cursor(i32::from(count))
+}
+
+pub(crate) fn delete_lines(count: u16) -> Result<()> {
+    move_lines_below_cursor(-i32::from(count))
+}
+
+pub(crate) fn insert_chars(count: u16) -> Result<()> {
+    move_chars_after_cursor(i32::from(count))
+}
+
+pub(crate) fn delete_chars(count: u16) -> Result<()> {
+    move_chars_after_cursor(-i32::from(count))
+}
+
+/// Moves the rows from the cursor row to the bottom of the window the given number of rows down
+/// (up if negative), like `CSI L` (`CSI M`).
+fn move_lines_below_cursor(row_count: i32) -> Result<()> {
     let screen_buffer = ScreenBuffer::current()?;
     let csbi = screen_buffer.info()?;
-    let mut window = csbi.terminal_window();
-    let buffer_size = csbi.buffer_size();
+    let window = csbi.terminal_window();
+    let cursor = csbi.cursor_pos();
+
+    let rect = SMALL_RECT {
+        Left: 0,
+        Top: cursor.y,
+        Right: csbi.buffer_size().width - 1,
+        Bottom: window.bottom,
+    };
+    move_rect_content(&screen_buffer, &csbi, rect, 0, row_count)
+}
+
+/// Moves the cells from the cursor to the end of the cursor row the given number of columns
+/// right (left if negative), like `CSI @` (`CSI P`).
+fn move_chars_after_cursor(column_count: i32) -> Result<()> {
+    let screen_buffer = ScreenBuffer::current()?;
+    let csbi = screen_buffer.info()?;
+    let cursor = csbi.cursor_pos();
+
+    let rect = SMALL_RECT {
+        Left: cursor.x,
+        Top: cursor.y,
+        Right: csbi.buffer_size().width - 1,
+        Bottom: cursor.y,
+    };
+    move_rect_content(&screen_buffer, &csbi, rect, column_count, 0)
+}
 
-    // check whether the window is too close to the screen buffer top
-    let count = row_count as i16;
-    if window.bottom < buffer_size.height - count {
-        window.top += count; // move top down
-        window.bottom += count; // move bottom down
+/// Moves the content of `rect` the given number of columns right and rows down (left and up if
+/// negative) and fills the freed cells with blanks.
+fn move_rect_content(
+    screen_buffer: &ScreenBuffer,
+    csbi: &ScreenBufferInfo,
+    rect: SMALL_RECT,
+    column_count: i32,
+    row_count: i32,
+) -> Result<()> {
+    let width = i32::from(rect.Right) - i32::from(rect.Left) + 1;
+    let height = i32::from(rect.Bottom) - i32::from(rect.Top) + 1;
+    if width <= 0 || height <= 0 {
+        return Ok(());
+    }
+
+    let column_count = column_count.clamp(-width, width);
+    let row_count = row_count.clamp(-height, height);
+    if column_count == 0 && row_count == 0 {
+        return Ok(());
+    }
 
-        Console::output()?.set_console_info(true, window)?;
+    // The rect is the moved area and the clipping area, the cells moved outside of it are
+    // discarded and the rest of the screen buffer is left untouched.
+    let destination = COORD {
+        X: (i32::from(rect.Left) + column_count) as i16,
+        Y: (i32::from(rect.Top) + row_count) as i16,
+    };
+
+    let mut fill: CHAR_INFO = unsafe { mem::zeroed() };
+    unsafe {
+        *fill.Char.UnicodeChar_mut() = ' ' as u16;
+    }
+    fill.Attributes = csbi.attributes();
+
+    unsafe {
+        if !is_true(ScrollConsoleScreenBufferW(
+            **screen_buffer.handle(),
+            &rect,
+            &rect,
+            destination,
+            &fill,
+        )) {
+            return Err(io::Error::last_os_error().into());
+        }
     }
     Ok(())
 }
@@ -118,6 +354,22 @@ pub(crate) fn set_size(width: u16, height: u16) -> Result<()> {
     let current_size = csbi.buffer_size();
     let window = csbi.terminal_window();
 
+    // Check the bounds first, a size which doesn't fit on the screen must not resize anything.
+    let bounds = console.largest_window_size();
+
+    if i32::from(width) > i32::from(bounds.x) {
+        return Err(ErrorKind::ResizingTerminalFailure(format!(
+            "Argument width: {} out of range when setting terminal width.",
+            width
+        )));
+    }
+    if i32::from(height) > i32::from(bounds.y) {
+        return Err(ErrorKind::ResizingTerminalFailure(format!(
+            "Argument height: {} out of range when setting terminal height.",
+            height
+        )));
+    }
+
     let mut new_size = Size::new(current_size.width, current_size.height);
 
     // If the buffer is smaller than this new window size, resize the
@@ -126,7 +378,7 @@ pub(crate) fn set_size(width: u16, height: u16) -> Result<()> {
 
     let width = width as i16;
     if current_size.width < window.left + width {
-        if window.left >= i16::max_value() - width {
+        if window.left >= i16::MAX - width {
             return Err(ErrorKind::ResizingTerminalFailure(String::from(
                 "Argument out of range when setting terminal width.",
             )));
@@ -137,7 +389,7 @@ pub(crate) fn set_size(width: u16, height: u16) -> Result<()> {
     }
     let height = height as i16;
     if current_size.height < window.top + height {
-        if window.top >= i16::max_value() - height {
+        if window.top >= i16::MAX - height {
             return Err(ErrorKind::ResizingTerminalFailure(String::from(
                 "Argument out of range when setting terminal height.",
             )));
@@ -175,21 +427,6 @@ pub(crate) fn set_size(width: u16, height: u16) -> Result<()> {
         )));
     }
 
-    let bounds = console.largest_window_size();
-
-    if width > bounds.x {
-        return Err(ErrorKind::ResizingTerminalFailure(format!(
-            "Argument width: {} out of range when setting terminal width.",
-            width
-        )));
-    }
-    if height > bounds.y {
-        return Err(ErrorKind::ResizingTerminalFailure(format!(
-            "Argument height: {} out of range when setting terminal height.",
-            width
-        )));
-    }
-
     Ok(())
 }
 
@@ -215,11 +452,85 @@ pub(crate) fn set_window_title(title: impl fmt::Display) -> Result<()> {
     }
 }
 
+pub(crate) fn title() -> Result<String> {
+    // Longer titles are truncated
+    let mut buffer = [0u16; 1024];
+    let len = unsafe { GetConsoleTitleW(buffer.as_mut_ptr(), buffer.len() as DWORD) };
+    if len == 0 {
+        let error = io::Error::last_os_error();
+        // An empty title is not an error
+        if error.raw_os_error() != Some(0) {
+            return Err(error.into());
+        }
+    }
+    Ok(String::from_utf16_lossy(
+        &buffer[..(len as usize).min(buffer.len())],
+    ))
+}
+
+pub(crate) fn capabilities() -> Result<Capabilities> {
+    // The answers of the terminal can't be read from the console input
+    Ok(Capabilities {
+        true_color: color_support() == ColorSupport::TrueColor,
+        ..Capabilities::default()
+    })
+}
+
+pub(crate) fn bell() -> Result<()> {
+    if !is_true(unsafe { MessageBeep(MB_OK) }) {
+        return Err(io::Error::last_os_error().into());
+    }
+    Ok(())
+}
+
+pub(crate) fn minimize_window() -> Result<()> {
+    show_window(SW_MINIMIZE)
+}
+
+pub(crate) fn maximize_window() -> Result<()> {
+    show_window(SW_MAXIMIZE)
+}
+
+pub(crate) fn restore_window() -> Result<()> {
+    show_window(SW_RESTORE)
+}
+
+pub(crate) fn raise_window() -> Result<()> {
+    set_window_pos(0, 0, SWP_NOMOVE)
+}
+
+pub(crate) fn move_window(x: u16, y: u16) -> Result<()> {
+    set_window_pos(i32::from(x), i32::from(y), SWP_NOZORDER)
+}
+
+fn console_window() -> Result<HWND> {
+    let window = unsafe { GetConsoleWindow() };
+    if window.is_null() {
+        return Err(io::Error::other("The console has no window").into());
+    }
+    Ok(window)
+}
+
+fn show_window(command: c_int) -> Result<()> {
+    // The return value is the previous visibility, not an error
+    unsafe { ShowWindow(console_window()?, command) };
+    Ok(())
+}
+
+fn set_window_pos(x: i32, y: i32, flags: UINT) -> Result<()> {
+    if !is_true(unsafe {
+        SetWindowPos(console_window()?, HWND_TOP, x, y, 0, 0, flags | SWP_NOSIZE)
+    }) {
+        return Err(io::Error::last_os_error().into());
+    }
+    Ok(())
+}
+
 fn clear_after_cursor(location: Coord, buffer_size: Size, current_attribute: u16) -> Result<()> {
     let (mut x, mut y) = (location.x, location.y);
 
     // if cursor position is at the outer right position
-    if x as i16 > buffer_size.width {
+    if x > buffer_size.width {
         y += 1;
         x = 0;
     }
@@ -227,8 +538,9 @@ fn clear_after_cursor(location: Coord, buffer_size: Size, current_attribute: u16
     // location where to start clearing
     let start_location = Coord::new(x, y);
 
-    // get sum cells before cursor
-    let cells_to_write = buffer_size.width as u32 * buffer_size.height as u32;
+    // get sum cells from the cursor until the end of the screen buffer
+    let cells_to_write = (buffer_size.width as u32 * (buffer_size.height - y).max(0) as u32)
+        .saturating_sub(x as u32);
 
     clear_winapi(start_location, cells_to_write, current_attribute)
 }
@@ -262,7 +574,7 @@ fn clear_entire_screen(buffer_size: Size, current_attribute: u16) -> Result<()>
     clear_winapi(start_location, cells_to_write, current_attribute)?;
 
     // put the cursor back at cell 0,0
-    cursor::sys::move_to(0, 0)?;
+    cursor::sys::move_to_buffer_position(0, 0)?;
     Ok(())
 }
 
@@ -273,12 +585,8 @@ fn clear_current_line(location: Coord, buffer_size: Size, current_attribute: u16
     // get sum cells before cursor
     let cells_to_write = buffer_size.width as u32;
 
-    // clear the whole current line
-    clear_winapi(start_location, cells_to_write, current_attribute)?;
-
-    // put the cursor back at cell 1 on current row
-    cursor::sys::move_to(0, location.y as u16)?;
-    Ok(())
+    // clear the whole current line, the cursor stays where it is (like `CSI 2 K`)
+    clear_winapi(start_location, cells_to_write, current_attribute)
 }
 
 fn clear_until_line(location: Coord, buffer_size: Size, current_attribute: u16) -> Result<()> {
@@ -288,14 +596,10 @@ fn clear_until_line(location: Coord, buffer_size: Size, current_attribute: u16)
     let start_location = Coord::new(x, y);
 
     // get sum cells before cursor
-    let cells_to_write = (buffer_size.width - x as i16) as u32;
+    let cells_to_write = (buffer_size.width - x) as u32;
 
-    // clear until the current line
-    clear_winapi(start_location, cells_to_write, current_attribute)?;
-
-    // put the cursor back at original cursor position before we did the clearing
-    cursor::sys::move_to(x as u16, y as u16)?;
-    Ok(())
+    // clear until the current line, filling the cells doesn't move the cursor
+    clear_winapi(start_location, cells_to_write, current_attribute)
 }
 
 fn clear_winapi(start_location: Coord, cells_to_write: u32, current_attribute: u16) -> Result<()> {
@@ -326,51 +630,25 @@ mod tests {
         assert_eq!((width, height), size().unwrap());
     }
 
-    // Test is disabled, because it's failing on Travis CI
     #[test]
-    #[ignore]
-    fn test_scroll_down_winapi() {
-        let current_window = ScreenBuffer::current()
-            .unwrap()
-            .info()
-            .unwrap()
-            .terminal_window();
-
+    fn test_scroll_keeps_window_winapi() {
+        let window = || {
+            ScreenBuffer::current()
+                .unwrap()
+                .info()
+                .unwrap()
+                .terminal_window()
+        };
+        let current_window = window();
+
+        // The content is scrolled, not the window
         scroll_down(2).unwrap();
-
-        let new_window = ScreenBuffer::current()
-            .unwrap()
-            .info()
-            .unwrap()
-            .terminal_window();
-
-        assert_eq!(new_window.top, current_window.top + 2);
-        assert_eq!(new_window.bottom, current_window.bottom + 2);
-    }
-
-    // Test is disabled, because it's failing on Travis CI
-    #[test]
-    #[ignore]
-    fn test_scroll_up_winapi() {
-        // move the terminal buffer down before moving it up
-        test_scroll_down_winapi();
-
-        let current_window = ScreenBuffer::current()
-            .unwrap()
-            .info()
-            .unwrap()
-            .terminal_window();
-
         scroll_up(2).unwrap();
+        scroll_up(u16::MAX).unwrap();
 
-        let new_window = ScreenBuffer::current()
-            .unwrap()
-            .info()
-            .unwrap()
-            .terminal_window();
-
-        assert_eq!(new_window.top, current_window.top - 2);
-        assert_eq!(new_window.bottom, current_window.bottom - 2);
+        let new_window = window();
+        assert_eq!(new_window.top, current_window.top);
+        assert_eq!(new_window.bottom, current_window.bottom);
     }
 
     #[test]
@@ -378,7 +656,7 @@ mod tests {
         let test_title = "this is a crossterm test title";
         set_window_title(test_title).unwrap();
 
-        let mut raw = [0 as u16; 128];
+        let mut raw = [0u16; 128];
         let length = unsafe { GetConsoleTitleW(raw.as_mut_ptr(), raw.len() as u32) } as usize;
         assert_ne!(0, length);
 
@@ -0,0 +1,153 @@
+//! An in-memory writer for testing the emitted escape codes.
+
+use std::{borrow::Cow, io};
+
+use crate::Command;
+
+/// A writer which captures the bytes written to it, e.g. to test that an application emits the
+/// expected commands without a terminal.
+///
+/// The commands can be queued and executed on it like on the standard output. Call
+/// [`set_plain_output(false)`](fn.set_plain_output.html) first, the escape codes are dropped by
+/// default if the standard output is not a terminal (e.g. in a CI job).
+///
+/// # Examples
+///
+/// ```
+/// use crossterm::{
+///     cursor::MoveTo,
+///     execute, queue,
+///     style::Print,
+///     terminal::{Clear, ClearType},
+///     set_plain_output, Result, TestBackend,
+/// };
+///
+/// fn draw(backend: &mut TestBackend) -> Result<()> {
+///     queue!(backend, Clear(ClearType::All), MoveTo(0, 0))?;
+///     execute!(backend, Print("Hello"))
+/// }
+///
+/// fn main() -> Result<()> {
+///     set_plain_output(false);
+///     let mut backend = TestBackend::new();
+///     draw(&mut backend)?;
+///
+///     # #[cfg(unix)]
+///     # {
+///     assert!(backend.contains(MoveTo(0, 0)));
+///     assert!(backend.output().ends_with("Hello"));
+///     assert_eq!(backend.flushed(), backend.bytes());
+///     # }
+///     Ok(())
+/// }
+/// ```
+///
+/// # Notes
+///
+/// On Windows versions lower than 10 the commands don't have an ANSI representation, they're
+/// executed on the current console instead of being written to the backend.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TestBackend {
+    buffer: Vec<u8>,
+    flushed: usize,
+}
+
+impl TestBackend {
+    /// Creates an empty backend.
+    pub fn new() -> TestBackend {
+        TestBackend::default()
+    }
+
+    /// Returns all the written bytes.
+    pub fn bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Returns the written bytes which were flushed.
+    pub fn flushed(&self) -> &[u8] {
+        &self.buffer[..self.flushed]
+    }
+
+    /// Returns the written bytes as text, invalid UTF-8 sequences are replaced with `�`.
+    pub fn output(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.buffer)
+    }
+
+    /// Returns whether the ANSI representation of the given command was written.
+    pub fn contains(&self, command: impl Command) -> bool {
+        let mut ansi = String::new();
+        if command.write_ansi(&mut ansi).is_err() {
+            return false;
+        }
+
+        let ansi = ansi.as_bytes();
+        ansi.is_empty() || self.buffer.windows(ansi.len()).any(|bytes| bytes == ansi)
+    }
+
+    /// Removes all the written bytes.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.flushed = 0;
+    }
+}
+
+impl io::Write for TestBackend {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushed = self.buffer.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::TestBackend;
+
+    #[test]
+    fn test_output() {
+        let mut backend = TestBackend::new();
+        backend.write_all(b"foo\xFFbar").unwrap();
+
+        assert_eq!(backend.bytes(), b"foo\xFFbar");
+        assert_eq!(backend.output(), "foo\u{FFFD}bar");
+    }
+
+    #[test]
+    fn test_flushed() {
+        let mut backend = TestBackend::new();
+        backend.write_all(b"foo").unwrap();
+        assert!(backend.flushed().is_empty());
+
+        backend.flush().unwrap();
+        backend.write_all(b"bar").unwrap();
+        assert_eq!(backend.flushed(), b"foo");
+
+        backend.clear();
+        assert!(backend.bytes().is_empty());
+        assert!(backend.flushed().is_empty());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_contains() {
+        use crate::{
+            cursor::{MoveTo, MoveUp},
+            queue,
+            style::Print,
+        };
+
+        let mut backend = TestBackend::new();
+        queue!(backend, MoveTo(1, 2), Print("text")).unwrap();
+
+        assert!(backend.contains(MoveTo(1, 2)));
+        assert!(backend.contains(Print("ex")));
+        assert!(!backend.contains(MoveTo(2, 1)));
+        assert!(!backend.contains(MoveUp(1)));
+    }
+}